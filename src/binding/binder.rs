@@ -0,0 +1,1209 @@
+use std::collections::HashMap;
+
+use crate::{
+    diagnostics::{Diagnostics, ErrorKind, Lint},
+    evaluator::Value,
+    lexer::source_text::TextSpan,
+    symbols::{FunctionSymbol, TypeSymbol, VariableKind, VariableSymbol},
+    syntax::{
+        AttributedStatementSyntax, BlockStatementSyntax, CallExpressionSyntax, ElseBodySyntax,
+        ExpressionId, ExpressionSyntax, FunctionDeclarationSyntax, IfStatementSyntax,
+        ReturnStatementSyntax, StatementId, StatementSyntax, SyntaxArena, SyntaxKind, SyntaxToken,
+        SyntaxTree, Tokens, TypeClauseSyntax, WhileStatementSyntax,
+    },
+};
+
+use super::{
+    control_flow::{check_control_flow, ControlFlowGraph},
+    data_flow::check_variables,
+    operators::{binary_operator_type, common_type, unary_operator_type},
+    BoundArrayLiteralExpression, BoundAssignmentExpression, BoundBinaryExpression,
+    BoundBlockStatement, BoundBreakStatement, BoundCallExpression, BoundCastExpression,
+    BoundCompoundAssignmentExpression, BoundConditionalExpression, BoundContinueStatement,
+    BoundErrorExpression, BoundErrorStatement, BoundExpression, BoundExpressionStatement,
+    BoundIfStatement, BoundIndexExpression, BoundLiteralExpression, BoundMemberAccessExpression,
+    BoundObjectLiteralExpression, BoundReturnStatement, BoundStatement, BoundUnaryExpression,
+    BoundUpdateExpression, BoundVariableDeclaration, BoundVariableExpression, BoundWhileStatement,
+};
+
+/// One bound input: its statements, the variables and functions it declares
+/// at the top level, the bodies of those functions and the errors binding
+/// and type checking found.
+pub struct BoundGlobalScope {
+    pub statements: Vec<BoundStatement>,
+    pub variables: Vec<VariableSymbol>,
+    pub functions: Vec<FunctionSymbol>,
+    pub function_bodies: HashMap<FunctionSymbol, BoundBlockStatement>,
+    pub diagnostics: Diagnostics,
+}
+
+impl BoundGlobalScope {
+    /// The type of the innermost expression containing the byte offset
+    /// `position`, for tools such as hover.
+    pub fn type_at(&self, position: usize) -> Option<TypeSymbol> {
+        let mut expressions = Vec::new();
+        let mut statements: Vec<&BoundStatement> = self.statements.iter().collect();
+        for body in self.function_bodies.values() {
+            statements.extend(&body.statements);
+        }
+        while let Some(statement) = statements.pop() {
+            match statement {
+                BoundStatement::BoundBlockStatement(block) => {
+                    statements.extend(&block.statements);
+                }
+                BoundStatement::BoundExpressionStatement(statement) => {
+                    expressions.push(&statement.expression);
+                }
+                BoundStatement::BoundVariableDeclaration(declaration) => {
+                    expressions.extend(&declaration.initializer);
+                }
+                BoundStatement::BoundIfStatement(statement) => {
+                    expressions.push(&statement.condition);
+                    statements.extend(&statement.body.statements);
+                    statements.extend(statement.else_body.as_deref());
+                }
+                BoundStatement::BoundReturnStatement(statement) => {
+                    expressions.extend(&statement.expression);
+                }
+                BoundStatement::BoundWhileStatement(statement) => {
+                    expressions.push(&statement.condition);
+                    statements.extend(&statement.body.statements);
+                }
+                BoundStatement::BoundBreakStatement(_)
+                | BoundStatement::BoundContinueStatement(_)
+                | BoundStatement::BoundErrorStatement(_) => {}
+            }
+        }
+
+        let contains = |expression: &BoundExpression| {
+            let span = expression.span();
+            span.get_start() <= position && position < span.get_end()
+        };
+        let mut found = expressions
+            .into_iter()
+            .find(|expression| contains(expression))?;
+        while let Some(child) = found.children().into_iter().find(|child| contains(child)) {
+            found = child;
+        }
+        Some(found.type_symbol())
+    }
+}
+
+/// Binds `tree` on top of the global variables and functions of earlier
+/// inputs, which it can use and shadow. Every identifier is resolved to the
+/// symbol of the declaration it names and every expression's type is
+/// inferred, so a use of an undeclared variable or an operator applied to
+/// the wrong types is reported before anything runs.
+///
+/// With `check_types` off, type errors are not reported: variables without
+/// an annotation take any value, and operators and annotations are checked
+/// when they run instead.
+pub fn bind_global_scope(
+    previous_variables: &[VariableSymbol],
+    previous_functions: &[FunctionSymbol],
+    tree: &SyntaxTree,
+    check_types: bool,
+) -> BoundGlobalScope {
+    let mut binder = Binder {
+        arena: tree.arena(),
+        previous_variables,
+        previous_functions,
+        scopes: vec![Vec::new()],
+        functions: Vec::new(),
+        function: None,
+        loop_depth: 0,
+        allowed: Vec::new(),
+        check_types,
+        diagnostics: Diagnostics::new(tree.full_text()),
+    };
+
+    // Functions are declared before anything is bound, so they can be
+    // called above their declaration and from each other.
+    let arena = tree.arena();
+    let declarations: Vec<(&FunctionDeclarationSyntax, FunctionSymbol)> = tree
+        .root()
+        .statements
+        .iter()
+        .filter_map(|statement| declared_function(arena, *statement))
+        .map(|declaration| (declaration, binder.declare_function(declaration)))
+        .collect();
+
+    let mut function_bodies = HashMap::new();
+    let mut statements = Vec::new();
+    let mut declarations = declarations.into_iter();
+    for statement in &tree.root().statements {
+        match declared_function(arena, *statement) {
+            Some(declaration) => {
+                if let StatementSyntax::AttributedStatementSyntax(statement) = &arena[*statement] {
+                    binder.bind_attributes(statement);
+                }
+                let (_, function) = declarations
+                    .next()
+                    .expect("every function was declared above");
+                let body = binder.bind_function_body(declaration, &function);
+                function_bodies.insert(function, body);
+            }
+            None => statements.push(binder.bind_statement(*statement)),
+        }
+    }
+    let variables = binder
+        .scopes
+        .pop()
+        .expect("the global scope is never popped");
+    let graph = ControlFlowGraph::build(&statements);
+    check_control_flow(&graph, &mut binder.diagnostics);
+    check_variables(&graph, &[], &variables, &mut binder.diagnostics);
+    for (lint, span) in binder.allowed {
+        binder.diagnostics.allow(lint, span);
+    }
+    BoundGlobalScope {
+        statements,
+        variables,
+        functions: binder.functions,
+        function_bodies,
+        diagnostics: binder.diagnostics,
+    }
+}
+
+struct Binder<'a> {
+    arena: &'a SyntaxArena,
+    previous_variables: &'a [VariableSymbol],
+    previous_functions: &'a [FunctionSymbol],
+    /// The variables declared in each enclosing scope, innermost last. The
+    /// first one is the input's global scope.
+    scopes: Vec<Vec<VariableSymbol>>,
+    /// The functions this input declares.
+    functions: Vec<FunctionSymbol>,
+    /// The function whose body is being bound, if any.
+    function: Option<FunctionSymbol>,
+    /// How many loops enclose the statement being bound, inside the current
+    /// function.
+    loop_depth: usize,
+    /// The lints `@allow` turns off and the statements it turns them off
+    /// for.
+    allowed: Vec<(Lint, TextSpan)>,
+    check_types: bool,
+    diagnostics: Diagnostics,
+}
+
+impl Binder<'_> {
+    /// Declares a variable in the innermost scope. A name can be declared
+    /// once per scope; a second declaration is reported and gives the
+    /// variable already declared, so uses of either are uses of the same
+    /// variable. It can shadow a variable of an outer scope or an earlier
+    /// input, unless that variable is a constant. Shadowing a variable of
+    /// this input is reported by the shadowing lint.
+    fn declare(
+        &mut self,
+        identifier: &SyntaxToken,
+        type_symbol: TypeSymbol,
+        kind: VariableKind,
+    ) -> VariableSymbol {
+        let name = identifier.text();
+        let scope = self.scopes.last().expect("there is always a scope");
+        if let Some(declared) = scope.iter().find(|declared| declared.name() == name) {
+            let declared = declared.clone();
+            self.diagnostics.add_error(ErrorKind::DuplicateDeclaration {
+                name: name.to_string(),
+                position: identifier.position(),
+            });
+            return declared;
+        }
+        let variable = VariableSymbol::new(name, type_symbol, kind);
+        self.check_not_constant(identifier);
+        let outer_scopes = &self.scopes[..self.scopes.len() - 1];
+        let shadowed = outer_scopes
+            .iter()
+            .rev()
+            .flatten()
+            .find(|declared| declared.name() == name);
+        if shadowed.is_some_and(|shadowed| shadowed.kind() != VariableKind::Constant) {
+            self.diagnostics.add_lint(
+                Lint::Shadowing,
+                ErrorKind::ShadowedVariable {
+                    name: name.to_string(),
+                    position: identifier.position(),
+                },
+            );
+        }
+        self.scopes
+            .last_mut()
+            .expect("there is always a scope")
+            .push(variable.clone());
+        variable
+    }
+
+    /// Reports a declaration that would shadow a constant.
+    fn check_not_constant(&mut self, identifier: &SyntaxToken) {
+        let shadows_constant = self
+            .lookup(identifier.text())
+            .is_some_and(|variable| variable.kind() == VariableKind::Constant);
+        if shadows_constant {
+            self.diagnostics.add_error(ErrorKind::ShadowedConstant {
+                name: identifier.text().to_string(),
+                position: identifier.position(),
+            });
+        }
+    }
+
+    /// Finds the variable `name` refers to, searching from the innermost
+    /// scope out and then through earlier inputs, latest first.
+    fn lookup(&self, name: &str) -> Option<&VariableSymbol> {
+        self.scopes
+            .iter()
+            .rev()
+            .flat_map(|scope| scope.iter())
+            .chain(self.previous_variables.iter().rev())
+            .find(|variable| variable.name() == name)
+    }
+
+    /// Finds the function `name` refers to, in this input or an earlier one.
+    fn lookup_function(&self, name: &str) -> Option<&FunctionSymbol> {
+        self.functions
+            .iter()
+            .chain(self.previous_functions.iter().rev())
+            .find(|function| function.name() == name)
+    }
+
+    /// Makes the symbol for a function from its signature. Parameters and
+    /// the return type without an annotation take any value.
+    fn declare_function(&mut self, declaration: &FunctionDeclarationSyntax) -> FunctionSymbol {
+        let mut parameters: Vec<VariableSymbol> = Vec::new();
+        for parameter in declaration.parameters.iter() {
+            if parameters
+                .iter()
+                .any(|declared| declared.name() == parameter.name())
+            {
+                self.diagnostics.add_error(ErrorKind::DuplicateDeclaration {
+                    name: parameter.name().to_string(),
+                    position: parameter.identifier.position(),
+                });
+                continue;
+            }
+            let type_symbol = self.bind_optional_type_clause(parameter.type_clause.as_ref());
+            parameters.push(VariableSymbol::new(
+                parameter.name(),
+                type_symbol,
+                VariableKind::Immutable,
+            ));
+        }
+        let return_type = self.bind_optional_type_clause(declaration.return_type.as_ref());
+        let function = FunctionSymbol::new(declaration.name(), parameters, return_type);
+
+        if self.lookup_function_in_input(declaration.name()) {
+            self.diagnostics.add_error(ErrorKind::DuplicateDeclaration {
+                name: declaration.name().to_string(),
+                position: declaration.identifier.position(),
+            });
+        } else {
+            self.functions.push(function.clone());
+        }
+        function
+    }
+
+    fn lookup_function_in_input(&self, name: &str) -> bool {
+        self.functions
+            .iter()
+            .any(|function| function.name() == name)
+    }
+
+    fn bind_function_body(
+        &mut self,
+        declaration: &FunctionDeclarationSyntax,
+        function: &FunctionSymbol,
+    ) -> BoundBlockStatement {
+        for parameter in declaration.parameters.iter() {
+            self.check_not_constant(&parameter.identifier);
+        }
+        self.function = Some(function.clone());
+        self.scopes.push(function.parameters().to_vec());
+        // A `break` in a function body cannot leave a loop of the code that
+        // called it.
+        let loop_depth = std::mem::replace(&mut self.loop_depth, 0);
+        let body = self.bind_block_statement(&declaration.body);
+        self.loop_depth = loop_depth;
+        self.scopes.pop();
+        self.function = None;
+
+        let graph = ControlFlowGraph::build(&body.statements);
+        let falls_through = check_control_flow(&graph, &mut self.diagnostics);
+        if falls_through && declaration.return_type.is_some() {
+            self.diagnostics.add_error(ErrorKind::MissingReturn {
+                name: function.name().to_string(),
+                position: declaration.body.close_brace.position(),
+            });
+        }
+        let parameters: Vec<(VariableSymbol, usize)> = function
+            .parameters()
+            .iter()
+            .filter_map(|parameter| {
+                let syntax = declaration
+                    .parameters
+                    .iter()
+                    .find(|syntax| syntax.name() == parameter.name())?;
+                Some((parameter.clone(), syntax.identifier.position()))
+            })
+            .collect();
+        check_variables(&graph, &parameters, &[], &mut self.diagnostics);
+        body
+    }
+
+    /// The type an annotation names. An unknown name is reported and gives
+    /// the error type, so nothing checked against it is reported again.
+    fn bind_type_clause(&mut self, type_clause: &TypeClauseSyntax) -> TypeSymbol {
+        match TypeSymbol::lookup(type_clause.type_name()) {
+            Some(type_symbol) if type_clause.is_nullable() => type_symbol.nullable(),
+            Some(type_symbol) => type_symbol,
+            None => {
+                self.diagnostics.add_error(ErrorKind::UnknownType {
+                    name: type_clause.type_name().to_string(),
+                    position: type_clause.type_token.position(),
+                });
+                TypeSymbol::Error
+            }
+        }
+    }
+
+    fn bind_optional_type_clause(&mut self, type_clause: Option<&TypeClauseSyntax>) -> TypeSymbol {
+        match type_clause {
+            Some(type_clause) => self.bind_type_clause(type_clause),
+            None => TypeSymbol::Any,
+        }
+    }
+
+    fn bind_statement(&mut self, id: StatementId) -> BoundStatement {
+        let arena = self.arena;
+        let span = arena[id].span();
+        match &arena[id] {
+            StatementSyntax::BlockStatementSyntax(block) => {
+                BoundStatement::BoundBlockStatement(self.bind_block_statement(block))
+            }
+            StatementSyntax::ExpressionStatementSyntax(statement) => {
+                BoundStatement::BoundExpressionStatement(BoundExpressionStatement {
+                    expression: self.bind_expression(statement.expression),
+                    span,
+                })
+            }
+            StatementSyntax::VariableDeclarationStatementSyntax(declaration) => {
+                // The initializer is bound first, so `let x = x + 1` reads the
+                // `x` from an outer scope or an earlier input.
+                let initializer = declaration
+                    .initializer
+                    .as_ref()
+                    .map(|initializer| self.bind_expression(initializer.expression));
+                let type_symbol = match (&declaration.type_clause, &initializer) {
+                    (Some(type_clause), initializer) => {
+                        let type_symbol = self.bind_type_clause(type_clause);
+                        if let Some(initializer) = initializer {
+                            self.check_assignable(
+                                type_symbol,
+                                initializer.type_symbol(),
+                                initializer.span().get_start(),
+                            );
+                        }
+                        type_symbol
+                    }
+                    (None, _) if !self.check_types => TypeSymbol::Any,
+                    (None, None) => TypeSymbol::Any,
+                    // A variable that starts out null could later hold
+                    // anything.
+                    (None, Some(initializer)) => match initializer.type_symbol() {
+                        TypeSymbol::Null => TypeSymbol::Any,
+                        type_symbol => type_symbol,
+                    },
+                };
+                // Only a variable that can be assigned later can start
+                // without a value.
+                if initializer.is_none() && !declaration.is_mutable() {
+                    self.diagnostics.add_error(ErrorKind::MissingInitializer {
+                        name: declaration.name().to_string(),
+                        position: declaration.identifier.position(),
+                    });
+                }
+                let kind = if declaration.is_const() {
+                    let non_constant = initializer
+                        .as_ref()
+                        .filter(|initializer| !is_constant(initializer));
+                    if let Some(initializer) = non_constant {
+                        self.diagnostics
+                            .add_error(ErrorKind::NonConstantInitializer {
+                                name: declaration.name().to_string(),
+                                position: initializer.span().get_start(),
+                            });
+                    }
+                    VariableKind::Constant
+                } else if declaration.is_mutable() {
+                    VariableKind::Mutable
+                } else {
+                    VariableKind::Immutable
+                };
+                let variable = self.declare(&declaration.identifier, type_symbol, kind);
+                BoundStatement::BoundVariableDeclaration(BoundVariableDeclaration {
+                    variable,
+                    initializer,
+                    span,
+                })
+            }
+            StatementSyntax::IfStatementSyntax(statement) => {
+                BoundStatement::BoundIfStatement(self.bind_if_statement(statement))
+            }
+            StatementSyntax::FunctionDeclarationSyntax(declaration) => {
+                self.diagnostics
+                    .add_error(ErrorKind::MisplacedFunctionDeclaration {
+                        position: declaration.fn_keyword.position(),
+                    });
+                BoundStatement::BoundErrorStatement(BoundErrorStatement {
+                    span: declaration.span,
+                })
+            }
+            StatementSyntax::ReturnStatementSyntax(statement) => {
+                self.bind_return_statement(statement)
+            }
+            StatementSyntax::WhileStatementSyntax(statement) => {
+                self.bind_while_statement(statement)
+            }
+            StatementSyntax::BreakStatementSyntax(statement) => {
+                if !self.check_in_loop(&statement.keyword) {
+                    return BoundStatement::BoundErrorStatement(BoundErrorStatement { span });
+                }
+                BoundStatement::BoundBreakStatement(BoundBreakStatement { span })
+            }
+            StatementSyntax::ContinueStatementSyntax(statement) => {
+                if !self.check_in_loop(&statement.keyword) {
+                    return BoundStatement::BoundErrorStatement(BoundErrorStatement { span });
+                }
+                BoundStatement::BoundContinueStatement(BoundContinueStatement { span })
+            }
+            StatementSyntax::AttributedStatementSyntax(statement) => {
+                self.bind_attributes(statement);
+                self.bind_statement(statement.statement)
+            }
+            StatementSyntax::ErrorStatementSyntax(statement) => {
+                BoundStatement::BoundErrorStatement(BoundErrorStatement {
+                    span: statement.span,
+                })
+            }
+        }
+    }
+
+    /// Checks the attributes of a statement. `@allow(name, ...)` turns the
+    /// named lints off for the statement and everything in it.
+    fn bind_attributes(&mut self, statement: &AttributedStatementSyntax) {
+        // A missing name was already reported by the parser.
+        for attribute in &statement.attributes {
+            if attribute.name().is_empty() {
+                continue;
+            }
+            if attribute.name() != "allow" {
+                self.diagnostics.add_error(ErrorKind::UnknownAttribute {
+                    name: attribute.name().to_string(),
+                    position: attribute.identifier.position(),
+                });
+                continue;
+            }
+            for argument in attribute.arguments.iter() {
+                let name = argument.name();
+                if argument.tokens.iter().any(|token| token.text().is_empty()) {
+                    continue;
+                }
+                match name.parse::<Lint>() {
+                    Ok(lint) => self.allowed.push((lint, statement.span)),
+                    Err(()) => self.diagnostics.add_error(ErrorKind::UnknownLint {
+                        name,
+                        position: argument.span.get_start(),
+                    }),
+                }
+            }
+        }
+    }
+
+    fn bind_return_statement(&mut self, statement: &ReturnStatementSyntax) -> BoundStatement {
+        let expression = statement
+            .expression
+            .map(|expression| self.bind_expression(expression));
+        let Some(function) = &self.function else {
+            self.diagnostics
+                .add_error(ErrorKind::ReturnOutsideFunction {
+                    position: statement.return_keyword.position(),
+                });
+            return BoundStatement::BoundErrorStatement(BoundErrorStatement {
+                span: statement.span,
+            });
+        };
+        let return_type = function.return_type();
+        match &expression {
+            Some(expression) => self.check_assignable(
+                return_type,
+                expression.type_symbol(),
+                expression.span().get_start(),
+            ),
+            None => self.check_assignable(
+                return_type,
+                TypeSymbol::Null,
+                statement.return_keyword.position(),
+            ),
+        }
+        BoundStatement::BoundReturnStatement(BoundReturnStatement {
+            expression,
+            span: statement.span,
+        })
+    }
+
+    fn bind_while_statement(&mut self, statement: &WhileStatementSyntax) -> BoundStatement {
+        let condition = self.bind_condition(statement.condition);
+        // A literal condition is left to the control flow check, which
+        // reports a loop that never runs or never ends.
+        if !matches!(condition, BoundExpression::BoundLiteralExpression(_)) {
+            self.check_constant_condition(&condition);
+        }
+        self.loop_depth += 1;
+        let body = self.bind_block_statement(&statement.body);
+        self.loop_depth -= 1;
+        BoundStatement::BoundWhileStatement(BoundWhileStatement {
+            condition,
+            body,
+            span: statement.span,
+        })
+    }
+
+    /// Reports a `break` or `continue` that is not inside a loop.
+    fn check_in_loop(&mut self, keyword: &SyntaxToken) -> bool {
+        if self.loop_depth == 0 {
+            self.diagnostics
+                .add_error(ErrorKind::LoopControlOutsideLoop {
+                    keyword: keyword.text().to_string(),
+                    position: keyword.position(),
+                });
+        }
+        self.loop_depth > 0
+    }
+
+    fn bind_block_statement(&mut self, block: &BlockStatementSyntax) -> BoundBlockStatement {
+        self.scopes.push(Vec::new());
+        let statements = block
+            .statements
+            .iter()
+            .map(|statement| self.bind_statement(*statement))
+            .collect();
+        self.scopes.pop();
+        BoundBlockStatement {
+            statements,
+            span: block.span,
+        }
+    }
+
+    fn bind_if_statement(&mut self, statement: &IfStatementSyntax) -> BoundIfStatement {
+        let condition = self.bind_condition(statement.condition);
+        self.check_constant_condition(&condition);
+        let body = self.bind_block_statement(&statement.body);
+        let else_body = statement.else_clause.as_ref().map(|else_clause| {
+            Box::new(match &else_clause.body {
+                ElseBodySyntax::Block(block) => {
+                    BoundStatement::BoundBlockStatement(self.bind_block_statement(block))
+                }
+                ElseBodySyntax::If(statement) => {
+                    BoundStatement::BoundIfStatement(self.bind_if_statement(statement))
+                }
+            })
+        });
+        BoundIfStatement {
+            condition,
+            body,
+            else_body,
+            span: statement.span,
+        }
+    }
+
+    /// Binds the condition of an `if`, `while` or `?:`, which must be a bool.
+    fn bind_condition(&mut self, id: ExpressionId) -> BoundExpression {
+        let condition = self.bind_expression(id);
+        match condition.type_symbol() {
+            TypeSymbol::Bool | TypeSymbol::Any | TypeSymbol::Error => condition,
+            _ if !self.check_types => condition,
+            found => {
+                self.diagnostics.add_error(ErrorKind::InvalidCondition {
+                    found: found.name(),
+                    position: condition.span().get_start(),
+                });
+                BoundExpression::BoundErrorExpression(BoundErrorExpression {
+                    span: condition.span(),
+                })
+            }
+        }
+    }
+
+    /// Reports a condition whose value is known before anything runs, so the
+    /// same branch is always taken.
+    fn check_constant_condition(&mut self, condition: &BoundExpression) {
+        if is_constant(condition) && !contains_error(condition) {
+            self.diagnostics.add_lint(
+                Lint::ConstantCondition,
+                ErrorKind::ConstantCondition {
+                    position: condition.span().get_start(),
+                },
+            );
+        }
+    }
+
+    /// Reports an assignment to a variable not declared with `let mut`.
+    /// Elements and fields can always be assigned: an immutable variable
+    /// holding an array always holds the same array, but the array itself
+    /// can change.
+    fn check_mutable(&mut self, target: &BoundExpression) {
+        if let BoundExpression::BoundVariableExpression(target) = target {
+            if !target.variable.is_mutable() {
+                self.diagnostics.add_error(ErrorKind::ImmutableAssignment {
+                    name: target.variable.name().to_string(),
+                    position: target.span.get_start(),
+                });
+            }
+        }
+    }
+
+    /// Reports a value of type `found` stored where `expected` is declared.
+    fn check_assignable(&mut self, expected: TypeSymbol, found: TypeSymbol, position: usize) {
+        if self.check_types && !expected.accepts(found) {
+            self.diagnostics.add_error(ErrorKind::TypeMismatch {
+                expected: expected.name(),
+                found: found.name(),
+                position,
+            });
+        }
+    }
+
+    /// The type of a binary operator applied to `left` and `right`, or
+    /// `None` after reporting that it does not apply. Operands that already
+    /// failed to bind give `None` without another report. Without type
+    /// checking an operator that does not apply gives `any`, and fails when
+    /// it runs.
+    fn bind_operator_type(
+        &mut self,
+        operator: &SyntaxKind,
+        operator_token: &SyntaxToken,
+        left: TypeSymbol,
+        right: TypeSymbol,
+    ) -> Option<TypeSymbol> {
+        if left == TypeSymbol::Error || right == TypeSymbol::Error {
+            return None;
+        }
+        let type_symbol = binary_operator_type(operator, left, right);
+        if type_symbol.is_none() && !self.check_types {
+            return Some(TypeSymbol::Any);
+        }
+        if type_symbol.is_none() {
+            self.diagnostics
+                .add_error(ErrorKind::InvalidBinaryOperands {
+                    operator: operator_token.text().to_string(),
+                    left: left.name(),
+                    right: right.name(),
+                    position: operator_token.position(),
+                });
+        }
+        type_symbol
+    }
+
+    fn bind_expression(&mut self, id: ExpressionId) -> BoundExpression {
+        let arena = self.arena;
+        let span = arena[id].span();
+        match &arena[id] {
+            ExpressionSyntax::LiteralExpressionSyntax(expression) => {
+                let value = Value::from_literal(&expression.value);
+                BoundExpression::BoundLiteralExpression(BoundLiteralExpression { value, span })
+            }
+            ExpressionSyntax::IdentifierExpressionSyntax(expression) => {
+                match self.lookup(expression.name()) {
+                    Some(variable) => {
+                        BoundExpression::BoundVariableExpression(BoundVariableExpression {
+                            variable: variable.clone(),
+                            span,
+                        })
+                    }
+                    None => {
+                        self.diagnostics.add_error(ErrorKind::UndefinedVariable {
+                            name: expression.name().to_string(),
+                            position: span.get_start(),
+                        });
+                        BoundExpression::BoundErrorExpression(BoundErrorExpression { span })
+                    }
+                }
+            }
+            ExpressionSyntax::ParenthesizedExpressionSyntax(expression) => {
+                self.bind_expression(expression.expression)
+            }
+            ExpressionSyntax::BinaryExpressionSyntax(expression) => {
+                let left = self.bind_expression(expression.left);
+                let right = self.bind_expression(expression.right);
+                let is_float = |type_symbol| {
+                    matches!(
+                        type_symbol,
+                        TypeSymbol::Float | TypeSymbol::Nullable(TypeSymbol::Float)
+                    )
+                };
+                if matches!(
+                    expression.operator.kind(),
+                    SyntaxKind::Token(Tokens::EqualsEqualsToken | Tokens::BangEqualsToken)
+                ) && (is_float(left.type_symbol()) || is_float(right.type_symbol()))
+                {
+                    self.diagnostics.add_lint(
+                        Lint::FloatEquality,
+                        ErrorKind::FloatEquality {
+                            position: expression.operator.position(),
+                        },
+                    );
+                }
+                let Some(type_symbol) = self.bind_operator_type(
+                    expression.operator.kind(),
+                    &expression.operator,
+                    left.type_symbol(),
+                    right.type_symbol(),
+                ) else {
+                    return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+                };
+                BoundExpression::BoundBinaryExpression(BoundBinaryExpression {
+                    left: Box::new(left),
+                    operator: expression.operator.clone(),
+                    right: Box::new(right),
+                    type_symbol,
+                    span,
+                })
+            }
+            ExpressionSyntax::UnaryExpressionSyntax(expression) => {
+                let operand = self.bind_expression(expression.operand);
+                if operand.type_symbol() == TypeSymbol::Error {
+                    return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+                }
+                let type_symbol =
+                    unary_operator_type(expression.operator.kind(), operand.type_symbol())
+                        .or((!self.check_types).then_some(TypeSymbol::Any));
+                let Some(type_symbol) = type_symbol else {
+                    self.diagnostics.add_error(ErrorKind::InvalidUnaryOperand {
+                        operator: expression.operator.text().to_string(),
+                        operand: operand.type_symbol().name(),
+                        position: expression.operator.position(),
+                    });
+                    return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+                };
+                BoundExpression::BoundUnaryExpression(BoundUnaryExpression {
+                    operator: expression.operator.clone(),
+                    operand: Box::new(operand),
+                    type_symbol,
+                    span,
+                })
+            }
+            ExpressionSyntax::AssignmentExpressionSyntax(expression) => {
+                let target = self.bind_expression(expression.target);
+                let value = self.bind_expression(expression.value);
+                self.check_mutable(&target);
+                self.check_assignable(
+                    target.type_symbol(),
+                    value.type_symbol(),
+                    value.span().get_start(),
+                );
+                BoundExpression::BoundAssignmentExpression(BoundAssignmentExpression {
+                    target: Box::new(target),
+                    value: Box::new(value),
+                    span,
+                })
+            }
+            ExpressionSyntax::CompoundAssignmentExpressionSyntax(expression) => {
+                let target = self.bind_expression(expression.target);
+                let value = self.bind_expression(expression.value);
+                self.check_mutable(&target);
+                // `x += 1` stores `x + 1` back into `x`, so the result must
+                // still have `x`'s type.
+                if let Some(result) = self.bind_operator_type(
+                    &SyntaxKind::Token(expression.binary_operator.clone()),
+                    &expression.operator_token,
+                    target.type_symbol(),
+                    value.type_symbol(),
+                ) {
+                    self.check_assignable(
+                        target.type_symbol(),
+                        result,
+                        expression.operator_token.position(),
+                    );
+                }
+                BoundExpression::BoundCompoundAssignmentExpression(
+                    BoundCompoundAssignmentExpression {
+                        target: Box::new(target),
+                        operator_token: expression.operator_token.clone(),
+                        binary_operator: expression.binary_operator.clone(),
+                        value: Box::new(value),
+                        span,
+                    },
+                )
+            }
+            ExpressionSyntax::UpdateExpressionSyntax(expression) => {
+                let operand = self.bind_expression(expression.operand);
+                self.check_mutable(&operand);
+                if let Some(result) = self.bind_operator_type(
+                    &SyntaxKind::Token(expression.binary_operator.clone()),
+                    &expression.operator_token,
+                    operand.type_symbol(),
+                    TypeSymbol::Int,
+                ) {
+                    self.check_assignable(
+                        operand.type_symbol(),
+                        result,
+                        expression.operator_token.position(),
+                    );
+                }
+                BoundExpression::BoundUpdateExpression(BoundUpdateExpression {
+                    operator_token: expression.operator_token.clone(),
+                    binary_operator: expression.binary_operator.clone(),
+                    operand: Box::new(operand),
+                    is_prefix: expression.is_prefix,
+                    span,
+                })
+            }
+            ExpressionSyntax::ConditionalExpressionSyntax(expression) => {
+                let condition = self.bind_condition(expression.condition);
+                self.check_constant_condition(&condition);
+                let when_true = self.bind_expression(expression.when_true);
+                let when_false = self.bind_expression(expression.when_false);
+                let type_symbol = common_type(when_true.type_symbol(), when_false.type_symbol());
+                BoundExpression::BoundConditionalExpression(BoundConditionalExpression {
+                    condition: Box::new(condition),
+                    when_true: Box::new(when_true),
+                    when_false: Box::new(when_false),
+                    type_symbol,
+                    span,
+                })
+            }
+            ExpressionSyntax::IndexExpressionSyntax(expression) => {
+                let target = self.bind_expression(expression.target);
+                let index = self.bind_expression(expression.index);
+                let is_conditional = expression.is_conditional();
+                let Some(type_symbol) = self.bind_index_type(&target, &index, is_conditional)
+                else {
+                    return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+                };
+                BoundExpression::BoundIndexExpression(BoundIndexExpression {
+                    target: Box::new(target),
+                    index: Box::new(index),
+                    is_conditional,
+                    type_symbol,
+                    span,
+                })
+            }
+            ExpressionSyntax::MemberAccessExpressionSyntax(expression) => {
+                let target = self.bind_expression(expression.target);
+                let is_conditional = expression.is_conditional();
+                let target_type = target.type_symbol();
+                // A missing field name was already reported by the parser.
+                let accepted = match target_type {
+                    _ if expression.name().is_empty() => true,
+                    TypeSymbol::Object | TypeSymbol::Any | TypeSymbol::Error => true,
+                    TypeSymbol::Nullable(TypeSymbol::Object) => is_conditional,
+                    _ => !self.check_types,
+                };
+                if !accepted {
+                    self.diagnostics.add_error(ErrorKind::InvalidMemberAccess {
+                        name: expression.name().to_string(),
+                        target: target_type.name(),
+                        position: span.get_start(),
+                    });
+                    return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+                }
+                BoundExpression::BoundMemberAccessExpression(BoundMemberAccessExpression {
+                    target: Box::new(target),
+                    name: expression.name().to_string(),
+                    is_conditional,
+                    span,
+                })
+            }
+            ExpressionSyntax::CastExpressionSyntax(expression) => {
+                let operand = self.bind_expression(expression.expression);
+                let Some(type_symbol) = TypeSymbol::lookup(expression.type_name()) else {
+                    self.diagnostics.add_error(ErrorKind::UnknownType {
+                        name: expression.type_name().to_string(),
+                        position: expression.type_token.position(),
+                    });
+                    return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+                };
+                // Only numbers convert, and only to other numbers.
+                let from = operand.type_symbol();
+                let convertible = from.is_numeric() || from == TypeSymbol::Any;
+                if self.check_types
+                    && from != TypeSymbol::Error
+                    && !(convertible && type_symbol.is_numeric())
+                {
+                    self.diagnostics.add_error(ErrorKind::InvalidCast {
+                        from: from.name(),
+                        to: type_symbol.name().to_string(),
+                        position: expression.as_keyword.position(),
+                    });
+                }
+                BoundExpression::BoundCastExpression(BoundCastExpression {
+                    expression: Box::new(operand),
+                    as_keyword: expression.as_keyword.clone(),
+                    type_token: expression.type_token.clone(),
+                    type_symbol,
+                    span,
+                })
+            }
+            ExpressionSyntax::CallExpressionSyntax(expression) => {
+                self.bind_call_expression(expression)
+            }
+            ExpressionSyntax::ArrayLiteralExpressionSyntax(expression) => {
+                let elements = expression
+                    .elements
+                    .iter()
+                    .map(|element| self.bind_expression(*element))
+                    .collect();
+                BoundExpression::BoundArrayLiteralExpression(BoundArrayLiteralExpression {
+                    elements,
+                    span,
+                })
+            }
+            ExpressionSyntax::ObjectLiteralExpressionSyntax(expression) => {
+                let properties = expression
+                    .properties
+                    .iter()
+                    .map(|property| {
+                        let value = self.bind_expression(property.value);
+                        (property.name().to_string(), value)
+                    })
+                    .collect();
+                BoundExpression::BoundObjectLiteralExpression(BoundObjectLiteralExpression {
+                    properties,
+                    span,
+                })
+            }
+            ExpressionSyntax::BadExpressionSyntax(_) => {
+                BoundExpression::BoundErrorExpression(BoundErrorExpression { span })
+            }
+        }
+    }
+
+    /// The type of indexing `target` with `index`, or `None` after reporting
+    /// that it does not apply. Arrays and strings take an int index and
+    /// objects a string one. A target that may be null needs `?[`. Indexing
+    /// a string gives one of its characters; anything else may hold any
+    /// value.
+    fn bind_index_type(
+        &mut self,
+        target: &BoundExpression,
+        index: &BoundExpression,
+        is_conditional: bool,
+    ) -> Option<TypeSymbol> {
+        let target_type = target.type_symbol();
+        let index_type = index.type_symbol();
+        let element_type = match target_type {
+            TypeSymbol::String => TypeSymbol::Char,
+            TypeSymbol::Nullable(TypeSymbol::String) => TypeSymbol::Char.nullable(),
+            _ => TypeSymbol::Any,
+        };
+        if !self.check_types || target_type == TypeSymbol::Error || index_type == TypeSymbol::Error
+        {
+            return Some(element_type);
+        }
+        let takes =
+            |index: TypeSymbol| matches!(index_type, TypeSymbol::Any) || index_type == index;
+        let accepted = match target_type {
+            TypeSymbol::Array | TypeSymbol::String => takes(TypeSymbol::Int),
+            TypeSymbol::Object => takes(TypeSymbol::String),
+            TypeSymbol::Nullable(TypeSymbol::Array | TypeSymbol::String) => {
+                is_conditional && takes(TypeSymbol::Int)
+            }
+            TypeSymbol::Nullable(TypeSymbol::Object) => is_conditional && takes(TypeSymbol::String),
+            TypeSymbol::Any => takes(TypeSymbol::Int) || takes(TypeSymbol::String),
+            _ => false,
+        };
+        if !accepted {
+            self.diagnostics.add_error(ErrorKind::InvalidIndex {
+                target: target_type.name(),
+                index: index_type.name(),
+                position: target.span().get_start(),
+            });
+            return None;
+        }
+        Some(element_type)
+    }
+
+    fn bind_call_expression(&mut self, expression: &CallExpressionSyntax) -> BoundExpression {
+        let span = expression.span;
+        let arguments: Vec<BoundExpression> = expression
+            .arguments
+            .iter()
+            .map(|argument| self.bind_expression(*argument))
+            .collect();
+        let Some(function) = self.lookup_function(expression.name()).cloned() else {
+            self.diagnostics.add_error(ErrorKind::UndefinedFunction {
+                name: expression.name().to_string(),
+                position: expression.identifier.position(),
+            });
+            return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+        };
+        if arguments.len() != function.parameters().len() {
+            self.diagnostics.add_error(ErrorKind::WrongArgumentCount {
+                name: function.name().to_string(),
+                expected: function.parameters().len(),
+                found: arguments.len(),
+                position: expression.identifier.position(),
+            });
+            return BoundExpression::BoundErrorExpression(BoundErrorExpression { span });
+        }
+        for (parameter, argument) in function.parameters().iter().zip(&arguments) {
+            self.check_assignable(
+                parameter.type_symbol(),
+                argument.type_symbol(),
+                argument.span().get_start(),
+            );
+        }
+        BoundExpression::BoundCallExpression(BoundCallExpression {
+            function,
+            arguments,
+            span,
+        })
+    }
+}
+
+/// The function a top-level statement declares, looking through any
+/// attributes written before it.
+fn declared_function(arena: &SyntaxArena, id: StatementId) -> Option<&FunctionDeclarationSyntax> {
+    match &arena[id] {
+        StatementSyntax::FunctionDeclarationSyntax(declaration) => Some(declaration),
+        StatementSyntax::AttributedStatementSyntax(statement) => {
+            declared_function(arena, statement.statement)
+        }
+        _ => None,
+    }
+}
+
+/// Whether an expression failed to bind somewhere.
+fn contains_error(expression: &BoundExpression) -> bool {
+    matches!(expression, BoundExpression::BoundErrorExpression(_))
+        || expression.children().into_iter().any(contains_error)
+}
+
+/// Whether an expression's value can be known before anything runs: it is
+/// made only of literals, constants and operators applied to them.
+fn is_constant(expression: &BoundExpression) -> bool {
+    match expression {
+        BoundExpression::BoundLiteralExpression(_) => true,
+        BoundExpression::BoundVariableExpression(expression) => {
+            expression.variable.kind() == VariableKind::Constant
+        }
+        BoundExpression::BoundBinaryExpression(_)
+        | BoundExpression::BoundUnaryExpression(_)
+        | BoundExpression::BoundConditionalExpression(_)
+        | BoundExpression::BoundCastExpression(_) => {
+            expression.children().into_iter().all(is_constant)
+        }
+        // Already reported, so it should not be reported again.
+        BoundExpression::BoundErrorExpression(_) => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lexer::{source_text::SourceText, Lexer},
+        parser::Parser,
+    };
+
+    fn errors(text: &str) -> Vec<ErrorKind> {
+        let tokens = Lexer::new(SourceText::new(text.to_string())).lex();
+        let tree = Parser::new(tokens, text.to_string()).parse();
+        bind_global_scope(&[], &[], &tree, true).diagnostics.errors
+    }
+
+    #[test]
+    fn valid_index_and_member_access() {
+        let inputs = [
+            "let xs = [1, 2]; xs[0]",
+            "\"ab\"[1]",
+            "let o = { a: 1 }; o[\"a\"]; o.a",
+            "let xs = [[1]]; xs[0][0]; xs[0].a",
+            "let s: string? = null; s?[0]",
+            "let o: object? = null; o?.a",
+        ];
+        for input in inputs {
+            let errors = errors(input);
+            assert!(errors.is_empty(), "{}: {:?}", input, errors);
+        }
+    }
+
+    #[test]
+    fn invalid_index() {
+        let cases = [
+            ("let xs = [1]; xs[\"a\"]", "array", "string"),
+            ("\"ab\"[true]", "string", "bool"),
+            ("let o = { a: 1 }; o[0]", "object", "int"),
+            ("let n = 5; n[0]", "int", "int"),
+            ("let s: string? = null; s[0]", "string?", "int"),
+        ];
+        for (input, expected_target, expected_index) in cases {
+            let errors = errors(input);
+            assert!(
+                matches!(
+                    errors.as_slice(),
+                    [ErrorKind::InvalidIndex { target, index, .. }]
+                        if *target == expected_target && *index == expected_index
+                ),
+                "{}: {:?}",
+                input,
+                errors
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_member_access() {
+        let cases = [
+            ("let n = 5; n.name", "int"),
+            ("let xs = [1]; xs.length", "array"),
+            ("let o: object? = null; o.a", "object?"),
+        ];
+        for (input, expected_target) in cases {
+            let errors = errors(input);
+            assert!(
+                matches!(
+                    errors.as_slice(),
+                    [ErrorKind::InvalidMemberAccess { target, .. }] if *target == expected_target
+                ),
+                "{}: {:?}",
+                input,
+                errors
+            );
+        }
+    }
+
+    #[test]
+    fn duplicate_declaration_is_the_declared_variable() {
+        for input in [
+            "let x = 1; let x = 2; x",
+            "fn f(): int { let x = 1; let x = 2; return x; }",
+        ] {
+            let tokens = Lexer::new(SourceText::new(input.to_string())).lex();
+            let tree = Parser::new(tokens, input.to_string()).parse();
+            let diagnostics = bind_global_scope(&[], &[], &tree, true).diagnostics;
+            assert!(
+                matches!(
+                    diagnostics.errors.as_slice(),
+                    [ErrorKind::DuplicateDeclaration { name, .. }] if name == "x"
+                ),
+                "{}: {:?}",
+                input,
+                diagnostics.errors
+            );
+            assert!(
+                !diagnostics
+                    .lints
+                    .iter()
+                    .any(|report| matches!(report.error, ErrorKind::UnusedVariable { .. })),
+                "{}",
+                input
+            );
+        }
+    }
+
+    #[test]
+    fn member_access_without_a_name_is_only_a_syntax_error() {
+        let errors = errors("let f = 1.5; f.");
+        assert!(errors.is_empty(), "{:?}", errors);
+    }
+}
@@ -0,0 +1,243 @@
+use crate::{
+    evaluator::Value,
+    lexer::source_text::TextSpan,
+    symbols::{FunctionSymbol, TypeSymbol, VariableSymbol},
+    syntax::{SyntaxToken, Tokens},
+};
+
+/// An expression whose names are resolved to symbols and whose type is
+/// inferred. Bound expressions keep the tokens runtime errors point at, and
+/// the span of the syntax they were bound from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoundExpression {
+    BoundLiteralExpression(BoundLiteralExpression),
+    BoundVariableExpression(BoundVariableExpression),
+    BoundBinaryExpression(BoundBinaryExpression),
+    BoundUnaryExpression(BoundUnaryExpression),
+    BoundAssignmentExpression(BoundAssignmentExpression),
+    BoundCompoundAssignmentExpression(BoundCompoundAssignmentExpression),
+    BoundUpdateExpression(BoundUpdateExpression),
+    BoundConditionalExpression(BoundConditionalExpression),
+    BoundIndexExpression(BoundIndexExpression),
+    BoundMemberAccessExpression(BoundMemberAccessExpression),
+    BoundCastExpression(BoundCastExpression),
+    BoundCallExpression(BoundCallExpression),
+    BoundArrayLiteralExpression(BoundArrayLiteralExpression),
+    BoundObjectLiteralExpression(BoundObjectLiteralExpression),
+    BoundErrorExpression(BoundErrorExpression),
+}
+
+impl BoundExpression {
+    pub fn span(&self) -> TextSpan {
+        match self {
+            BoundExpression::BoundLiteralExpression(expression) => expression.span,
+            BoundExpression::BoundVariableExpression(expression) => expression.span,
+            BoundExpression::BoundBinaryExpression(expression) => expression.span,
+            BoundExpression::BoundUnaryExpression(expression) => expression.span,
+            BoundExpression::BoundAssignmentExpression(expression) => expression.span,
+            BoundExpression::BoundCompoundAssignmentExpression(expression) => expression.span,
+            BoundExpression::BoundUpdateExpression(expression) => expression.span,
+            BoundExpression::BoundConditionalExpression(expression) => expression.span,
+            BoundExpression::BoundIndexExpression(expression) => expression.span,
+            BoundExpression::BoundMemberAccessExpression(expression) => expression.span,
+            BoundExpression::BoundCastExpression(expression) => expression.span,
+            BoundExpression::BoundCallExpression(expression) => expression.span,
+            BoundExpression::BoundArrayLiteralExpression(expression) => expression.span,
+            BoundExpression::BoundObjectLiteralExpression(expression) => expression.span,
+            BoundExpression::BoundErrorExpression(expression) => expression.span,
+        }
+    }
+
+    /// The static type of the expression's value.
+    pub fn type_symbol(&self) -> TypeSymbol {
+        match self {
+            BoundExpression::BoundLiteralExpression(expression) => expression.value.type_symbol(),
+            BoundExpression::BoundVariableExpression(expression) => {
+                expression.variable.type_symbol()
+            }
+            BoundExpression::BoundBinaryExpression(expression) => expression.type_symbol,
+            BoundExpression::BoundUnaryExpression(expression) => expression.type_symbol,
+            BoundExpression::BoundAssignmentExpression(expression) => {
+                expression.value.type_symbol()
+            }
+            BoundExpression::BoundCompoundAssignmentExpression(expression) => {
+                expression.target.type_symbol()
+            }
+            BoundExpression::BoundUpdateExpression(expression) => expression.operand.type_symbol(),
+            BoundExpression::BoundConditionalExpression(expression) => expression.type_symbol,
+            BoundExpression::BoundIndexExpression(expression) => expression.type_symbol,
+            BoundExpression::BoundMemberAccessExpression(_) => TypeSymbol::Any,
+            BoundExpression::BoundCastExpression(expression) => expression.type_symbol,
+            BoundExpression::BoundCallExpression(expression) => expression.function.return_type(),
+            BoundExpression::BoundArrayLiteralExpression(_) => TypeSymbol::Array,
+            BoundExpression::BoundObjectLiteralExpression(_) => TypeSymbol::Object,
+            BoundExpression::BoundErrorExpression(_) => TypeSymbol::Error,
+        }
+    }
+
+    /// The expressions directly inside this one, in source order.
+    pub fn children(&self) -> Vec<&BoundExpression> {
+        match self {
+            BoundExpression::BoundLiteralExpression(_)
+            | BoundExpression::BoundVariableExpression(_)
+            | BoundExpression::BoundErrorExpression(_) => Vec::new(),
+            BoundExpression::BoundBinaryExpression(expression) => {
+                vec![&expression.left, &expression.right]
+            }
+            BoundExpression::BoundUnaryExpression(expression) => vec![&expression.operand],
+            BoundExpression::BoundAssignmentExpression(expression) => {
+                vec![&expression.target, &expression.value]
+            }
+            BoundExpression::BoundCompoundAssignmentExpression(expression) => {
+                vec![&expression.target, &expression.value]
+            }
+            BoundExpression::BoundUpdateExpression(expression) => vec![&expression.operand],
+            BoundExpression::BoundConditionalExpression(expression) => vec![
+                &expression.condition,
+                &expression.when_true,
+                &expression.when_false,
+            ],
+            BoundExpression::BoundIndexExpression(expression) => {
+                vec![&expression.target, &expression.index]
+            }
+            BoundExpression::BoundMemberAccessExpression(expression) => vec![&expression.target],
+            BoundExpression::BoundCastExpression(expression) => vec![&expression.expression],
+            BoundExpression::BoundCallExpression(expression) => {
+                expression.arguments.iter().collect()
+            }
+            BoundExpression::BoundArrayLiteralExpression(expression) => {
+                expression.elements.iter().collect()
+            }
+            BoundExpression::BoundObjectLiteralExpression(expression) => expression
+                .properties
+                .iter()
+                .map(|(_, value)| value)
+                .collect(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundLiteralExpression {
+    pub value: Value,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundVariableExpression {
+    pub variable: VariableSymbol,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundBinaryExpression {
+    pub left: Box<BoundExpression>,
+    pub operator: SyntaxToken,
+    pub right: Box<BoundExpression>,
+    pub type_symbol: TypeSymbol,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundUnaryExpression {
+    pub operator: SyntaxToken,
+    pub operand: Box<BoundExpression>,
+    pub type_symbol: TypeSymbol,
+    pub span: TextSpan,
+}
+
+/// `target = value`. The target is a variable, index or member access.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundAssignmentExpression {
+    pub target: Box<BoundExpression>,
+    pub value: Box<BoundExpression>,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundCompoundAssignmentExpression {
+    pub target: Box<BoundExpression>,
+    pub operator_token: SyntaxToken,
+    pub binary_operator: Tokens,
+    pub value: Box<BoundExpression>,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundUpdateExpression {
+    pub operator_token: SyntaxToken,
+    pub binary_operator: Tokens,
+    pub operand: Box<BoundExpression>,
+    pub is_prefix: bool,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundConditionalExpression {
+    pub condition: Box<BoundExpression>,
+    pub when_true: Box<BoundExpression>,
+    pub when_false: Box<BoundExpression>,
+    pub type_symbol: TypeSymbol,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundIndexExpression {
+    pub target: Box<BoundExpression>,
+    pub index: Box<BoundExpression>,
+    /// Whether this is a null-conditional `?[`.
+    pub is_conditional: bool,
+    pub type_symbol: TypeSymbol,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundMemberAccessExpression {
+    pub target: Box<BoundExpression>,
+    pub name: String,
+    /// Whether this is a null-conditional `?.`.
+    pub is_conditional: bool,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundCastExpression {
+    pub expression: Box<BoundExpression>,
+    pub as_keyword: SyntaxToken,
+    pub type_token: SyntaxToken,
+    pub type_symbol: TypeSymbol,
+    pub span: TextSpan,
+}
+
+impl BoundCastExpression {
+    pub fn type_name(&self) -> &str {
+        self.type_token.text()
+    }
+}
+
+/// A call with one argument for each of the function's parameters.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundCallExpression {
+    pub function: FunctionSymbol,
+    pub arguments: Vec<BoundExpression>,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundArrayLiteralExpression {
+    pub elements: Vec<BoundExpression>,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundObjectLiteralExpression {
+    pub properties: Vec<(String, BoundExpression)>,
+    pub span: TextSpan,
+}
+
+/// Stands in for an expression that could not be bound or type checked. The
+/// error has already been reported, and a tree containing one is never evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundErrorExpression {
+    pub span: TextSpan,
+}
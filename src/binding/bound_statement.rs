@@ -0,0 +1,99 @@
+use crate::{lexer::source_text::TextSpan, symbols::VariableSymbol};
+
+use super::BoundExpression;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum BoundStatement {
+    BoundBlockStatement(BoundBlockStatement),
+    BoundExpressionStatement(BoundExpressionStatement),
+    BoundVariableDeclaration(BoundVariableDeclaration),
+    BoundIfStatement(BoundIfStatement),
+    BoundReturnStatement(BoundReturnStatement),
+    BoundWhileStatement(BoundWhileStatement),
+    BoundBreakStatement(BoundBreakStatement),
+    BoundContinueStatement(BoundContinueStatement),
+    BoundErrorStatement(BoundErrorStatement),
+}
+
+impl BoundStatement {
+    pub fn span(&self) -> TextSpan {
+        match self {
+            BoundStatement::BoundBlockStatement(statement) => statement.span,
+            BoundStatement::BoundExpressionStatement(statement) => statement.span,
+            BoundStatement::BoundVariableDeclaration(statement) => statement.span,
+            BoundStatement::BoundIfStatement(statement) => statement.span,
+            BoundStatement::BoundReturnStatement(statement) => statement.span,
+            BoundStatement::BoundWhileStatement(statement) => statement.span,
+            BoundStatement::BoundBreakStatement(statement) => statement.span,
+            BoundStatement::BoundContinueStatement(statement) => statement.span,
+            BoundStatement::BoundErrorStatement(statement) => statement.span,
+        }
+    }
+}
+
+/// A block and the variables declared in it, which go out of scope at its
+/// end.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundBlockStatement {
+    pub statements: Vec<BoundStatement>,
+    pub span: TextSpan,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundExpressionStatement {
+    pub expression: BoundExpression,
+    pub span: TextSpan,
+}
+
+/// A variable declaration. A variable without an initializer has no value
+/// until it is assigned.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundVariableDeclaration {
+    pub variable: VariableSymbol,
+    pub initializer: Option<BoundExpression>,
+    pub span: TextSpan,
+}
+
+/// `if` with an optional `else`, which is a block or, for `else if`, another
+/// `if` statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundIfStatement {
+    pub condition: BoundExpression,
+    pub body: BoundBlockStatement,
+    pub else_body: Option<Box<BoundStatement>>,
+    pub span: TextSpan,
+}
+
+/// `return`, which gives null when it has no value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundReturnStatement {
+    pub expression: Option<BoundExpression>,
+    pub span: TextSpan,
+}
+
+/// `while`, which runs its body for as long as its condition is true.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundWhileStatement {
+    pub condition: BoundExpression,
+    pub body: BoundBlockStatement,
+    pub span: TextSpan,
+}
+
+/// `break`, which leaves the innermost loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundBreakStatement {
+    pub span: TextSpan,
+}
+
+/// `continue`, which goes back to the condition of the innermost loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundContinueStatement {
+    pub span: TextSpan,
+}
+
+/// Stands in for the tokens the parser skipped. The syntax error has already
+/// been reported, and a tree containing one is never evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BoundErrorStatement {
+    pub span: TextSpan,
+}
@@ -0,0 +1,277 @@
+use crate::{
+    diagnostics::{Diagnostics, ErrorKind, Lint},
+    evaluator::Value,
+};
+
+use super::{BoundExpression, BoundStatement};
+
+/// An index into the blocks of a [`ControlFlowGraph`].
+pub type BlockId = usize;
+
+/// How control can move through a list of statements. Statements that always
+/// run one after another share a basic block; `if`, `while`, `break`,
+/// `continue` and `return` end one and add edges to the blocks that can run
+/// next.
+///
+/// Only the statements that do not contain other statements are placed in
+/// blocks. The condition of an `if` or `while` is evaluated at the end of the
+/// block that branches on it.
+#[derive(Debug)]
+pub struct ControlFlowGraph<'a> {
+    pub blocks: Vec<BasicBlock<'a>>,
+    /// The block that is reached after the last statement, or by `return`.
+    pub end: BlockId,
+    /// The block that runs after the last statement, so control gets to
+    /// `end` without a `return` if it is reachable.
+    pub fall_through: BlockId,
+    /// Every statement and the block that is running when it starts.
+    starts: Vec<StatementStart<'a>>,
+    loops: Vec<LoopExits<'a>>,
+}
+
+#[derive(Debug, Default)]
+pub struct BasicBlock<'a> {
+    pub statements: Vec<&'a BoundStatement>,
+    pub condition: Option<&'a BoundExpression>,
+    pub successors: Vec<BlockId>,
+}
+
+#[derive(Debug)]
+struct StatementStart<'a> {
+    statement: &'a BoundStatement,
+    block: BlockId,
+    /// The index in `starts` of the statement before this one in the same
+    /// list. The first statement of a list is reachable whenever the
+    /// statement containing the list is.
+    previous: Option<usize>,
+}
+
+/// A `while` with a literal condition and the blocks that leave it by
+/// `break` or `return`.
+#[derive(Debug)]
+struct LoopExits<'a> {
+    statement: &'a BoundStatement,
+    /// The block that runs when the loop starts.
+    block: BlockId,
+    condition: bool,
+    exits: Vec<BlockId>,
+}
+
+impl<'a> ControlFlowGraph<'a> {
+    pub fn build(statements: &'a [BoundStatement]) -> Self {
+        let mut builder = GraphBuilder {
+            blocks: vec![BasicBlock::default(), BasicBlock::default()],
+            current: 0,
+            end: 1,
+            breaks: Vec::new(),
+            returns: Vec::new(),
+            starts: Vec::new(),
+            loops: Vec::new(),
+        };
+        builder.build_statements(statements);
+        let fall_through = builder.current;
+        builder.connect(fall_through, builder.end);
+        ControlFlowGraph {
+            blocks: builder.blocks,
+            end: builder.end,
+            fall_through,
+            starts: builder.starts,
+            loops: builder.loops,
+        }
+    }
+
+    /// Which blocks control can get to from the first one.
+    pub fn reachable(&self) -> Vec<bool> {
+        let mut reachable = vec![false; self.blocks.len()];
+        let mut pending = vec![0];
+        while let Some(block) = pending.pop() {
+            if !reachable[block] {
+                reachable[block] = true;
+                pending.extend(&self.blocks[block].successors);
+            }
+        }
+        reachable
+    }
+}
+
+/// Reports code that can never run and loops whose literal condition makes
+/// them never run or never end. Returns whether control can get past the
+/// last statement without a `return`.
+pub fn check_control_flow(graph: &ControlFlowGraph, diagnostics: &mut Diagnostics) -> bool {
+    let reachable = graph.reachable();
+
+    // Only the first statement that cannot run is reported; the ones after
+    // it and inside it cannot run for the same reason.
+    for start in &graph.starts {
+        let Some(previous) = start.previous else {
+            continue;
+        };
+        if reachable[graph.starts[previous].block] && !reachable[start.block] {
+            diagnostics.add_lint(
+                Lint::UnreachableCode,
+                ErrorKind::UnreachableCode {
+                    position: start.statement.span().get_start(),
+                },
+            );
+        }
+    }
+
+    for loop_exits in &graph.loops {
+        if !reachable[loop_exits.block] {
+            continue;
+        }
+        let position = loop_exits.statement.span().get_start();
+        if !loop_exits.condition {
+            diagnostics.add_lint(
+                Lint::ConstantCondition,
+                ErrorKind::LoopNeverRuns { position },
+            );
+        } else if !loop_exits.exits.iter().any(|exit| reachable[*exit]) {
+            diagnostics.add_lint(Lint::InfiniteLoop, ErrorKind::InfiniteLoop { position });
+        }
+    }
+
+    reachable[graph.fall_through]
+}
+
+struct GraphBuilder<'a> {
+    blocks: Vec<BasicBlock<'a>>,
+    current: BlockId,
+    end: BlockId,
+    /// The condition block and exit block of each enclosing loop, innermost
+    /// last, and the blocks that `break` out of it so far.
+    breaks: Vec<(BlockId, BlockId, Vec<BlockId>)>,
+    /// The blocks that end with a `return`.
+    returns: Vec<BlockId>,
+    starts: Vec<StatementStart<'a>>,
+    loops: Vec<LoopExits<'a>>,
+}
+
+impl<'a> GraphBuilder<'a> {
+    fn new_block(&mut self) -> BlockId {
+        self.blocks.push(BasicBlock::default());
+        self.blocks.len() - 1
+    }
+
+    fn connect(&mut self, from: BlockId, to: BlockId) {
+        self.blocks[from].successors.push(to);
+    }
+
+    /// Ends the current block with a jump to `to`. What follows starts a
+    /// block nothing jumps to, so it is unreachable until something does.
+    fn jump(&mut self, to: BlockId) {
+        self.connect(self.current, to);
+        self.current = self.new_block();
+    }
+
+    fn build_statements(&mut self, statements: &'a [BoundStatement]) {
+        let mut previous = None;
+        for statement in statements {
+            self.starts.push(StatementStart {
+                statement,
+                block: self.current,
+                previous,
+            });
+            previous = Some(self.starts.len() - 1);
+            self.build_statement(statement);
+        }
+    }
+
+    fn build_statement(&mut self, statement: &'a BoundStatement) {
+        match statement {
+            BoundStatement::BoundBlockStatement(block) => self.build_statements(&block.statements),
+            BoundStatement::BoundIfStatement(statement) => {
+                let branch = self.current;
+                self.blocks[branch].condition = Some(&statement.condition);
+                let condition = literal_condition(&statement.condition);
+                let after = self.new_block();
+
+                self.current = self.new_block();
+                if condition != Some(false) {
+                    self.connect(branch, self.current);
+                }
+                self.build_statements(&statement.body.statements);
+                self.connect(self.current, after);
+
+                self.current = self.new_block();
+                if condition != Some(true) {
+                    self.connect(branch, self.current);
+                }
+                if let Some(else_body) = &statement.else_body {
+                    self.build_statement(else_body);
+                }
+                self.connect(self.current, after);
+                self.current = after;
+            }
+            BoundStatement::BoundWhileStatement(while_statement) => {
+                let block = self.current;
+                let header = self.new_block();
+                self.connect(block, header);
+                self.blocks[header].condition = Some(&while_statement.condition);
+                let condition = literal_condition(&while_statement.condition);
+                let exit = self.new_block();
+                if condition != Some(true) {
+                    self.connect(header, exit);
+                }
+
+                let returns = self.returns.len();
+                self.breaks.push((header, exit, Vec::new()));
+                self.current = self.new_block();
+                if condition != Some(false) {
+                    self.connect(header, self.current);
+                }
+                self.build_statements(&while_statement.body.statements);
+                self.connect(self.current, header);
+                let (_, _, mut exits) = self.breaks.pop().expect("the loop was just pushed");
+
+                if let Some(condition) = condition {
+                    exits.extend(&self.returns[returns..]);
+                    self.loops.push(LoopExits {
+                        statement,
+                        block,
+                        condition,
+                        exits,
+                    });
+                }
+                self.current = exit;
+            }
+            BoundStatement::BoundBreakStatement(_) => {
+                self.blocks[self.current].statements.push(statement);
+                let current = self.current;
+                if let Some((_, exit, exits)) = self.breaks.last_mut() {
+                    exits.push(current);
+                    let exit = *exit;
+                    self.jump(exit);
+                }
+            }
+            BoundStatement::BoundContinueStatement(_) => {
+                self.blocks[self.current].statements.push(statement);
+                if let Some((header, _, _)) = self.breaks.last() {
+                    let header = *header;
+                    self.jump(header);
+                }
+            }
+            BoundStatement::BoundReturnStatement(_) => {
+                self.blocks[self.current].statements.push(statement);
+                self.returns.push(self.current);
+                self.jump(self.end);
+            }
+            BoundStatement::BoundExpressionStatement(_)
+            | BoundStatement::BoundVariableDeclaration(_)
+            | BoundStatement::BoundErrorStatement(_) => {
+                self.blocks[self.current].statements.push(statement);
+            }
+        }
+    }
+}
+
+/// The value of a condition that is a literal `true` or `false`.
+fn literal_condition(condition: &BoundExpression) -> Option<bool> {
+    match condition {
+        BoundExpression::BoundLiteralExpression(literal) => match literal.value {
+            Value::Boolean(value) => Some(value),
+            _ => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,564 @@
+use std::collections::HashMap;
+
+use crate::{
+    diagnostics::{Diagnostics, ErrorKind, Lint},
+    symbols::VariableSymbol,
+    syntax::{SyntaxKind, Tokens},
+};
+
+use super::{
+    control_flow::{BasicBlock, ControlFlowGraph},
+    BoundExpression, BoundStatement,
+};
+
+/// A read or write of a variable. A block's accesses are listed in the order
+/// they happen when it runs.
+#[derive(Clone, Copy, Debug)]
+enum Access<'a> {
+    Read {
+        variable: &'a VariableSymbol,
+        position: usize,
+    },
+    /// `definite` is false for a write that only happens on some runs of the
+    /// expression containing it, such as one on the right of `??`.
+    Write {
+        variable: &'a VariableSymbol,
+        position: usize,
+        definite: bool,
+    },
+    /// A declaration, which gives the variable a value if it has an
+    /// initializer and otherwise leaves it without one.
+    Declare {
+        variable: &'a VariableSymbol,
+        position: usize,
+        initialized: bool,
+    },
+    /// Marks a variable that every arm of a `?:` assigns, so it has a value
+    /// after the expression although no single write in it is definite.
+    Assigned { variable: &'a VariableSymbol },
+}
+
+/// Reports reads of variables that may not have a value yet, variables that
+/// are never read and values that are assigned but never read.
+///
+/// `parameters` are the parameters of the function whose body `graph` was
+/// built from, with the position of each name. `globals` are the variables
+/// the statements declare for later inputs, which are only checked for
+/// reads without a value, since a later input may still read them.
+pub fn check_variables(
+    graph: &ControlFlowGraph,
+    parameters: &[(VariableSymbol, usize)],
+    globals: &[VariableSymbol],
+    diagnostics: &mut Diagnostics,
+) {
+    let accesses: Vec<Vec<Access>> = graph.blocks.iter().map(block_accesses).collect();
+    let reachable = graph.reachable();
+    check_definite_assignment(graph, &accesses, &reachable, diagnostics);
+
+    let mut locals: Vec<(&VariableSymbol, usize)> = parameters
+        .iter()
+        .map(|(parameter, position)| (parameter, *position))
+        .collect();
+    for access in accesses.iter().flatten() {
+        if let Access::Declare {
+            variable, position, ..
+        } = access
+        {
+            // A duplicate declaration declares the same variable again.
+            let declared = locals.iter().any(|(local, _)| local == variable);
+            if !globals.contains(variable) && !declared {
+                locals.push((variable, *position));
+            }
+        }
+    }
+    let read = |variable: &VariableSymbol| {
+        accesses.iter().flatten().any(
+            |access| matches!(access, Access::Read { variable: read, .. } if *read == variable),
+        )
+    };
+    let (unread, locals): (Vec<_>, Vec<_>) = locals
+        .into_iter()
+        .partition(|(variable, _)| !read(variable));
+    for (variable, position) in unread {
+        if !variable.name().starts_with('_') {
+            diagnostics.add_lint(
+                Lint::UnusedVariable,
+                ErrorKind::UnusedVariable {
+                    name: variable.name().to_string(),
+                    position,
+                },
+            );
+        }
+    }
+    let locals: Vec<&VariableSymbol> = locals.into_iter().map(|(variable, _)| variable).collect();
+    check_unused_assignments(graph, &accesses, &reachable, &locals, diagnostics);
+}
+
+/// Reports reads of variables declared without a value that are not
+/// assigned on every path to the read. A variable declared with a value, a
+/// parameter or a variable of an earlier input always has one.
+fn check_definite_assignment(
+    graph: &ControlFlowGraph,
+    accesses: &[Vec<Access>],
+    reachable: &[bool],
+    diagnostics: &mut Diagnostics,
+) {
+    let mut tracked: HashMap<&VariableSymbol, usize> = HashMap::new();
+    for access in accesses.iter().flatten() {
+        if let Access::Declare {
+            variable,
+            initialized: false,
+            ..
+        } = access
+        {
+            let index = tracked.len();
+            tracked.entry(variable).or_insert(index);
+        }
+    }
+    if tracked.is_empty() {
+        return;
+    }
+
+    let transfer = |assigned: &mut Vec<bool>, access: &Access| match access {
+        Access::Write {
+            variable,
+            definite: true,
+            ..
+        }
+        | Access::Assigned { variable } => {
+            if let Some(index) = tracked.get(variable) {
+                assigned[*index] = true;
+            }
+        }
+        Access::Declare {
+            variable,
+            initialized,
+            ..
+        } => {
+            if let Some(index) = tracked.get(variable) {
+                assigned[*index] = *initialized;
+            }
+        }
+        _ => {}
+    };
+
+    // Whether each variable has a value on every path to the start of a
+    // block. Blocks start out assuming every variable does, and lose that
+    // as paths without an assignment are found.
+    let predecessors = predecessors(graph);
+    let mut assigned_in = vec![vec![true; tracked.len()]; graph.blocks.len()];
+    assigned_in[0] = vec![false; tracked.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in 1..graph.blocks.len() {
+            let mut assigned = vec![true; tracked.len()];
+            for predecessor in &predecessors[block] {
+                if !reachable[*predecessor] {
+                    continue;
+                }
+                let mut out = assigned_in[*predecessor].clone();
+                for access in &accesses[*predecessor] {
+                    transfer(&mut out, access);
+                }
+                for (assigned, out) in assigned.iter_mut().zip(out) {
+                    *assigned &= out;
+                }
+            }
+            if assigned != assigned_in[block] {
+                assigned_in[block] = assigned;
+                changed = true;
+            }
+        }
+    }
+
+    for (block, accesses) in accesses.iter().enumerate() {
+        if !reachable[block] {
+            continue;
+        }
+        let mut assigned = assigned_in[block].clone();
+        for access in accesses {
+            if let Access::Read { variable, position } = access {
+                if tracked.get(variable).is_some_and(|index| !assigned[*index]) {
+                    diagnostics.add_error(ErrorKind::PossiblyUnassignedVariable {
+                        name: variable.name().to_string(),
+                        position: *position,
+                    });
+                }
+            }
+            transfer(&mut assigned, access);
+        }
+    }
+}
+
+/// Reports values stored in `locals` that no path reads before the variable
+/// is assigned again or goes out of scope.
+fn check_unused_assignments(
+    graph: &ControlFlowGraph,
+    accesses: &[Vec<Access>],
+    reachable: &[bool],
+    locals: &[&VariableSymbol],
+    diagnostics: &mut Diagnostics,
+) {
+    let index = |variable: &VariableSymbol| locals.iter().position(|local| *local == variable);
+    // Goes backwards over an access: a read needs the value the variable
+    // has before it, a write or declaration replaces it.
+    let transfer = |live: &mut Vec<bool>, access: &Access| match access {
+        Access::Read { variable, .. } => {
+            if let Some(index) = index(variable) {
+                live[index] = true;
+            }
+        }
+        Access::Write {
+            variable,
+            definite: true,
+            ..
+        }
+        | Access::Declare { variable, .. } => {
+            if let Some(index) = index(variable) {
+                live[index] = false;
+            }
+        }
+        Access::Write { .. } | Access::Assigned { .. } => {}
+    };
+
+    // Whether the value each variable has at the end of a block can still
+    // be read.
+    let mut live_out = vec![vec![false; locals.len()]; graph.blocks.len()];
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for block in (0..graph.blocks.len()).rev() {
+            let mut live = vec![false; locals.len()];
+            for successor in &graph.blocks[block].successors {
+                let mut live_in = live_out[*successor].clone();
+                for access in accesses[*successor].iter().rev() {
+                    transfer(&mut live_in, access);
+                }
+                for (live, live_in) in live.iter_mut().zip(live_in) {
+                    *live |= live_in;
+                }
+            }
+            if live != live_out[block] {
+                live_out[block] = live;
+                changed = true;
+            }
+        }
+    }
+
+    for (block, accesses) in accesses.iter().enumerate() {
+        if !reachable[block] {
+            continue;
+        }
+        let mut live = live_out[block].clone();
+        for access in accesses.iter().rev() {
+            let (variable, position) = match access {
+                Access::Write {
+                    variable, position, ..
+                }
+                | Access::Declare {
+                    variable,
+                    position,
+                    initialized: true,
+                } => (variable, position),
+                _ => {
+                    transfer(&mut live, access);
+                    continue;
+                }
+            };
+            if index(variable).is_some_and(|index| !live[index]) {
+                diagnostics.add_lint(
+                    Lint::UnusedAssignment,
+                    ErrorKind::UnusedAssignment {
+                        name: variable.name().to_string(),
+                        position: *position,
+                    },
+                );
+            }
+            transfer(&mut live, access);
+        }
+    }
+}
+
+fn predecessors(graph: &ControlFlowGraph) -> Vec<Vec<usize>> {
+    let mut predecessors = vec![Vec::new(); graph.blocks.len()];
+    for (block, basic_block) in graph.blocks.iter().enumerate() {
+        for successor in &basic_block.successors {
+            predecessors[*successor].push(block);
+        }
+    }
+    predecessors
+}
+
+fn block_accesses<'a>(block: &BasicBlock<'a>) -> Vec<Access<'a>> {
+    let mut accesses = Vec::new();
+    for statement in &block.statements {
+        match statement {
+            BoundStatement::BoundExpressionStatement(statement) => {
+                expression_accesses(&statement.expression, true, &mut accesses);
+            }
+            BoundStatement::BoundVariableDeclaration(declaration) => {
+                if let Some(initializer) = &declaration.initializer {
+                    expression_accesses(initializer, true, &mut accesses);
+                }
+                accesses.push(Access::Declare {
+                    variable: &declaration.variable,
+                    position: declaration.span.get_start(),
+                    initialized: declaration.initializer.is_some(),
+                });
+            }
+            BoundStatement::BoundReturnStatement(statement) => {
+                if let Some(expression) = &statement.expression {
+                    expression_accesses(expression, true, &mut accesses);
+                }
+            }
+            _ => {}
+        }
+    }
+    if let Some(condition) = block.condition {
+        expression_accesses(condition, true, &mut accesses);
+    }
+    accesses
+}
+
+/// Lists the accesses of `expression` in the order they run. `definite` is
+/// false inside a part of an expression that does not always run.
+fn expression_accesses<'a>(
+    expression: &'a BoundExpression,
+    definite: bool,
+    accesses: &mut Vec<Access<'a>>,
+) {
+    match expression {
+        BoundExpression::BoundVariableExpression(expression) => accesses.push(Access::Read {
+            variable: &expression.variable,
+            position: expression.span.get_start(),
+        }),
+        BoundExpression::BoundBinaryExpression(expression) => {
+            expression_accesses(&expression.left, definite, accesses);
+            // The right side of `??`, `&&` and `||` does not always run.
+            let short_circuits = matches!(
+                expression.operator.kind(),
+                SyntaxKind::Token(
+                    Tokens::QuestionMarkQuestionMarkToken
+                        | Tokens::AmpersandAmpersandToken
+                        | Tokens::PipePipeToken
+                )
+            );
+            expression_accesses(&expression.right, definite && !short_circuits, accesses);
+        }
+        BoundExpression::BoundAssignmentExpression(expression) => {
+            match expression.target.as_ref() {
+                BoundExpression::BoundVariableExpression(_) => {}
+                target => expression_accesses(target, definite, accesses),
+            }
+            expression_accesses(&expression.value, definite, accesses);
+            write(
+                &expression.target,
+                expression.span.get_start(),
+                definite,
+                accesses,
+            );
+        }
+        BoundExpression::BoundCompoundAssignmentExpression(expression) => {
+            expression_accesses(&expression.target, definite, accesses);
+            // `&&=` and `||=` do not always evaluate the value or write.
+            let short_circuits = matches!(
+                expression.binary_operator,
+                Tokens::AmpersandAmpersandToken | Tokens::PipePipeToken
+            );
+            expression_accesses(&expression.value, definite && !short_circuits, accesses);
+            write(
+                &expression.target,
+                expression.span.get_start(),
+                definite && !short_circuits,
+                accesses,
+            );
+        }
+        BoundExpression::BoundUpdateExpression(expression) => {
+            expression_accesses(&expression.operand, definite, accesses);
+            write(
+                &expression.operand,
+                expression.span.get_start(),
+                definite,
+                accesses,
+            );
+        }
+        BoundExpression::BoundConditionalExpression(expression) => {
+            expression_accesses(&expression.condition, definite, accesses);
+            // Only one arm runs, so no write in either is definite. A
+            // variable both arms assign still has a value afterwards.
+            let mut when_true = Vec::new();
+            let mut when_false = Vec::new();
+            expression_accesses(&expression.when_true, definite, &mut when_true);
+            expression_accesses(&expression.when_false, definite, &mut when_false);
+            let assigned = definite_writes(&when_true);
+            let assigned: Vec<&VariableSymbol> = definite_writes(&when_false)
+                .into_iter()
+                .filter(|variable| assigned.contains(variable))
+                .collect();
+            accesses.extend(
+                when_true
+                    .into_iter()
+                    .chain(when_false)
+                    .map(|access| match access {
+                        Access::Write {
+                            variable, position, ..
+                        } => Access::Write {
+                            variable,
+                            position,
+                            definite: false,
+                        },
+                        access => access,
+                    }),
+            );
+            accesses.extend(
+                assigned
+                    .into_iter()
+                    .map(|variable| Access::Assigned { variable }),
+            );
+        }
+        BoundExpression::BoundIndexExpression(expression) => {
+            expression_accesses(&expression.target, definite, accesses);
+            let definite = definite && !expression.is_conditional;
+            expression_accesses(&expression.index, definite, accesses);
+        }
+        expression => {
+            for child in expression.children() {
+                expression_accesses(child, definite, accesses);
+            }
+        }
+    }
+}
+
+/// The variables `accesses` always assign.
+fn definite_writes<'a>(accesses: &[Access<'a>]) -> Vec<&'a VariableSymbol> {
+    accesses
+        .iter()
+        .filter_map(|access| match access {
+            Access::Write {
+                variable,
+                definite: true,
+                ..
+            }
+            | Access::Assigned { variable } => Some(*variable),
+            _ => None,
+        })
+        .collect()
+}
+
+/// The write of an assignment to `target`, if it is a variable. Assigning an
+/// element or field reads the variable holding the array or object instead.
+fn write<'a>(
+    target: &'a BoundExpression,
+    position: usize,
+    definite: bool,
+    accesses: &mut Vec<Access<'a>>,
+) {
+    if let BoundExpression::BoundVariableExpression(target) = target {
+        accesses.push(Access::Write {
+            variable: &target.variable,
+            position,
+            definite,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        binding::bind_global_scope,
+        diagnostics::{Diagnostics, ErrorKind, Lint},
+        lexer::{source_text::SourceText, Lexer},
+        parser::Parser,
+    };
+
+    fn bind(text: &str) -> Diagnostics {
+        let tokens = Lexer::new(SourceText::new(text.to_string())).lex();
+        let tree = Parser::new(tokens, text.to_string()).parse();
+        bind_global_scope(&[], &[], &tree, true).diagnostics
+    }
+
+    /// The variables `text` may read before assigning them.
+    fn possibly_unassigned(text: &str) -> Vec<String> {
+        bind(text)
+            .errors
+            .into_iter()
+            .map(|error| match error {
+                ErrorKind::PossiblyUnassignedVariable { name, .. } => name,
+                error => panic!("{}: unexpected error {:?}", text, error),
+            })
+            .collect()
+    }
+
+    /// The variables `text` assigns a value that is never read, with the
+    /// position of each assignment, in source order.
+    fn unused_assignments(text: &str) -> Vec<(String, usize)> {
+        let mut reports: Vec<_> = bind(text)
+            .lints
+            .into_iter()
+            .filter(|report| report.lint == Lint::UnusedAssignment)
+            .map(|report| match report.error {
+                ErrorKind::UnusedAssignment { name, position } => (name, position),
+                error => panic!("{}: unexpected report {:?}", text, error),
+            })
+            .collect();
+        reports.sort_by_key(|(_, position)| *position);
+        reports
+    }
+
+    #[test]
+    fn assigned_on_every_path() {
+        let inputs = [
+            "let mut x: int; x = 1; x",
+            "fn f(c: bool): int { let mut x: int; if (c) { x = 1; } else { x = 2; } return x; }",
+            "fn f(c: bool): int { let mut x: int; c ? (x = 1) : (x = 2); return x; }",
+            "fn f(c: bool, d: bool): int { let mut x: int; c ? (x = 1) : d ? (x = 2) : (x = 3); return x; }",
+            "fn f(): int { let mut x: int; while (true) { x = 1; break; } return x; }",
+        ];
+        for input in inputs {
+            assert!(possibly_unassigned(input).is_empty(), "{}", input);
+        }
+    }
+
+    #[test]
+    fn assigned_on_some_paths() {
+        let inputs = [
+            "let mut x: int; x",
+            "fn f(c: bool): int { let mut x: int; if (c) { x = 1; } return x; }",
+            "fn f(c: bool): int { let mut x: int; c ? (x = 1) : 2; return x; }",
+            "fn f(c: bool): int { let mut x: int; while (c) { x = 1; } return x; }",
+            "fn f(c: int?): int { let mut x: int; let _y = c ?? (x = 1); return x; }",
+            "fn f(c: bool): bool { let mut x: bool; if (c || (x = true)) { return x; } return false; }",
+            "fn f(c: bool): bool { let mut x: bool; c && (x = true); return x; }",
+            "fn f(c: bool): bool { let mut x: bool; let mut d = c; d ||= (x = true); return x && d; }",
+        ];
+        for input in inputs {
+            assert_eq!(possibly_unassigned(input), vec!["x"], "{}", input);
+        }
+    }
+
+    #[test]
+    fn values_never_read() {
+        let text = "fn f(): int { let mut x = 1; x = 2; return x; }";
+        assert_eq!(unused_assignments(text), vec![("x".to_string(), 14)]);
+        let text = "fn f(): int { let mut x = 1; x = 2; x = 3; return x; }";
+        assert_eq!(
+            unused_assignments(text),
+            vec![("x".to_string(), 14), ("x".to_string(), 29)]
+        );
+    }
+
+    #[test]
+    fn values_read_later() {
+        let inputs = [
+            "fn f(): int { let mut x = 1; x += 2; return x; }",
+            "fn f(c: bool): int { let mut x = 1; if (c) { x = 2; } return x; }",
+            "fn f(): int { let mut x = 0; while (x < 3) { x += 1; } return x; }",
+            // Top-level variables may be read by a later input.
+            "let mut x = 1; x = 2;",
+        ];
+        for input in inputs {
+            assert!(unused_assignments(input).is_empty(), "{}", input);
+        }
+    }
+}
@@ -0,0 +1,12 @@
+#![allow(clippy::enum_variant_names)]
+
+mod binder;
+mod bound_expression;
+mod bound_statement;
+mod control_flow;
+mod data_flow;
+mod operators;
+
+pub use binder::*;
+pub use bound_expression::*;
+pub use bound_statement::*;
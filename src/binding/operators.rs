@@ -0,0 +1,230 @@
+use crate::{
+    symbols::TypeSymbol,
+    syntax::{Keywords, SyntaxKind, Tokens},
+};
+
+/// The type of `left operator right`, or `None` when the operator does not
+/// apply to those types. These are the rules `binary_expression_evaluator`
+/// applies to values, so an expression that type checks only fails at
+/// runtime on values typed `any`, or on errors such as overflow.
+pub fn binary_operator_type(
+    operator: &SyntaxKind,
+    left: TypeSymbol,
+    right: TypeSymbol,
+) -> Option<TypeSymbol> {
+    let comparison = matches!(
+        operator,
+        SyntaxKind::Token(
+            Tokens::EqualsEqualsToken
+                | Tokens::BangEqualsToken
+                | Tokens::LessThanToken
+                | Tokens::LessThanEqualsToken
+                | Tokens::GreaterThanToken
+                | Tokens::GreaterThanEqualsToken
+                | Tokens::AmpersandAmpersandToken
+                | Tokens::PipePipeToken
+        )
+    );
+    if left == TypeSymbol::Any || right == TypeSymbol::Any {
+        return Some(if comparison {
+            TypeSymbol::Bool
+        } else {
+            TypeSymbol::Any
+        });
+    }
+
+    let numeric = numeric_type(left, right);
+    let both = |expected: TypeSymbol, result: TypeSymbol| {
+        (left == expected && right == expected).then_some(result)
+    };
+    match operator {
+        SyntaxKind::Token(Tokens::PlusToken) => {
+            both(TypeSymbol::String, TypeSymbol::String).or(numeric)
+        }
+        SyntaxKind::Token(Tokens::MinusToken | Tokens::StarToken | Tokens::PercentToken)
+        | SyntaxKind::Keyword(Keywords::DivKeyword) => numeric,
+        // Dividing ints gives a float; `div` is the int division.
+        SyntaxKind::Token(Tokens::SlashToken) => numeric.map(|numeric| match numeric {
+            TypeSymbol::Int => TypeSymbol::Float,
+            numeric => numeric,
+        }),
+        SyntaxKind::Token(Tokens::EqualsEqualsToken | Tokens::BangEqualsToken) => {
+            equatable(left, right).then_some(TypeSymbol::Bool)
+        }
+        SyntaxKind::Token(
+            Tokens::LessThanToken
+            | Tokens::LessThanEqualsToken
+            | Tokens::GreaterThanToken
+            | Tokens::GreaterThanEqualsToken,
+        ) => numeric
+            .map(|_| TypeSymbol::Bool)
+            .or(both(TypeSymbol::String, TypeSymbol::Bool))
+            .or(both(TypeSymbol::Char, TypeSymbol::Bool)),
+        SyntaxKind::Token(Tokens::AmpersandAmpersandToken | Tokens::PipePipeToken) => {
+            both(TypeSymbol::Bool, TypeSymbol::Bool)
+        }
+        SyntaxKind::Token(
+            Tokens::AmpersandToken
+            | Tokens::PipeToken
+            | Tokens::CaretToken
+            | Tokens::LessThanLessThanToken
+            | Tokens::GreaterThanGreaterThanToken,
+        ) => both(TypeSymbol::Int, TypeSymbol::Int),
+        // Only a left side that may be null ever gives the right side.
+        SyntaxKind::Token(Tokens::QuestionMarkQuestionMarkToken) => Some(match left {
+            TypeSymbol::Null => right,
+            TypeSymbol::Nullable(inner) => common_type(*inner, right),
+            left => left,
+        }),
+        _ => None,
+    }
+}
+
+/// The type of `operator operand`, or `None` when the operator does not
+/// apply to the type.
+pub fn unary_operator_type(operator: &SyntaxKind, operand: TypeSymbol) -> Option<TypeSymbol> {
+    match (operator, operand) {
+        (SyntaxKind::Token(Tokens::BangToken), TypeSymbol::Any) => Some(TypeSymbol::Bool),
+        (_, TypeSymbol::Any) => Some(TypeSymbol::Any),
+        (SyntaxKind::Token(Tokens::PlusToken | Tokens::MinusToken), operand)
+            if operand.is_numeric() =>
+        {
+            Some(operand)
+        }
+        (SyntaxKind::Token(Tokens::BangToken), TypeSymbol::Bool) => Some(TypeSymbol::Bool),
+        (SyntaxKind::Token(Tokens::TildeToken), TypeSymbol::Int) => Some(TypeSymbol::Int),
+        _ => None,
+    }
+}
+
+/// The type two values of these types could have. A type and null give the
+/// nullable type; otherwise it is `any` when they differ.
+pub fn common_type(left: TypeSymbol, right: TypeSymbol) -> TypeSymbol {
+    match (left, right) {
+        (left, right) if left == right => left,
+        (TypeSymbol::Any | TypeSymbol::Error, _) | (_, TypeSymbol::Any | TypeSymbol::Error) => {
+            TypeSymbol::Any
+        }
+        (TypeSymbol::Null, other) | (other, TypeSymbol::Null) => other.nullable(),
+        (TypeSymbol::Nullable(inner), other) | (other, TypeSymbol::Nullable(inner))
+            if *inner == other =>
+        {
+            TypeSymbol::Nullable(inner)
+        }
+        _ => TypeSymbol::Any,
+    }
+}
+
+/// Whether `==` and `!=` compare values of these types: numbers that mix in
+/// arithmetic, two values of the same type, or null with anything that may
+/// be null.
+fn equatable(left: TypeSymbol, right: TypeSymbol) -> bool {
+    match (left, right) {
+        (TypeSymbol::Null, other) | (other, TypeSymbol::Null) => {
+            matches!(other, TypeSymbol::Null | TypeSymbol::Nullable(_))
+        }
+        (TypeSymbol::Nullable(inner), other) | (other, TypeSymbol::Nullable(inner)) => {
+            equatable(*inner, other)
+        }
+        (left, right) => left == right || numeric_type(left, right).is_some(),
+    }
+}
+
+/// The type arithmetic on two numbers gives: an int mixed with a float or a
+/// decimal is widened to it, while floats and decimals do not mix.
+fn numeric_type(left: TypeSymbol, right: TypeSymbol) -> Option<TypeSymbol> {
+    match (left, right) {
+        (left, right) if left == right && left.is_numeric() => Some(left),
+        (TypeSymbol::Int, other) | (other, TypeSymbol::Int)
+            if matches!(other, TypeSymbol::Float | TypeSymbol::Decimal) =>
+        {
+            Some(other)
+        }
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn binary(operator: Tokens, left: TypeSymbol, right: TypeSymbol) -> Option<TypeSymbol> {
+        binary_operator_type(&SyntaxKind::Token(operator), left, right)
+    }
+
+    #[test]
+    fn equality() {
+        let bool = Some(TypeSymbol::Bool);
+        let int = TypeSymbol::Int;
+        assert_eq!(
+            binary(Tokens::EqualsEqualsToken, int, TypeSymbol::Float),
+            bool
+        );
+        assert_eq!(
+            binary(
+                Tokens::BangEqualsToken,
+                TypeSymbol::String,
+                TypeSymbol::String
+            ),
+            bool
+        );
+        assert_eq!(
+            binary(Tokens::EqualsEqualsToken, int.nullable(), TypeSymbol::Null),
+            bool
+        );
+        assert_eq!(binary(Tokens::EqualsEqualsToken, int.nullable(), int), bool);
+        assert_eq!(
+            binary(Tokens::EqualsEqualsToken, int, TypeSymbol::Null),
+            None
+        );
+        assert_eq!(
+            binary(Tokens::EqualsEqualsToken, int, TypeSymbol::String),
+            None
+        );
+        assert_eq!(
+            binary(
+                Tokens::EqualsEqualsToken,
+                TypeSymbol::Float,
+                TypeSymbol::Decimal
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn ordering() {
+        let bool = Some(TypeSymbol::Bool);
+        assert_eq!(
+            binary(Tokens::LessThanToken, TypeSymbol::Int, TypeSymbol::Decimal),
+            bool
+        );
+        assert_eq!(
+            binary(
+                Tokens::GreaterThanEqualsToken,
+                TypeSymbol::Char,
+                TypeSymbol::Char
+            ),
+            bool
+        );
+        assert_eq!(
+            binary(
+                Tokens::LessThanEqualsToken,
+                TypeSymbol::Any,
+                TypeSymbol::Int
+            ),
+            bool
+        );
+        assert_eq!(
+            binary(Tokens::GreaterThanToken, TypeSymbol::Bool, TypeSymbol::Bool),
+            None
+        );
+        assert_eq!(
+            binary(
+                Tokens::LessThanToken,
+                TypeSymbol::Int.nullable(),
+                TypeSymbol::Int
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,123 @@
+use std::{collections::HashMap, str::FromStr};
+
+use colored::*;
+
+/// How serious a diagnostic is. Only errors stop an input from running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+    Info,
+    Hint,
+}
+
+impl Severity {
+    /// The label a diagnostic of this severity is printed with.
+    pub fn label(self) -> ColoredString {
+        match self {
+            Severity::Error => "Error".red().bold(),
+            Severity::Warning => "Warning".yellow().bold(),
+            Severity::Info => "Info".blue().bold(),
+            Severity::Hint => "Hint".cyan().bold(),
+        }
+    }
+}
+
+/// A named check for code that runs but is likely a mistake. Each lint is
+/// reported at a level that can be changed from the command line or the
+/// REPL, and turned off for one statement with `@allow(name)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Lint {
+    UnusedVariable,
+    UnusedAssignment,
+    UnreachableCode,
+    ConstantCondition,
+    InfiniteLoop,
+    Shadowing,
+    FloatEquality,
+}
+
+impl Lint {
+    pub const ALL: [Lint; 7] = [
+        Lint::UnusedVariable,
+        Lint::UnusedAssignment,
+        Lint::UnreachableCode,
+        Lint::ConstantCondition,
+        Lint::InfiniteLoop,
+        Lint::Shadowing,
+        Lint::FloatEquality,
+    ];
+
+    /// The name used for the lint in flags and `@allow`.
+    pub fn name(self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "unused-variable",
+            Lint::UnusedAssignment => "unused-assignment",
+            Lint::UnreachableCode => "unreachable-code",
+            Lint::ConstantCondition => "constant-condition",
+            Lint::InfiniteLoop => "infinite-loop",
+            Lint::Shadowing => "shadowing",
+            Lint::FloatEquality => "float-equality",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Lint::UnusedVariable => "a local variable or parameter that is never read",
+            Lint::UnusedAssignment => "a value stored in a local variable that is never read",
+            Lint::UnreachableCode => "a statement that can never run",
+            Lint::ConstantCondition => "an if, while or ?: whose condition never changes",
+            Lint::InfiniteLoop => "a while (true) loop with no break or return",
+            Lint::Shadowing => "a declaration that hides a variable of an outer scope",
+            Lint::FloatEquality => "floats compared with == or !=, which rounding makes unreliable",
+        }
+    }
+
+    /// The level the lint is reported at unless it is changed. `None` means
+    /// it is not reported.
+    pub fn default_level(self) -> Option<Severity> {
+        match self {
+            Lint::Shadowing => Some(Severity::Hint),
+            _ => Some(Severity::Warning),
+        }
+    }
+}
+
+impl FromStr for Lint {
+    type Err = ();
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Lint::ALL
+            .into_iter()
+            .find(|lint| lint.name() == name)
+            .ok_or(())
+    }
+}
+
+/// The level every lint is reported at.
+#[derive(Clone, Debug, Default)]
+pub struct LintLevels {
+    levels: HashMap<Lint, Option<Severity>>,
+    /// Whether lints reported as warnings are errors instead, so they stop
+    /// the input from running.
+    pub warnings_as_errors: bool,
+}
+
+impl LintLevels {
+    /// Reports `lint` at `level` from now on, or not at all for `None`.
+    pub fn set(&mut self, lint: Lint, level: Option<Severity>) {
+        self.levels.insert(lint, level);
+    }
+
+    pub fn level(&self, lint: Lint) -> Option<Severity> {
+        let level = self
+            .levels
+            .get(&lint)
+            .copied()
+            .unwrap_or_else(|| lint.default_level());
+        match level {
+            Some(Severity::Warning) if self.warnings_as_errors => Some(Severity::Error),
+            level => level,
+        }
+    }
+}
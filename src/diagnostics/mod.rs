@@ -1,16 +1,37 @@
-use crate::syntax::SyntaxKindDescriptor;
+use crate::{
+    lexer::source_text::{SourceText, TextSpan},
+    syntax::SyntaxToken,
+};
 use colored::*;
+use num_bigint::BigInt;
+
+mod lint;
+
+pub use lint::*;
 
 #[derive(Debug, Clone)]
 pub struct Diagnostics {
     pub errors: Vec<ErrorKind>,
+    /// What lints found, which only stops the input from running when it is
+    /// reported as an error.
+    pub lints: Vec<LintReport>,
     pub input: String,
 }
 
+/// One problem a lint found, and the severity it is reported with. A report
+/// whose lint is turned off has no severity and is not printed.
+#[derive(Debug, Clone)]
+pub struct LintReport {
+    pub lint: Lint,
+    pub severity: Option<Severity>,
+    pub error: ErrorKind,
+}
+
 impl Diagnostics {
     pub fn new(input: String) -> Self {
         Self {
             errors: Vec::new(),
+            lints: Vec::new(),
             input,
         }
     }
@@ -19,46 +40,584 @@ impl Diagnostics {
         self.errors.push(error);
     }
 
+    /// Adds what `lint` found, at the lint's default level.
+    pub fn add_lint(&mut self, lint: Lint, error: ErrorKind) {
+        self.lints.push(LintReport {
+            lint,
+            severity: lint.default_level(),
+            error,
+        });
+    }
+
+    /// Drops what `lint` found in `span`, for `@allow`.
+    pub fn allow(&mut self, lint: Lint, span: TextSpan) {
+        self.lints.retain(|report| {
+            let position = report.error.position();
+            report.lint != lint || position < span.get_start() || position >= span.get_end()
+        });
+    }
+
+    /// Reports every lint at the level `levels` gives it.
+    pub fn apply_levels(&mut self, levels: &LintLevels) {
+        for report in &mut self.lints {
+            report.severity = levels.level(report.lint);
+        }
+    }
+
     pub fn merge(&mut self, diagnostics: Diagnostics) {
         self.errors.extend(diagnostics.errors);
+        self.lints.extend(diagnostics.lints);
     }
 
+    /// Whether anything stops the input from running: an error, or a lint
+    /// reported as one.
     pub fn has_errors(&self) -> bool {
-        self.errors.len() > 0
+        !self.errors.is_empty()
+            || self
+                .lints
+                .iter()
+                .any(|report| report.severity == Some(Severity::Error))
+    }
+
+    /// Clamps a byte offset into the input down to the nearest char boundary
+    /// so that slicing never splits a multibyte character.
+    fn floor_char_boundary(&self, position: usize) -> usize {
+        let mut position = position.min(self.input.len());
+        while !self.input.is_char_boundary(position) {
+            position -= 1;
+        }
+        position
     }
 
+    /// Formats a byte offset as a 1-based `line:column`, counting the column
+    /// in characters.
+    fn location(&self, source: &SourceText, position: usize) -> String {
+        let position = self.floor_char_boundary(position);
+        let line_index = source.get_line_index(position);
+        let line_start = source.lines()[line_index].get_start();
+        let column = self.input[line_start..position].chars().count();
+        format!("{}:{}", line_index + 1, column + 1)
+    }
+
+    /// Prints every error and lint report in source order, so lexer and
+    /// parser errors interleave the way they appear in the input.
     pub fn print(&self) {
-        for error in &self.errors {
+        let source = SourceText::new(self.input.clone());
+        let mut errors: Vec<(&ErrorKind, Severity)> = self
+            .errors
+            .iter()
+            .map(|error| (error, Severity::Error))
+            .chain(
+                self.lints
+                    .iter()
+                    .filter_map(|report| Some((&report.error, report.severity?))),
+            )
+            .collect();
+        errors.sort_by_key(|(error, _)| error.position());
+
+        for (error, severity) in errors {
+            // Only lint reports use the label of their severity; every other
+            // error has its own.
+            let label = severity.label();
+            // A runtime error found by folding is reported before anything
+            // runs, so it is an ordinary error.
+            let (error, runtime_label) = match error {
+                ErrorKind::FailsWhenRun { error } => (&**error, "Error"),
+                error => (error, "Runtime error"),
+            };
             match error {
                 ErrorKind::UnexpectedToken {
                     expected,
                     found: _,
                     position,
                 } => {
-                    let range_start = if *position as isize - 10 > 0 {
-                        *position - 10
-                    } else {
-                        0
-                    };
-                    let range_end = if *position + 10 < self.input.len() {
-                        *position + 10
-                    } else {
-                        self.input.len()
-                    };
+                    // The context stays on the line of the error, so a
+                    // missing token at a line end does not drag in the next
+                    // line.
+                    let position = self.floor_char_boundary(*position);
+                    let line = &source.lines()[source.get_line_index(position)];
+                    let line_end = line.get_start() + line.get_length();
+                    let range_start =
+                        self.floor_char_boundary(position.saturating_sub(10).max(line.get_start()));
+                    let character_end = self.input[position..line_end.max(position)]
+                        .chars()
+                        .next()
+                        .map_or(position, |c| position + c.len_utf8());
+                    let range_end =
+                        self.floor_char_boundary((position + 10).min(line_end).max(character_end));
 
-                    let start = &self.input[range_start..*position];
-                    let character = &self.input[*position..*position];
+                    let start = &self.input[range_start..position];
+                    let character = &self.input[position..character_end];
 
                     let mut tokens = String::from(start);
                     tokens.push_str(&character.red().bold().to_string().underline());
-                    tokens.push_str(&self.input[*position..range_end]);
+                    tokens.push_str(&self.input[character_end..range_end]);
 
                     println!(
-                        "{}: Unexpected token {} found at position {}. Expected {}.",
+                        "{}: Unexpected token {} found at {}. Expected {}.",
                         "Error".red().bold(),
                         tokens.bold().red(),
-                        position,
-                        format!("{:?}", expected.syntax()).green().bold()
+                        self.location(&source, position),
+                        format!("{:?}", expected.kind()).green().bold()
+                    );
+                }
+                ErrorKind::UnknownToken { token, position } => {
+                    println!(
+                        "{}: Unknown token {} found at {}.",
+                        "Error".red().bold(),
+                        format!("{:?}", token.kind()).red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ExpectedExpression { found, position } => {
+                    println!(
+                        "{}: Expected an expression at {}, found {}.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                        format!("{:?}", found.kind()).red().bold(),
+                    );
+                }
+                ErrorKind::UnterminatedComment { position } => {
+                    println!(
+                        "{}: Unterminated block comment starting at {}.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidAssignmentTarget { position } => {
+                    println!(
+                        "{}: Cannot assign to the expression at {}. Only variables, indexes and fields can be assigned.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidBinaryOperands {
+                    operator,
+                    left,
+                    right,
+                    position,
+                } => {
+                    println!(
+                        "{}: Operator {} cannot be applied to {} and {} at {}.",
+                        "Type error".red().bold(),
+                        operator.red().bold(),
+                        left.green().bold(),
+                        right.green().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidUnaryOperand {
+                    operator,
+                    operand,
+                    position,
+                } => {
+                    println!(
+                        "{}: Operator {} cannot be applied to {} at {}.",
+                        "Type error".red().bold(),
+                        operator.red().bold(),
+                        operand.green().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ShiftOutOfRange { amount, position } => {
+                    println!(
+                        "{}: Cannot shift by {} bits at {}. The amount must be between 0 and 63.",
+                        runtime_label.red().bold(),
+                        amount.to_string().red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidIndex {
+                    target,
+                    index,
+                    position,
+                } => {
+                    let hint = if target.ends_with('?') {
+                        "Use ?[ on a value that may be null."
+                    } else {
+                        "Arrays and strings take an int index and objects a string."
+                    };
+                    println!(
+                        "{}: Cannot index {} with {} at {}. {}",
+                        "Type error".red().bold(),
+                        target.green().bold(),
+                        index.green().bold(),
+                        self.location(&source, *position),
+                        hint,
+                    );
+                }
+                ErrorKind::InvalidMemberAccess {
+                    name,
+                    target,
+                    position,
+                } => {
+                    let hint = if target.ends_with('?') {
+                        "Use ?. on a value that may be null."
+                    } else {
+                        "Only objects have fields."
+                    };
+                    println!(
+                        "{}: Cannot read field {} of {} at {}. {}",
+                        "Type error".red().bold(),
+                        name.red().bold(),
+                        target.green().bold(),
+                        self.location(&source, *position),
+                        hint,
+                    );
+                }
+                ErrorKind::InvalidCondition { found, position } => {
+                    println!(
+                        "{}: Condition at {} must be a bool, found {}.",
+                        "Type error".red().bold(),
+                        self.location(&source, *position),
+                        found.green().bold(),
+                    );
+                }
+                ErrorKind::IndexOutOfRange {
+                    index,
+                    length,
+                    position,
+                } => {
+                    println!(
+                        "{}: Index {} at {} is out of range for a length of {}.",
+                        runtime_label.red().bold(),
+                        index.to_string().red().bold(),
+                        self.location(&source, *position),
+                        length.to_string().green().bold(),
+                    );
+                }
+                ErrorKind::NoSuchField { name, position } => {
+                    println!(
+                        "{}: The object at {} has no field {}.",
+                        runtime_label.red().bold(),
+                        self.location(&source, *position),
+                        name.red().bold(),
+                    );
+                }
+                ErrorKind::CannotIndex {
+                    target,
+                    index,
+                    position,
+                } => {
+                    println!(
+                        "{}: Cannot index {} with {} at {}. Arrays and strings take an int index, objects a string.",
+                        runtime_label.red().bold(),
+                        target.red().bold(),
+                        index.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::CannotAccessMember {
+                    name,
+                    target,
+                    position,
+                } => {
+                    println!(
+                        "{}: Cannot read field {} of {} at {}. Only objects have fields.",
+                        runtime_label.red().bold(),
+                        name.red().bold(),
+                        target.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::CharacterAssignment { position } => {
+                    println!(
+                        "{}: Cannot assign to a character of a string at {}. Strings cannot be changed; build a new one instead.",
+                        runtime_label.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::DivisionByZero { position } => {
+                    println!(
+                        "{}: Division by zero at {}.",
+                        runtime_label.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::IntegerOverflow { operator, position } => {
+                    println!(
+                        "{}: Integer overflow in {} at {}.",
+                        runtime_label.red().bold(),
+                        operator.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidNumberLiteral { position } => {
+                    println!(
+                        "{}: Number literal at {} has an exponent without digits.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidDecimalLiteral { position } => {
+                    println!(
+                        "{}: Decimal literal at {} does not fit in a decimal.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::DecimalOverflow { operator, position } => {
+                    println!(
+                        "{}: Decimal overflow in {} at {}.",
+                        runtime_label.red().bold(),
+                        operator.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::UnknownType { name, position } => {
+                    println!(
+                        "{}: Unknown type {} at {}.",
+                        "Type error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InvalidCast { from, to, position } => {
+                    println!(
+                        "{}: Cannot convert {} to {} at {}.",
+                        "Type error".red().bold(),
+                        from.green().bold(),
+                        to.green().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ConversionOutOfRange { from, to, position } => {
+                    println!(
+                        "{}: The {} at {} does not fit in a {}.",
+                        runtime_label.red().bold(),
+                        from.green().bold(),
+                        self.location(&source, *position),
+                        to.green().bold(),
+                    );
+                }
+                ErrorKind::UndefinedVariable { name, position } => {
+                    println!(
+                        "{}: Variable {} is not declared at {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::DuplicateDeclaration { name, position } => {
+                    println!(
+                        "{}: {} is already declared in this scope at {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::TypeMismatch {
+                    expected,
+                    found,
+                    position,
+                } => {
+                    println!(
+                        "{}: Expected a value of type {} at {}, found {}.",
+                        "Type error".red().bold(),
+                        expected.green().bold(),
+                        self.location(&source, *position),
+                        found.green().bold(),
+                    );
+                }
+                ErrorKind::ImmutableAssignment { name, position } => {
+                    println!(
+                        "{}: {} at {} cannot be assigned. Only variables declared with {} can.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                        "let mut".green().bold(),
+                    );
+                }
+                ErrorKind::ShadowedConstant { name, position } => {
+                    println!(
+                        "{}: Constant {} cannot be shadowed at {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::NonConstantInitializer { name, position } => {
+                    println!(
+                        "{}: The value of constant {} at {} must be made of literals, constants and operators.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::UndefinedFunction { name, position } => {
+                    println!(
+                        "{}: Function {} is not declared at {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::WrongArgumentCount {
+                    name,
+                    expected,
+                    found,
+                    position,
+                } => {
+                    println!(
+                        "{}: Function {} expects {} arguments at {}, found {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        expected.to_string().green().bold(),
+                        self.location(&source, *position),
+                        found.to_string().red().bold(),
+                    );
+                }
+                ErrorKind::MisplacedFunctionDeclaration { position } => {
+                    println!(
+                        "{}: Functions can only be declared at the top level, not at {}.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ReturnOutsideFunction { position } => {
+                    println!(
+                        "{}: Cannot return from outside a function at {}.",
+                        "Error".red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::UnassignedVariable { name, position } => {
+                    println!(
+                        "{}: Variable {} has no value at {}.",
+                        runtime_label.red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::StackOverflow { name, position } => {
+                    println!(
+                        "{}: Too many nested calls to {} at {}.",
+                        runtime_label.red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::LoopControlOutsideLoop { keyword, position } => {
+                    println!(
+                        "{}: {} at {} must be inside a loop.",
+                        "Error".red().bold(),
+                        keyword.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::MissingReturn { name, position } => {
+                    println!(
+                        "{}: Function {} can reach the end of its body at {} without returning a value.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::MissingInitializer { name, position } => {
+                    println!(
+                        "{}: {} at {} needs a value. Only variables declared with {} can start without one.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                        "let mut".green().bold(),
+                    );
+                }
+                ErrorKind::PossiblyUnassignedVariable { name, position } => {
+                    println!(
+                        "{}: Variable {} may not have a value at {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::UnusedVariable { name, position } => {
+                    println!(
+                        "{}: Variable {} declared at {} is never read.",
+                        label,
+                        name.yellow().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::UnusedAssignment { name, position } => {
+                    println!(
+                        "{}: The value assigned to {} at {} is never read.",
+                        label,
+                        name.yellow().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ShadowedVariable { name, position } => {
+                    println!(
+                        "{}: {} at {} hides a variable of an outer scope with the same name.",
+                        label,
+                        name.yellow().bold(),
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ConstantCondition { position } => {
+                    println!(
+                        "{}: Condition at {} is made only of literals and constants, so it always has the same value.",
+                        label,
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::FloatEquality { position } => {
+                    println!(
+                        "{}: Floats compared at {}. Rounding can make equal results differ; compare their difference with a tolerance instead.",
+                        label,
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::UnknownAttribute { name, position } => {
+                    println!(
+                        "{}: Unknown attribute {} at {}. Only {} is supported.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                        "@allow".green().bold(),
+                    );
+                }
+                ErrorKind::UnknownLint { name, position } => {
+                    println!(
+                        "{}: Unknown lint {} at {}, expected one of {}.",
+                        "Error".red().bold(),
+                        name.red().bold(),
+                        self.location(&source, *position),
+                        Lint::ALL.map(|lint| lint.name()).join(", ").green().bold(),
+                    );
+                }
+                ErrorKind::UnreachableCode { position } => {
+                    println!(
+                        "{}: Unreachable code at {}.",
+                        label,
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::LoopNeverRuns { position } => {
+                    println!(
+                        "{}: The body of the loop at {} never runs, its condition is always false.",
+                        label,
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::InfiniteLoop { position } => {
+                    println!(
+                        "{}: The loop at {} never ends, its condition is always true and nothing in it breaks or returns.",
+                        label,
+                        self.location(&source, *position),
+                    );
+                }
+                ErrorKind::ExpectedToken {
+                    expected,
+                    found,
+                    position,
+                } => {
+                    println!(
+                        "{}: Expected {} at {}, found {}.",
+                        "Error".red().bold(),
+                        format!("{:?}", expected.kind()).green().bold(),
+                        self.location(&source, *position),
+                        format!("{:?}", found.kind()).red().bold(),
                     );
                 }
                 _ => {}
@@ -70,49 +629,309 @@ impl Diagnostics {
 #[derive(Debug, Clone)]
 pub enum ErrorKind {
     UnknownToken {
-        token: SyntaxKindDescriptor,
+        token: SyntaxToken,
         position: usize,
     },
     UnexpectedToken {
-        expected: SyntaxKindDescriptor,
-        found: SyntaxKindDescriptor,
+        expected: SyntaxToken,
+        found: SyntaxToken,
         position: usize,
     },
     UnexpectedEndOfFile {
-        expected: SyntaxKindDescriptor,
+        expected: SyntaxToken,
         position: usize,
     },
     ExpectedToken {
-        expected: SyntaxKindDescriptor,
-        found: SyntaxKindDescriptor,
+        expected: SyntaxToken,
+        found: SyntaxToken,
         position: usize,
     },
     ExpectedExpression {
-        found: SyntaxKindDescriptor,
+        found: SyntaxToken,
         position: usize,
     },
     ExpectedIdentifier {
-        found: SyntaxKindDescriptor,
+        found: SyntaxToken,
         position: usize,
     },
     ExpectedEquals {
-        found: SyntaxKindDescriptor,
+        found: SyntaxToken,
         position: usize,
     },
     ExpectedSemicolon {
-        found: SyntaxKindDescriptor,
+        found: SyntaxToken,
         position: usize,
     },
     ParserError {
-        token: SyntaxKindDescriptor,
+        token: SyntaxToken,
         position: usize,
     },
     LexerError {
-        token: SyntaxKindDescriptor,
+        token: SyntaxToken,
+        position: usize,
+    },
+    UnterminatedComment {
+        position: usize,
+    },
+    InvalidAssignmentTarget {
+        position: usize,
+    },
+    InvalidBinaryOperands {
+        operator: String,
+        left: &'static str,
+        right: &'static str,
+        position: usize,
+    },
+    InvalidUnaryOperand {
+        operator: String,
+        operand: &'static str,
+        position: usize,
+    },
+    ShiftOutOfRange {
+        amount: BigInt,
+        position: usize,
+    },
+    InvalidIndex {
+        target: &'static str,
+        index: &'static str,
+        position: usize,
+    },
+    InvalidMemberAccess {
+        name: String,
+        target: &'static str,
+        position: usize,
+    },
+    InvalidCondition {
+        found: &'static str,
+        position: usize,
+    },
+    IndexOutOfRange {
+        index: i64,
+        length: usize,
+        position: usize,
+    },
+    NoSuchField {
+        name: String,
+        position: usize,
+    },
+    CannotIndex {
+        target: &'static str,
+        index: &'static str,
+        position: usize,
+    },
+    CannotAccessMember {
+        name: String,
+        target: &'static str,
+        position: usize,
+    },
+    CharacterAssignment {
+        position: usize,
+    },
+    /// A runtime error that an operator on literals would always raise,
+    /// found before anything runs.
+    FailsWhenRun {
+        error: Box<ErrorKind>,
+    },
+    DivisionByZero {
+        position: usize,
+    },
+    IntegerOverflow {
+        operator: String,
+        position: usize,
+    },
+    InvalidNumberLiteral {
+        position: usize,
+    },
+    InvalidDecimalLiteral {
+        position: usize,
+    },
+    DecimalOverflow {
+        operator: String,
+        position: usize,
+    },
+    UnknownType {
+        name: String,
+        position: usize,
+    },
+    InvalidCast {
+        from: &'static str,
+        to: String,
+        position: usize,
+    },
+    ConversionOutOfRange {
+        from: &'static str,
+        to: String,
+        position: usize,
+    },
+    UndefinedVariable {
+        name: String,
+        position: usize,
+    },
+    DuplicateDeclaration {
+        name: String,
+        position: usize,
+    },
+    TypeMismatch {
+        expected: &'static str,
+        found: &'static str,
+        position: usize,
+    },
+    ImmutableAssignment {
+        name: String,
+        position: usize,
+    },
+    ShadowedConstant {
+        name: String,
+        position: usize,
+    },
+    NonConstantInitializer {
+        name: String,
+        position: usize,
+    },
+    UndefinedFunction {
+        name: String,
+        position: usize,
+    },
+    WrongArgumentCount {
+        name: String,
+        expected: usize,
+        found: usize,
+        position: usize,
+    },
+    MisplacedFunctionDeclaration {
+        position: usize,
+    },
+    ReturnOutsideFunction {
+        position: usize,
+    },
+    UnassignedVariable {
+        name: String,
+        position: usize,
+    },
+    StackOverflow {
+        name: String,
+        position: usize,
+    },
+    LoopControlOutsideLoop {
+        keyword: String,
+        position: usize,
+    },
+    MissingReturn {
+        name: String,
+        position: usize,
+    },
+    MissingInitializer {
+        name: String,
+        position: usize,
+    },
+    PossiblyUnassignedVariable {
+        name: String,
+        position: usize,
+    },
+    UnusedVariable {
+        name: String,
+        position: usize,
+    },
+    UnusedAssignment {
+        name: String,
+        position: usize,
+    },
+    ShadowedVariable {
+        name: String,
+        position: usize,
+    },
+    ConstantCondition {
+        position: usize,
+    },
+    FloatEquality {
+        position: usize,
+    },
+    UnknownAttribute {
+        name: String,
+        position: usize,
+    },
+    UnknownLint {
+        name: String,
+        position: usize,
+    },
+    UnreachableCode {
+        position: usize,
+    },
+    LoopNeverRuns {
+        position: usize,
+    },
+    InfiniteLoop {
         position: usize,
     },
     InvalidCharacterError {
-        token: SyntaxKindDescriptor,
+        token: SyntaxToken,
         position: usize,
     },
 }
+
+impl ErrorKind {
+    /// The byte offset in the input the error points at.
+    pub fn position(&self) -> usize {
+        match self {
+            ErrorKind::UnknownToken { position, .. }
+            | ErrorKind::UnexpectedToken { position, .. }
+            | ErrorKind::UnexpectedEndOfFile { position, .. }
+            | ErrorKind::ExpectedToken { position, .. }
+            | ErrorKind::ExpectedExpression { position, .. }
+            | ErrorKind::ExpectedIdentifier { position, .. }
+            | ErrorKind::ExpectedEquals { position, .. }
+            | ErrorKind::ExpectedSemicolon { position, .. }
+            | ErrorKind::ParserError { position, .. }
+            | ErrorKind::LexerError { position, .. }
+            | ErrorKind::UnterminatedComment { position }
+            | ErrorKind::InvalidAssignmentTarget { position }
+            | ErrorKind::InvalidBinaryOperands { position, .. }
+            | ErrorKind::InvalidUnaryOperand { position, .. }
+            | ErrorKind::ShiftOutOfRange { position, .. }
+            | ErrorKind::InvalidIndex { position, .. }
+            | ErrorKind::InvalidMemberAccess { position, .. }
+            | ErrorKind::InvalidCondition { position, .. }
+            | ErrorKind::IndexOutOfRange { position, .. }
+            | ErrorKind::NoSuchField { position, .. }
+            | ErrorKind::CannotIndex { position, .. }
+            | ErrorKind::CannotAccessMember { position, .. }
+            | ErrorKind::CharacterAssignment { position }
+            | ErrorKind::DivisionByZero { position }
+            | ErrorKind::IntegerOverflow { position, .. }
+            | ErrorKind::InvalidNumberLiteral { position }
+            | ErrorKind::InvalidDecimalLiteral { position }
+            | ErrorKind::DecimalOverflow { position, .. }
+            | ErrorKind::UnknownType { position, .. }
+            | ErrorKind::InvalidCast { position, .. }
+            | ErrorKind::ConversionOutOfRange { position, .. }
+            | ErrorKind::UndefinedVariable { position, .. }
+            | ErrorKind::DuplicateDeclaration { position, .. }
+            | ErrorKind::TypeMismatch { position, .. }
+            | ErrorKind::ImmutableAssignment { position, .. }
+            | ErrorKind::ShadowedConstant { position, .. }
+            | ErrorKind::NonConstantInitializer { position, .. }
+            | ErrorKind::UndefinedFunction { position, .. }
+            | ErrorKind::WrongArgumentCount { position, .. }
+            | ErrorKind::MisplacedFunctionDeclaration { position }
+            | ErrorKind::ReturnOutsideFunction { position }
+            | ErrorKind::UnassignedVariable { position, .. }
+            | ErrorKind::StackOverflow { position, .. }
+            | ErrorKind::LoopControlOutsideLoop { position, .. }
+            | ErrorKind::MissingReturn { position, .. }
+            | ErrorKind::MissingInitializer { position, .. }
+            | ErrorKind::PossiblyUnassignedVariable { position, .. }
+            | ErrorKind::UnusedVariable { position, .. }
+            | ErrorKind::UnusedAssignment { position, .. }
+            | ErrorKind::ShadowedVariable { position, .. }
+            | ErrorKind::ConstantCondition { position }
+            | ErrorKind::FloatEquality { position }
+            | ErrorKind::UnknownAttribute { position, .. }
+            | ErrorKind::UnknownLint { position, .. }
+            | ErrorKind::UnreachableCode { position }
+            | ErrorKind::LoopNeverRuns { position }
+            | ErrorKind::InfiniteLoop { position }
+            | ErrorKind::InvalidCharacterError { position, .. } => *position,
+            ErrorKind::FailsWhenRun { error } => error.position(),
+        }
+    }
+}
@@ -0,0 +1,248 @@
+use crate::{
+    binding::{
+        BoundAssignmentExpression, BoundCompoundAssignmentExpression, BoundExpression,
+        BoundIndexExpression, BoundMemberAccessExpression, BoundUpdateExpression,
+    },
+    diagnostics::ErrorKind,
+    symbols::VariableSymbol,
+};
+
+use super::{
+    binary_expression_evaluator, check_type, ArrayElements, Evaluator, ObjectFields, Value,
+};
+
+/// A location that can be read and written: a variable, an array element or
+/// an object field. Resolving a place evaluates the expressions that pick it
+/// out, such as the array and the index, exactly once.
+///
+/// Each place keeps the position of the expression naming it, for errors.
+pub enum Place {
+    Variable(VariableSymbol, usize),
+    Element(ArrayElements, usize),
+    Field(ObjectFields, String, usize),
+    /// A character of a string, which can be read but not assigned.
+    Character(char, usize),
+}
+
+impl Place {
+    pub fn get(&self, evaluator: &Evaluator) -> Result<Value, Box<ErrorKind>> {
+        let value = match self {
+            Place::Variable(variable, position) => evaluator.variable(variable, *position)?,
+            Place::Element(elements, index) => elements.borrow()[*index].clone(),
+            Place::Field(fields, name, position) => fields
+                .borrow()
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value.clone())
+                .ok_or_else(|| {
+                    Box::new(ErrorKind::NoSuchField {
+                        name: name.clone(),
+                        position: *position,
+                    })
+                })?,
+            Place::Character(character, _) => Value::Char(*character),
+        };
+        Ok(value)
+    }
+
+    /// Stores `value`. A variable only takes values of the type it was
+    /// declared with. Assigning to a field the object does not have yet
+    /// adds it.
+    pub fn set(&self, evaluator: &mut Evaluator, value: Value) -> Result<(), Box<ErrorKind>> {
+        match self {
+            Place::Variable(variable, position) => {
+                check_type(variable.type_symbol(), &value, *position)?;
+                evaluator.assign(variable, value);
+            }
+            Place::Element(elements, index) => elements.borrow_mut()[*index] = value,
+            Place::Field(fields, name, _) => {
+                let mut fields = fields.borrow_mut();
+                match fields.iter_mut().find(|(field, _)| field == name) {
+                    Some((_, existing)) => *existing = value,
+                    None => fields.push((name.clone(), value)),
+                }
+            }
+            Place::Character(_, position) => {
+                return Err(Box::new(ErrorKind::CharacterAssignment {
+                    position: *position,
+                }));
+            }
+        }
+        Ok(())
+    }
+}
+
+pub fn evaluate(
+    evaluator: &mut Evaluator,
+    expression: &BoundAssignmentExpression,
+) -> Result<Value, Box<ErrorKind>> {
+    let Some(place) = resolve_place(evaluator, &expression.target)? else {
+        return Ok(Value::Null);
+    };
+    let value = evaluator.evaluate_expression(&expression.value)?;
+    place.set(evaluator, value.clone())?;
+    Ok(value)
+}
+
+/// Reads the target, combines it with the value and writes the result back.
+/// The target is resolved first and only once, so in `xs[i] += f()` neither
+/// `xs` nor `i` is evaluated twice.
+pub fn evaluate_compound(
+    evaluator: &mut Evaluator,
+    expression: &BoundCompoundAssignmentExpression,
+) -> Result<Value, Box<ErrorKind>> {
+    let Some(place) = resolve_place(evaluator, &expression.target)? else {
+        return Ok(Value::Null);
+    };
+    let current = place.get(evaluator)?;
+    // `&&=` and `||=` leave the target alone when it already decides the
+    // result, without evaluating the value.
+    if let Some(result) =
+        binary_expression_evaluator::short_circuit(&expression.binary_operator, &current)
+    {
+        return Ok(result);
+    }
+    let value = evaluator.evaluate_expression(&expression.value)?;
+    let result = binary_expression_evaluator::evaluate_operator(
+        &expression.binary_operator,
+        &expression.operator_token,
+        evaluator.arithmetic,
+        current,
+        value,
+    )?;
+    place.set(evaluator, result.clone())?;
+    Ok(result)
+}
+
+/// `++` and `--`. The prefix forms give the updated value, the postfix forms
+/// the value the operand had before.
+pub fn evaluate_update(
+    evaluator: &mut Evaluator,
+    expression: &BoundUpdateExpression,
+) -> Result<Value, Box<ErrorKind>> {
+    let Some(place) = resolve_place(evaluator, &expression.operand)? else {
+        return Ok(Value::Null);
+    };
+    let current = place.get(evaluator)?;
+    let updated = binary_expression_evaluator::evaluate_operator(
+        &expression.binary_operator,
+        &expression.operator_token,
+        evaluator.arithmetic,
+        current.clone(),
+        Value::Int(1),
+    )?;
+    place.set(evaluator, updated.clone())?;
+    if expression.is_prefix {
+        Ok(updated)
+    } else {
+        Ok(current)
+    }
+}
+
+/// Resolves the place `target` names. `None` means a `?.` or `?[` in the
+/// target found null, in which case the whole assignment is skipped and gives
+/// null, as in C#.
+pub fn resolve_place(
+    evaluator: &mut Evaluator,
+    target: &BoundExpression,
+) -> Result<Option<Place>, Box<ErrorKind>> {
+    let place = match target {
+        BoundExpression::BoundVariableExpression(expression) => Some(Place::Variable(
+            expression.variable.clone(),
+            expression.span.get_start(),
+        )),
+        BoundExpression::BoundIndexExpression(expression) => index_place(evaluator, expression)?,
+        BoundExpression::BoundMemberAccessExpression(expression) => {
+            member_place(evaluator, expression)?
+        }
+        expression => panic!("Cannot assign to {:?}", expression),
+    };
+    Ok(place)
+}
+
+/// Evaluates the target of an index or member access. A target that is
+/// itself part of a `?.`/`?[` chain that found null gives `None`, so the
+/// rest of the chain is skipped too: `a?.b.c` is null when `a` is, rather
+/// than an error for reading `c` from null.
+fn chain_target(
+    evaluator: &mut Evaluator,
+    target: &BoundExpression,
+) -> Result<Option<Value>, Box<ErrorKind>> {
+    let place = match target {
+        BoundExpression::BoundIndexExpression(expression) => index_place(evaluator, expression)?,
+        BoundExpression::BoundMemberAccessExpression(expression) => {
+            member_place(evaluator, expression)?
+        }
+        _ => return evaluator.evaluate_expression(target).map(Some),
+    };
+    place.map(|place| place.get(evaluator)).transpose()
+}
+
+pub fn index_place(
+    evaluator: &mut Evaluator,
+    expression: &BoundIndexExpression,
+) -> Result<Option<Place>, Box<ErrorKind>> {
+    let target = match chain_target(evaluator, &expression.target)? {
+        Some(Value::Null) if expression.is_conditional => return Ok(None),
+        Some(target) => target,
+        None => return Ok(None),
+    };
+    let index = evaluator.evaluate_expression(&expression.index)?;
+    let position = expression.span.get_start();
+    let index_position = expression.index.span().get_start();
+    // Strings are indexed by character, not by byte.
+    let place = match (target, index) {
+        (Value::Array(elements), Value::Int(index)) => {
+            let length = elements.borrow().len();
+            Place::Element(elements, check_index(index, length, index_position)?)
+        }
+        (Value::String(value), Value::Int(index)) => {
+            let length = value.chars().count();
+            let index = check_index(index, length, index_position)?;
+            let character = value.chars().nth(index).expect("the index was checked");
+            Place::Character(character, position)
+        }
+        (Value::Object(fields), Value::String(name)) => Place::Field(fields, name, position),
+        (target, index) => {
+            return Err(Box::new(ErrorKind::CannotIndex {
+                target: target.type_name(),
+                index: index.type_name(),
+                position,
+            }))
+        }
+    };
+    Ok(Some(place))
+}
+
+pub fn member_place(
+    evaluator: &mut Evaluator,
+    expression: &BoundMemberAccessExpression,
+) -> Result<Option<Place>, Box<ErrorKind>> {
+    let position = expression.span.get_start();
+    match chain_target(evaluator, &expression.target)? {
+        Some(Value::Null) if expression.is_conditional => Ok(None),
+        Some(Value::Object(fields)) => Ok(Some(Place::Field(
+            fields,
+            expression.name.to_string(),
+            position,
+        ))),
+        None => Ok(None),
+        Some(target) => Err(Box::new(ErrorKind::CannotAccessMember {
+            name: expression.name.to_string(),
+            target: target.type_name(),
+            position,
+        })),
+    }
+}
+
+/// Checks that `index` picks one of `length` elements.
+fn check_index(index: i64, length: usize, position: usize) -> Result<usize, Box<ErrorKind>> {
+    match usize::try_from(index) {
+        Ok(index) if index < length => Ok(index),
+        _ => Err(Box::new(ErrorKind::IndexOutOfRange {
+            index,
+            length,
+            position,
+        })),
+    }
+}
@@ -1,164 +1,769 @@
-use crate::syntax::SyntaxKind;
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{ToPrimitive, Zero};
+use rust_decimal::Decimal;
 
-use super::{EvaluationResult, Types};
+use crate::{
+    binding::BoundBinaryExpression,
+    diagnostics::ErrorKind,
+    syntax::{Keywords, SyntaxKind, SyntaxToken, Tokens},
+};
+
+use super::{Arithmetic, Evaluator, OverflowPolicy, RoundingMode, Value};
 
 pub fn evaluate(
-    left: SyntaxKind,
-    right: SyntaxKind,
-    operator: SyntaxKind,
-    variables: &mut Vec<(String, Types)>,
-) -> EvaluationResult {
-    let mut left = super::evaluate(left, variables);
-    let mut right = super::evaluate(right, variables);
-    let left_clone = left.clone();
-    let right_clone = right.clone();
-
-    loop {
-        match left {
-            EvaluationResult::Syntax(syntax) => {
-                left = super::evaluate(syntax, variables);
-            }
-            _ => {}
+    evaluator: &mut Evaluator,
+    expression: &BoundBinaryExpression,
+) -> Result<Value, Box<ErrorKind>> {
+    let left = evaluator.evaluate_expression(&expression.left)?;
+    if let SyntaxKind::Token(operator) = expression.operator.kind() {
+        if let Some(result) = short_circuit(operator, &left) {
+            return Ok(result);
         }
-        match right {
-            EvaluationResult::Syntax(syntax) => {
-                right = super::evaluate(syntax, variables);
-            }
-            _ => {
-                break;
-            }
+    }
+    let right = evaluator.evaluate_expression(&expression.right)?;
+    // `??` only gets here when the left side is null.
+    if expression.operator.kind() == &SyntaxKind::Token(Tokens::QuestionMarkQuestionMarkToken) {
+        return Ok(right);
+    }
+    apply(&expression.operator, evaluator.arithmetic, left, right)
+}
+
+/// The result of `left operator right` when `left` alone decides it, so the
+/// right side is not evaluated: `false && _`, `true || _`, and `??` on a
+/// value that is not null.
+pub fn short_circuit(operator: &Tokens, left: &Value) -> Option<Value> {
+    match (operator, left) {
+        (Tokens::AmpersandAmpersandToken, Value::Boolean(false))
+        | (Tokens::PipePipeToken, Value::Boolean(true)) => Some(left.clone()),
+        (Tokens::QuestionMarkQuestionMarkToken, Value::Null) => None,
+        (Tokens::QuestionMarkQuestionMarkToken, _) => Some(left.clone()),
+        _ => None,
+    }
+}
+
+/// Applies the binary operator `operator_token` stands for, which may be the
+/// `div` keyword. `??` has to be handled by the caller, since it does not
+/// always use its right side.
+pub fn apply(
+    operator_token: &SyntaxToken,
+    arithmetic: Arithmetic,
+    left: Value,
+    right: Value,
+) -> Result<Value, Box<ErrorKind>> {
+    match operator_token.kind() {
+        SyntaxKind::Token(operator) => {
+            evaluate_operator(operator, operator_token, arithmetic, left, right)
+        }
+        SyntaxKind::Keyword(Keywords::DivKeyword) => {
+            floor_divide(operator_token, arithmetic, left, right)
         }
+        operator => panic!("{:?} is not a binary operator", operator),
     }
+}
 
-    match operator {
-        SyntaxKind::Token(crate::syntax::Tokens::PlusToken) => match (left, right) {
-            (EvaluationResult::String(left), EvaluationResult::String(right)) => {
-                EvaluationResult::String(format!("{}{}", left, right))
-            }
-            (EvaluationResult::Int(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Int(left + right)
-            }
-            (EvaluationResult::Float(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left + right)
-            }
-            (EvaluationResult::Int(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left as f64 + right)
-            }
+/// Applies a binary operator to two values that are already evaluated. Also
+/// used by compound assignments, which read their left operand from a place.
+/// `operator_token` is the token written in the source, which errors point
+/// at.
+pub fn evaluate_operator(
+    operator: &Tokens,
+    operator_token: &SyntaxToken,
+    arithmetic: Arithmetic,
+    left: Value,
+    right: Value,
+) -> Result<Value, Box<ErrorKind>> {
+    let invalid_operands = ErrorKind::InvalidBinaryOperands {
+        operator: operator_token.text().to_string(),
+        left: left.type_name(),
+        right: right.type_name(),
+        position: operator_token.position(),
+    };
+    let (left, right) = widen(operator_token, left, right)?;
 
-            (EvaluationResult::Float(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Float(left + right as f64)
-            }
+    let result = match operator {
+        Tokens::PlusToken => match (left, right) {
+            (Value::String(left), Value::String(right)) => {
+                Value::String(format!("{}{}", left, right))
+            }
+            (Value::Int(left), Value::Int(right)) => checked_int(
+                arithmetic.overflow,
+                operator_token,
+                left.checked_add(right),
+                || left.wrapping_add(right),
+                || left.saturating_add(right),
+                || BigInt::from(left) + right,
+            )?,
+            (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left + right),
+            (Value::Decimal(left), Value::Decimal(right)) => {
+                checked_decimal(operator_token, left.checked_add(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => Value::Float(left + right),
+            (Value::Int(left), Value::Float(right)) => Value::Float(left as f64 + right),
 
-            _ => {
-                panic!("Cannot add {:?} and {:?}", left_clone, right_clone);
-            }
+            (Value::Float(left), Value::Int(right)) => Value::Float(left + right as f64),
+
+            _ => return Err(invalid_operands.into()),
         },
-        SyntaxKind::Token(crate::syntax::Tokens::MinusToken) => match (left, right) {
-            (EvaluationResult::Int(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Int(left - right)
-            }
-            (EvaluationResult::Float(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left - right)
-            }
-            (EvaluationResult::Int(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left as f64 - right)
-            }
+        Tokens::MinusToken => match (left, right) {
+            (Value::Int(left), Value::Int(right)) => checked_int(
+                arithmetic.overflow,
+                operator_token,
+                left.checked_sub(right),
+                || left.wrapping_sub(right),
+                || left.saturating_sub(right),
+                || BigInt::from(left) - right,
+            )?,
+            (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left - right),
+            (Value::Decimal(left), Value::Decimal(right)) => {
+                checked_decimal(operator_token, left.checked_sub(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => Value::Float(left - right),
+            (Value::Int(left), Value::Float(right)) => Value::Float(left as f64 - right),
 
-            (EvaluationResult::Float(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Float(left - right as f64)
-            }
-            _ => {
-                panic!("Cannot subtract {:?} and {:?}", left_clone, right_clone);
-            }
+            (Value::Float(left), Value::Int(right)) => Value::Float(left - right as f64),
+            _ => return Err(invalid_operands.into()),
         },
-        SyntaxKind::Token(crate::syntax::Tokens::StarToken) => match (left, right) {
-            (EvaluationResult::Int(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Int(left * right)
-            }
-            (EvaluationResult::Float(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left * right)
-            }
-            (EvaluationResult::Int(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left as f64 * right)
-            }
+        Tokens::StarToken => match (left, right) {
+            (Value::Int(left), Value::Int(right)) => checked_int(
+                arithmetic.overflow,
+                operator_token,
+                left.checked_mul(right),
+                || left.wrapping_mul(right),
+                || left.saturating_mul(right),
+                || BigInt::from(left) * right,
+            )?,
+            (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left * right),
+            (Value::Decimal(left), Value::Decimal(right)) => {
+                checked_decimal(operator_token, left.checked_mul(right))?
+            }
+            (Value::Float(left), Value::Float(right)) => Value::Float(left * right),
+            (Value::Int(left), Value::Float(right)) => Value::Float(left as f64 * right),
 
-            (EvaluationResult::Float(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Float(left * right as f64)
-            }
-            _ => {
-                panic!("Cannot multiply {:?} and {:?}", left_clone, right_clone);
-            }
+            (Value::Float(left), Value::Int(right)) => Value::Float(left * right as f64),
+            _ => return Err(invalid_operands.into()),
         },
-        SyntaxKind::Token(crate::syntax::Tokens::SlashToken) => match (left, right) {
-            (EvaluationResult::Float(a), EvaluationResult::Float(b)) => {
-                if b == 0.0 {
-                    panic!("Cannot divide by zero");
-                }
-                return EvaluationResult::Float(a / b);
-            }
-            (EvaluationResult::Int(a), EvaluationResult::Int(b)) => {
-                if b == 0 {
-                    panic!("Cannot divide by zero");
-                }
-                EvaluationResult::Float(a as f64 / b as f64)
-            }
-            (EvaluationResult::Int(a), EvaluationResult::Float(b)) => {
-                if b == 0.0 {
-                    panic!("Cannot divide by zero");
-                }
-                EvaluationResult::Float(a as f64 / b)
-            }
-            (EvaluationResult::Float(a), EvaluationResult::Int(b)) => {
-                if b == 0 {
-                    panic!("Cannot divide by zero");
-                }
-                EvaluationResult::Float(a / b as f64)
-            }
-            _ => {
-                panic!("Cannot divide {:?} and {:?}", left_clone, right_clone);
-            }
+        Tokens::SlashToken => match check_divisor(operator_token, left, right)? {
+            (Value::Float(a), Value::Float(b)) => Value::Float(a / b),
+            (Value::Int(a), Value::Int(b)) => Value::Float(a as f64 / b as f64),
+            (Value::Int(a), Value::Float(b)) => Value::Float(a as f64 / b),
+            (Value::Float(a), Value::Int(b)) => Value::Float(a / b as f64),
+            (Value::BigInt(a), Value::BigInt(b)) => {
+                Value::Float(big_int_to_float(&a) / big_int_to_float(&b))
+            }
+            (Value::Decimal(a), Value::Decimal(b)) => checked_decimal(
+                operator_token,
+                divide_decimal(a, b, arithmetic.rounding, Decimal::MAX_SCALE),
+            )?,
+            _ => return Err(invalid_operands.into()),
         },
-        SyntaxKind::Token(crate::syntax::Tokens::PercentToken) => match (left, right) {
-            (EvaluationResult::Int(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Int(left % right)
+        Tokens::PercentToken => match check_divisor(operator_token, left, right)? {
+            (Value::Int(left), Value::Int(right)) => Value::Int(floor_mod(left, right)),
+            (Value::Float(left), Value::Float(right)) => Value::Float(floor_mod_float(left, right)),
+            (Value::Int(left), Value::Float(right)) => {
+                Value::Float(floor_mod_float(left as f64, right))
             }
-            (EvaluationResult::Float(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left % right)
+            (Value::Float(left), Value::Int(right)) => {
+                Value::Float(floor_mod_float(left, right as f64))
             }
-            (EvaluationResult::Int(left), EvaluationResult::Float(right)) => {
-                EvaluationResult::Float(left as f64 % right)
+            (Value::BigInt(left), Value::BigInt(right)) => {
+                Value::from_big_int(left.mod_floor(&right))
             }
-            (EvaluationResult::Float(left), EvaluationResult::Int(right)) => {
-                EvaluationResult::Float(left % right as f64)
-            }
-            _ => {
-                panic!("Cannot modulo {:?} and {:?}", left_clone, right_clone);
+            (Value::Decimal(left), Value::Decimal(right)) => {
+                Value::Decimal(floor_mod_decimal(left, right))
             }
+            _ => return Err(invalid_operands.into()),
+        },
+        Tokens::EqualsEqualsToken => match equal(&left, &right) {
+            Some(equal) => Value::Boolean(equal),
+            None => return Err(invalid_operands.into()),
+        },
+        Tokens::BangEqualsToken => match equal(&left, &right) {
+            Some(equal) => Value::Boolean(!equal),
+            None => return Err(invalid_operands.into()),
+        },
+        Tokens::LessThanToken
+        | Tokens::LessThanEqualsToken
+        | Tokens::GreaterThanToken
+        | Tokens::GreaterThanEqualsToken => match compare(operator, &left, &right) {
+            Some(result) => Value::Boolean(result),
+            None => return Err(invalid_operands.into()),
+        },
+        Tokens::AmpersandAmpersandToken => match (left, right) {
+            (Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left && right),
+            _ => return Err(invalid_operands.into()),
+        },
+        Tokens::PipePipeToken => match (left, right) {
+            (Value::Boolean(left), Value::Boolean(right)) => Value::Boolean(left || right),
+            _ => return Err(invalid_operands.into()),
+        },
+        Tokens::AmpersandToken => match (left, right) {
+            (Value::Int(left), Value::Int(right)) => Value::Int(left & right),
+            (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left & right),
+            _ => return Err(invalid_operands.into()),
+        },
+        Tokens::PipeToken => match (left, right) {
+            (Value::Int(left), Value::Int(right)) => Value::Int(left | right),
+            (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left | right),
+            _ => return Err(invalid_operands.into()),
         },
-        SyntaxKind::Token(crate::syntax::Tokens::BangEqualsToken) => match (left, right) {
-            (EvaluationResult::Boolean(a), EvaluationResult::Boolean(b)) => {
-                EvaluationResult::Boolean(a != b)
-            },
-            _ => {
-                panic!("Cannot compare {:?} and {:?}", left_clone, right_clone);
+        Tokens::CaretToken => match (left, right) {
+            (Value::Int(left), Value::Int(right)) => Value::Int(left ^ right),
+            (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left ^ right),
+            _ => return Err(invalid_operands.into()),
+        },
+        Tokens::LessThanLessThanToken | Tokens::GreaterThanGreaterThanToken => {
+            match (left, right) {
+                (Value::Int(left), Value::Int(right)) => {
+                    let amount = shift_amount(operator_token, right.into())?;
+                    shift(operator, operator_token, arithmetic, left, amount)?
+                }
+                (Value::BigInt(left), Value::BigInt(right)) => {
+                    let amount = shift_amount(operator_token, right)?;
+                    match operator {
+                        Tokens::LessThanLessThanToken => Value::from_big_int(left << amount),
+                        _ => Value::from_big_int(left >> amount),
+                    }
+                }
+                _ => return Err(invalid_operands.into()),
             }
         }
-        SyntaxKind::Token(crate::syntax::Tokens::AmpersandAmpersandToken) => match (left, right) {
-            (EvaluationResult::Boolean(left), EvaluationResult::Boolean(right)) => {
-                EvaluationResult::Boolean(left && right)
-            }
-            _ => {
-                panic!(
-                    "Cannot evaluate binary expression with operator: {:?} on types {:?} and {:?}",
-                    operator, left_clone, right_clone
-                );
-            }
+        _ => return Err(invalid_operands.into()),
+    };
+    Ok(result)
+}
+
+/// The number of bits to shift by. An amount outside `0..64` is a runtime
+/// error rather than being masked the way the CPU would.
+fn shift_amount(operator_token: &SyntaxToken, amount: BigInt) -> Result<u32, Box<ErrorKind>> {
+    match u32::try_from(&amount) {
+        Ok(bits) if bits < 64 => Ok(bits),
+        _ => Err(Box::new(ErrorKind::ShiftOutOfRange {
+            amount,
+            position: operator_token.position(),
+        })),
+    }
+}
+
+/// Shifts `value` by `amount` bits. `>>` is arithmetic, so it keeps the
+/// sign. A `<<` that shifts bits out of the int overflows.
+fn shift(
+    operator: &Tokens,
+    operator_token: &SyntaxToken,
+    arithmetic: Arithmetic,
+    value: i64,
+    amount: u32,
+) -> Result<Value, Box<ErrorKind>> {
+    if operator != &Tokens::LessThanLessThanToken {
+        return Ok(Value::Int(value >> amount));
+    }
+    let shifted = value << amount;
+    checked_int(
+        arithmetic.overflow,
+        operator_token,
+        (shifted >> amount == value).then_some(shifted),
+        || shifted,
+        || if value < 0 { i64::MIN } else { i64::MAX },
+        || BigInt::from(value) << amount,
+    )
+}
+
+/// Passes the operands through unless they are numbers and the divisor is
+/// zero, which is a runtime error for ints and floats alike.
+fn check_divisor(
+    operator_token: &SyntaxToken,
+    left: Value,
+    right: Value,
+) -> Result<(Value, Value), Box<ErrorKind>> {
+    let left_is_number = matches!(
+        left,
+        Value::Int(_) | Value::BigInt(_) | Value::Decimal(_) | Value::Float(_)
+    );
+    let right_is_zero = match &right {
+        Value::Int(right) => *right == 0,
+        Value::BigInt(right) => right.is_zero(),
+        Value::Decimal(right) => right.is_zero(),
+        Value::Float(right) => *right == 0.0,
+        _ => false,
+    };
+    if left_is_number && right_is_zero {
+        return Err(Box::new(ErrorKind::DivisionByZero {
+            position: operator_token.position(),
+        }));
+    }
+    Ok((left, right))
+}
+
+/// `a div b`. Division and remainder follow one rule for every sign: `div`
+/// rounds the quotient down, towards negative infinity, and `%` is what is
+/// left over, so `a == (a div b) * b + a % b` always holds and the remainder
+/// has the sign of the divisor. `-7 div 2` is -4 and `-7 % 2` is 1. `/`
+/// always divides exactly and gives a float.
+///
+/// Gives an int for two ints, and the rounded down quotient as a float when
+/// either side is a float.
+fn floor_divide(
+    operator_token: &SyntaxToken,
+    arithmetic: Arithmetic,
+    left: Value,
+    right: Value,
+) -> Result<Value, Box<ErrorKind>> {
+    let invalid_operands = ErrorKind::InvalidBinaryOperands {
+        operator: operator_token.text().to_string(),
+        left: left.type_name(),
+        right: right.type_name(),
+        position: operator_token.position(),
+    };
+    let (left, right) = widen(operator_token, left, right)?;
+    let result = match check_divisor(operator_token, left, right)? {
+        (Value::Int(left), Value::Int(right)) => {
+            // Only `i64::MIN div -1` overflows, and its quotient is exact,
+            // so rounding down does not matter for the wrapped or clamped
+            // result.
+            checked_int(
+                arithmetic.overflow,
+                operator_token,
+                left.checked_div(right).map(|_| floor_div(left, right)),
+                || left.wrapping_div(right),
+                || left.saturating_div(right),
+                || BigInt::from(left).div_floor(&BigInt::from(right)),
+            )?
         }
-        _ => {
-            panic!(
-                "Cannot evaluate binary expression with operator: {:?} on types {:?} and {:?}",
-                operator, left_clone, right_clone
-            );
+        (Value::BigInt(left), Value::BigInt(right)) => Value::from_big_int(left.div_floor(&right)),
+        (Value::Decimal(left), Value::Decimal(right)) => checked_decimal(
+            operator_token,
+            divide_decimal(left, right, RoundingMode::Floor, 0),
+        )?,
+        (Value::Float(left), Value::Float(right)) => Value::Float((left / right).floor()),
+        (Value::Int(left), Value::Float(right)) => Value::Float((left as f64 / right).floor()),
+        (Value::Float(left), Value::Int(right)) => Value::Float((left / right as f64).floor()),
+        _ => return Err(invalid_operands.into()),
+    };
+    Ok(result)
+}
+
+fn floor_div(left: i64, right: i64) -> i64 {
+    let quotient = left / right;
+    if left % right != 0 && (left < 0) != (right < 0) {
+        quotient - 1
+    } else {
+        quotient
+    }
+}
+
+fn floor_mod(left: i64, right: i64) -> i64 {
+    // `i64::MIN % -1` is 0, but plain `%` overflows computing it.
+    let remainder = left.wrapping_rem(right);
+    if remainder != 0 && (remainder < 0) != (right < 0) {
+        remainder + right
+    } else {
+        remainder
+    }
+}
+
+fn floor_mod_decimal(left: Decimal, right: Decimal) -> Decimal {
+    // The remainder is never larger than either operand, so it always fits.
+    let remainder = left % right;
+    if !remainder.is_zero() && remainder.is_sign_negative() != right.is_sign_negative() {
+        remainder + right
+    } else {
+        remainder
+    }
+}
+
+fn floor_mod_float(left: f64, right: f64) -> f64 {
+    let remainder = left % right;
+    if remainder != 0.0 && (remainder < 0.0) != (right < 0.0) {
+        remainder + right
+    } else {
+        remainder
+    }
+}
+
+/// Gives the result of an int operation under the overflow policy. `checked`
+/// is `None` when the operation overflowed; the closures compute the result
+/// each of the other policies falls back to.
+pub fn checked_int(
+    overflow: OverflowPolicy,
+    operator_token: &SyntaxToken,
+    checked: Option<i64>,
+    wrapping: impl FnOnce() -> i64,
+    saturating: impl FnOnce() -> i64,
+    promoted: impl FnOnce() -> BigInt,
+) -> Result<Value, Box<ErrorKind>> {
+    match (checked, overflow) {
+        (Some(value), _) => Ok(Value::Int(value)),
+        (None, OverflowPolicy::Error) => Err(Box::new(ErrorKind::IntegerOverflow {
+            operator: operator_token.text().to_string(),
+            position: operator_token.position(),
+        })),
+        (None, OverflowPolicy::Wrap) => Ok(Value::Int(wrapping())),
+        (None, OverflowPolicy::Saturate) => Ok(Value::Int(saturating())),
+        (None, OverflowPolicy::Promote) => Ok(Value::from_big_int(promoted())),
+    }
+}
+
+/// Whether two widened values are equal, or `None` when `==` does not
+/// compare them. Null compares with anything and is only equal to itself.
+fn equal(left: &Value, right: &Value) -> Option<bool> {
+    match (left, right) {
+        (Value::Int(int), Value::Float(float)) | (Value::Float(float), Value::Int(int)) => {
+            Some(*int as f64 == *float)
         }
+        (Value::Null, _) | (_, Value::Null) => Some(left == right),
+        (left, right) if std::mem::discriminant(left) == std::mem::discriminant(right) => {
+            Some(left == right)
+        }
+        _ => None,
+    }
+}
+
+/// The result of `<`, `<=`, `>` or `>=` on two widened values, or `None`
+/// when they do not order. Strings order by their characters' code points,
+/// and nothing orders with a NaN.
+fn compare(operator: &Tokens, left: &Value, right: &Value) -> Option<bool> {
+    let ordering = match (left, right) {
+        (Value::Int(left), Value::Int(right)) => left.partial_cmp(right),
+        (Value::Float(left), Value::Float(right)) => left.partial_cmp(right),
+        (Value::Int(left), Value::Float(right)) => (*left as f64).partial_cmp(right),
+        (Value::Float(left), Value::Int(right)) => left.partial_cmp(&(*right as f64)),
+        (Value::BigInt(left), Value::BigInt(right)) => left.partial_cmp(right),
+        (Value::Decimal(left), Value::Decimal(right)) => left.partial_cmp(right),
+        (Value::String(left), Value::String(right)) => left.partial_cmp(right),
+        (Value::Char(left), Value::Char(right)) => left.partial_cmp(right),
+        _ => return None,
+    };
+    Some(ordering.is_some_and(|ordering| match operator {
+        Tokens::LessThanToken => ordering.is_lt(),
+        Tokens::LessThanEqualsToken => ordering.is_le(),
+        Tokens::GreaterThanToken => ordering.is_gt(),
+        _ => ordering.is_ge(),
+    }))
+}
+
+/// Brings a big int and an int to two big ints, a big int and a float to two
+/// floats, and an int or big int next to a decimal to two decimals, so the
+/// operators only have to handle operands of one kind. A decimal and a float
+/// are left alone: mixing them needs an explicit `as`.
+fn widen(
+    operator_token: &SyntaxToken,
+    left: Value,
+    right: Value,
+) -> Result<(Value, Value), Box<ErrorKind>> {
+    let operands = match (left, right) {
+        (Value::BigInt(left), Value::Int(right)) => {
+            (Value::BigInt(left), Value::BigInt(right.into()))
+        }
+        (Value::Int(left), Value::BigInt(right)) => {
+            (Value::BigInt(left.into()), Value::BigInt(right))
+        }
+        (Value::BigInt(left), Value::Float(right)) => {
+            (Value::Float(big_int_to_float(&left)), Value::Float(right))
+        }
+        (Value::Float(left), Value::BigInt(right)) => {
+            (Value::Float(left), Value::Float(big_int_to_float(&right)))
+        }
+        (Value::Decimal(left), Value::Int(right)) => {
+            (Value::Decimal(left), Value::Decimal(right.into()))
+        }
+        (Value::Int(left), Value::Decimal(right)) => {
+            (Value::Decimal(left.into()), Value::Decimal(right))
+        }
+        (Value::Decimal(left), Value::BigInt(right)) => (
+            Value::Decimal(left),
+            Value::Decimal(checked_decimal_from(operator_token, &right)?),
+        ),
+        (Value::BigInt(left), Value::Decimal(right)) => (
+            Value::Decimal(checked_decimal_from(operator_token, &left)?),
+            Value::Decimal(right),
+        ),
+        operands => operands,
+    };
+    Ok(operands)
+}
+
+/// A big int as a decimal. Only big ints past the 96 bits of a decimal fail,
+/// and any arithmetic on them would overflow anyway.
+fn checked_decimal_from(
+    operator_token: &SyntaxToken,
+    value: &BigInt,
+) -> Result<Decimal, Box<ErrorKind>> {
+    big_int_to_decimal(value).ok_or_else(|| decimal_overflow(operator_token))
+}
+
+/// Gives the result of a decimal operation, which is `None` when it did not
+/// fit in a decimal.
+fn checked_decimal(
+    operator_token: &SyntaxToken,
+    value: Option<Decimal>,
+) -> Result<Value, Box<ErrorKind>> {
+    value
+        .map(Value::Decimal)
+        .ok_or_else(|| decimal_overflow(operator_token))
+}
+
+fn decimal_overflow(operator_token: &SyntaxToken) -> Box<ErrorKind> {
+    Box::new(ErrorKind::DecimalOverflow {
+        operator: operator_token.text().to_string(),
+        position: operator_token.position(),
+    })
+}
+
+/// The decimal with the value of `value`, if it fits in a decimal's 96 bits.
+pub fn big_int_to_decimal(value: &BigInt) -> Option<Decimal> {
+    let value = i128::try_from(value).ok()?;
+    Decimal::try_from_i128_with_scale(value, 0).ok()
+}
+
+/// Divides two decimals, keeping as many of the at most `max_scale` digits
+/// after the point as fit, and rounds the last one kept under `rounding`.
+/// The quotient is computed exactly first, so it is rounded only once.
+/// Trailing zeros are dropped, so `1.50d / 1` is `1.5`.
+pub fn divide_decimal(
+    left: Decimal,
+    right: Decimal,
+    rounding: RoundingMode,
+    max_scale: u32,
+) -> Option<Decimal> {
+    // left / right == left.mantissa * 10^right.scale / (right.mantissa * 10^left.scale)
+    let ten = BigInt::from(10);
+    let numerator = BigInt::from(left.mantissa()) * ten.pow(right.scale());
+    let denominator = BigInt::from(right.mantissa()) * ten.pow(left.scale());
+    (0..=max_scale)
+        .rev()
+        .find_map(|scale| {
+            let quotient = rounding.divide(&(&numerator * ten.pow(scale)), &denominator);
+            let quotient = i128::try_from(quotient).ok()?;
+            Decimal::try_from_i128_with_scale(quotient, scale).ok()
+        })
+        .map(|quotient| quotient.normalize())
+}
+
+/// The nearest float, or an infinity when the big int is beyond `f64`.
+pub fn big_int_to_float(value: &BigInt) -> f64 {
+    value.to_f64().expect("big ints always convert to a float")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn token(operator: Tokens, text: &str) -> SyntaxToken {
+        SyntaxToken::with_trivia(
+            0,
+            SyntaxKind::Token(operator),
+            text.to_string(),
+            Vec::new(),
+            Vec::new(),
+        )
+    }
+
+    fn add(overflow: OverflowPolicy, left: i64, right: i64) -> Result<Value, Box<ErrorKind>> {
+        let arithmetic = Arithmetic {
+            overflow,
+            ..Arithmetic::default()
+        };
+        evaluate_operator(
+            &Tokens::PlusToken,
+            &token(Tokens::PlusToken, "+"),
+            arithmetic,
+            Value::Int(left),
+            Value::Int(right),
+        )
+    }
+
+    #[test]
+    fn overflow_policies() {
+        assert!(matches!(
+            *add(OverflowPolicy::Error, i64::MAX, 1).unwrap_err(),
+            ErrorKind::IntegerOverflow { ref operator, position: 0 } if operator == "+"
+        ));
+        assert!(matches!(
+            add(OverflowPolicy::Wrap, i64::MAX, 1),
+            Ok(Value::Int(i64::MIN))
+        ));
+        assert!(matches!(
+            add(OverflowPolicy::Saturate, i64::MIN, -1),
+            Ok(Value::Int(i64::MIN))
+        ));
+        assert!(matches!(
+            add(OverflowPolicy::Promote, i64::MAX, 1),
+            Ok(Value::BigInt(ref value)) if *value == BigInt::from(i64::MAX) + 1
+        ));
+        for policy in [
+            OverflowPolicy::Error,
+            OverflowPolicy::Wrap,
+            OverflowPolicy::Saturate,
+            OverflowPolicy::Promote,
+        ] {
+            assert!(matches!(add(policy, 1, 2), Ok(Value::Int(3))));
+        }
+    }
+
+    #[test]
+    fn promoted_results_that_fit_become_ints_again() {
+        let arithmetic = Arithmetic {
+            overflow: OverflowPolicy::Promote,
+            ..Arithmetic::default()
+        };
+        let big = add(OverflowPolicy::Promote, i64::MAX, 1).unwrap();
+        let result = evaluate_operator(
+            &Tokens::MinusToken,
+            &token(Tokens::MinusToken, "-"),
+            arithmetic,
+            big,
+            Value::Int(1),
+        );
+        assert!(matches!(result, Ok(Value::Int(i64::MAX))));
+    }
+
+    #[test]
+    fn shifts() {
+        let shift = |overflow: OverflowPolicy, operator: Tokens, left: Value, right: i64| {
+            let arithmetic = Arithmetic {
+                overflow,
+                ..Arithmetic::default()
+            };
+            evaluate_operator(
+                &operator,
+                &token(operator.clone(), "<<"),
+                arithmetic,
+                left,
+                Value::Int(right),
+            )
+        };
+        let left = Tokens::LessThanLessThanToken;
+        let right = Tokens::GreaterThanGreaterThanToken;
+        assert!(matches!(
+            shift(OverflowPolicy::Error, left.clone(), Value::Int(1), 4),
+            Ok(Value::Int(16))
+        ));
+        assert!(matches!(
+            shift(OverflowPolicy::Error, right.clone(), Value::Int(-16), 2),
+            Ok(Value::Int(-4))
+        ));
+        assert!(matches!(
+            *shift(OverflowPolicy::Error, left.clone(), Value::Int(1), 63).unwrap_err(),
+            ErrorKind::IntegerOverflow { .. }
+        ));
+        assert!(matches!(
+            shift(OverflowPolicy::Wrap, left.clone(), Value::Int(1), 63),
+            Ok(Value::Int(i64::MIN))
+        ));
+        assert!(matches!(
+            shift(OverflowPolicy::Saturate, left.clone(), Value::Int(-3), 62),
+            Ok(Value::Int(i64::MIN))
+        ));
+        let big = shift(OverflowPolicy::Promote, left.clone(), Value::Int(1), 63).unwrap();
+        assert!(matches!(big, Value::BigInt(ref value) if *value == BigInt::from(1) << 63));
+        assert!(matches!(
+            shift(OverflowPolicy::Error, right, big.clone(), 62),
+            Ok(Value::Int(2))
+        ));
+        assert!(matches!(
+            *shift(OverflowPolicy::Error, left, big, 64).unwrap_err(),
+            ErrorKind::ShiftOutOfRange { ref amount, .. } if *amount == BigInt::from(64)
+        ));
+    }
+
+    #[test]
+    fn rounding_modes() {
+        // Each mode's result for 5 / 2 and -5 / 2 kept to whole numbers.
+        let cases = [
+            (RoundingMode::HalfEven, 2, -2),
+            (RoundingMode::HalfUp, 3, -3),
+            (RoundingMode::HalfDown, 2, -2),
+            (RoundingMode::Up, 3, -3),
+            (RoundingMode::Down, 2, -2),
+            (RoundingMode::Floor, 2, -3),
+            (RoundingMode::Ceiling, 3, -2),
+        ];
+        for (mode, positive, negative) in cases {
+            let divide = |left: i64| divide_decimal(left.into(), 2.into(), mode, 0);
+            assert_eq!(divide(5), Some(positive.into()), "{:?}", mode);
+            assert_eq!(divide(-5), Some(negative.into()), "{:?}", mode);
+        }
+        assert_eq!(
+            divide_decimal(7.into(), 2.into(), RoundingMode::HalfEven, 0),
+            Some(4.into())
+        );
+    }
+
+    #[test]
+    fn decimal_division_keeps_the_digits_that_fit() {
+        let third = divide_decimal(
+            1.into(),
+            3.into(),
+            RoundingMode::HalfEven,
+            Decimal::MAX_SCALE,
+        )
+        .unwrap();
+        assert_eq!(third.to_string(), "0.3333333333333333333333333333");
+        let two_thirds = divide_decimal(
+            2.into(),
+            3.into(),
+            RoundingMode::HalfEven,
+            Decimal::MAX_SCALE,
+        )
+        .unwrap();
+        assert_eq!(two_thirds.to_string(), "0.6666666666666666666666666667");
+        let down = divide_decimal(2.into(), 3.into(), RoundingMode::Down, Decimal::MAX_SCALE);
+        assert_eq!(down.unwrap().to_string(), "0.6666666666666666666666666666");
+        assert_eq!(
+            divide_decimal(Decimal::new(150, 2), 1.into(), RoundingMode::HalfEven, 28)
+                .unwrap()
+                .to_string(),
+            "1.5"
+        );
+    }
+
+    #[test]
+    fn comparisons() {
+        let compare = |operator: Tokens, left: Value, right: Value| {
+            apply(&token(operator, "?"), Arithmetic::default(), left, right).unwrap()
+        };
+        assert_eq!(
+            compare(Tokens::LessThanToken, Value::Int(1), Value::Float(1.5)),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            compare(
+                Tokens::GreaterThanEqualsToken,
+                Value::BigInt(BigInt::from(u64::MAX)),
+                Value::Int(i64::MAX)
+            ),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            compare(
+                Tokens::LessThanToken,
+                Value::String("abc".to_string()),
+                Value::String("abd".to_string())
+            ),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            compare(
+                Tokens::LessThanEqualsToken,
+                Value::Float(f64::NAN),
+                Value::Int(1)
+            ),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            compare(Tokens::EqualsEqualsToken, Value::Null, Value::Int(1)),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            compare(Tokens::BangEqualsToken, Value::Char('a'), Value::Char('b')),
+            Value::Boolean(true)
+        );
+        assert!(apply(
+            &token(Tokens::EqualsEqualsToken, "=="),
+            Arithmetic::default(),
+            Value::Float(1.0),
+            Value::Decimal(1.into())
+        )
+        .is_err());
     }
 }
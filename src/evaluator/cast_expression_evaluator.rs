@@ -0,0 +1,80 @@
+use num_bigint::BigInt;
+use num_traits::{FromPrimitive, ToPrimitive};
+use rust_decimal::Decimal;
+
+use crate::{binding::BoundCastExpression, diagnostics::ErrorKind};
+
+use super::{
+    binary_expression_evaluator::{big_int_to_decimal, big_int_to_float},
+    Evaluator, Value,
+};
+
+/// `value as type` converts a number to `int`, `float` or `decimal`.
+/// Converting to `int` drops the fraction, rounding towards zero, and gives
+/// a big int when the result is too large for an int. A value that does not
+/// fit in the target type at all, such as an infinite float, is a runtime
+/// error.
+pub fn evaluate(
+    evaluator: &mut Evaluator,
+    expression: &BoundCastExpression,
+) -> Result<Value, Box<ErrorKind>> {
+    let value = evaluator.evaluate_expression(&expression.expression)?;
+    let from = value.type_name();
+    let to = expression.type_name();
+    let position = expression.as_keyword.position();
+    let invalid_cast = || {
+        Box::new(ErrorKind::InvalidCast {
+            from,
+            to: to.to_string(),
+            position,
+        })
+    };
+    let out_of_range = || {
+        Box::new(ErrorKind::ConversionOutOfRange {
+            from,
+            to: to.to_string(),
+            position,
+        })
+    };
+
+    let result = match to {
+        "int" => match value {
+            Value::Int(_) | Value::BigInt(_) => value,
+            Value::Float(value) => {
+                Value::from_big_int(BigInt::from_f64(value).ok_or_else(out_of_range)?)
+            }
+            Value::Decimal(value) => {
+                let value = value.trunc().to_i128().expect("decimals fit in an i128");
+                Value::from_big_int(BigInt::from(value))
+            }
+            _ => return Err(invalid_cast()),
+        },
+        "float" => match value {
+            Value::Int(value) => Value::Float(value as f64),
+            Value::BigInt(value) => Value::Float(big_int_to_float(&value)),
+            Value::Decimal(value) => {
+                Value::Float(value.to_f64().expect("decimals always convert to a float"))
+            }
+            Value::Float(_) => value,
+            _ => return Err(invalid_cast()),
+        },
+        "decimal" => match value {
+            Value::Int(value) => Value::Decimal(value.into()),
+            Value::BigInt(value) => {
+                Value::Decimal(big_int_to_decimal(&value).ok_or_else(out_of_range)?)
+            }
+            Value::Float(value) => {
+                Value::Decimal(Decimal::from_f64(value).ok_or_else(out_of_range)?)
+            }
+            Value::Decimal(_) => value,
+            _ => return Err(invalid_cast()),
+        },
+        name => {
+            return Err(Box::new(ErrorKind::UnknownType {
+                name: name.to_string(),
+                position: expression.type_token.position(),
+            }))
+        }
+    };
+    Ok(result)
+}
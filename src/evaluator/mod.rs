@@ -1,242 +1,673 @@
-use crate::syntax::{self, SyntaxKind};
+use std::{cell::RefCell, cmp::Ordering, collections::HashMap, rc::Rc, str::FromStr};
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Signed, Zero};
+
+use crate::{
+    binding::{
+        BoundArrayLiteralExpression, BoundBlockStatement, BoundCallExpression, BoundExpression,
+        BoundGlobalScope, BoundIfStatement, BoundObjectLiteralExpression, BoundReturnStatement,
+        BoundStatement, BoundVariableDeclaration, BoundWhileStatement,
+    },
+    diagnostics::ErrorKind,
+    symbols::{FunctionSymbol, TypeSymbol, VariableSymbol},
+};
+
+mod assignment_expression_evaluator;
 mod binary_expression_evaluator;
+mod cast_expression_evaluator;
 mod unary_expression_evaluator;
+mod value;
+
+pub use binary_expression_evaluator::{apply as apply_binary_operator, short_circuit};
+pub use unary_expression_evaluator::apply as apply_unary_operator;
+pub use value::{ArrayElements, ObjectFields, Value};
 
-#[derive(Debug)]
-pub enum EvaluationResult {
-    String(String),
-    Char(char),
-    Int(i64),
-    Float(f64),
-    Boolean(bool),
-    Syntax(SyntaxKind),
-    Null,
+/// What int arithmetic does when a result does not fit in an `i64`. Checked
+/// explicitly, so it is the same in debug and release builds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum OverflowPolicy {
+    /// Stop with a runtime error.
+    #[default]
+    Error,
+    /// Wrap around in two's complement, so `i64::MAX + 1` is `i64::MIN`.
+    Wrap,
+    /// Clamp to `i64::MIN` or `i64::MAX`.
+    Saturate,
+    /// Continue with the exact result as a big int.
+    Promote,
 }
 
-impl Clone for EvaluationResult {
-    fn clone(&self) -> Self {
-        match self {
-            Self::String(arg0) => Self::String(arg0.clone()),
-            Self::Char(arg0) => Self::Char(arg0.clone()),
-            Self::Int(arg0) => Self::Int(arg0.clone()),
-            Self::Float(arg0) => Self::Float(arg0.clone()),
-            Self::Boolean(arg0) => Self::Boolean(arg0.clone()),
-            Self::Syntax(arg0) => Self::Syntax(arg0.clone()),
-            Self::Null => Self::Null,
+impl FromStr for OverflowPolicy {
+    type Err = ();
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy {
+            "error" => Ok(OverflowPolicy::Error),
+            "wrap" => Ok(OverflowPolicy::Wrap),
+            "saturate" => Ok(OverflowPolicy::Saturate),
+            "promote" => Ok(OverflowPolicy::Promote),
+            _ => Err(()),
         }
     }
 }
 
+/// How decimal division rounds a quotient that does not fit in a decimal's
+/// digits.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub enum RoundingMode {
+    /// To the nearest digit, and to the even one on a tie. Also called
+    /// banker's rounding.
+    #[default]
+    HalfEven,
+    /// To the nearest digit, and away from zero on a tie.
+    HalfUp,
+    /// To the nearest digit, and towards zero on a tie.
+    HalfDown,
+    /// Away from zero.
+    Up,
+    /// Towards zero.
+    Down,
+    /// Towards negative infinity.
+    Floor,
+    /// Towards positive infinity.
+    Ceiling,
+}
 
-#[derive(Debug)]
-pub enum Types {
-    String(String),
-    Char(char),
-    Int(i64),
-    Float(f64),
-    Boolean(bool),
-    Null,
+impl RoundingMode {
+    /// Divides `numerator` by `denominator` and rounds the quotient to an
+    /// integer.
+    pub fn divide(self, numerator: &BigInt, denominator: &BigInt) -> BigInt {
+        let (quotient, remainder) = numerator.div_rem(denominator);
+        if remainder.is_zero() {
+            return quotient;
+        }
+        let negative = numerator.is_negative() != denominator.is_negative();
+        let half = (remainder.magnitude() * 2u32).cmp(denominator.magnitude());
+        let away_from_zero = match self {
+            RoundingMode::HalfEven => {
+                half == Ordering::Greater || (half == Ordering::Equal && quotient.is_odd())
+            }
+            RoundingMode::HalfUp => half != Ordering::Less,
+            RoundingMode::HalfDown => half == Ordering::Greater,
+            RoundingMode::Up => true,
+            RoundingMode::Down => false,
+            RoundingMode::Floor => negative,
+            RoundingMode::Ceiling => !negative,
+        };
+        match (away_from_zero, negative) {
+            (false, _) => quotient,
+            (true, false) => quotient + 1,
+            (true, true) => quotient - 1,
+        }
+    }
 }
 
-impl Clone for Types {
-    fn clone(&self) -> Self {
-        match self {
-            Self::String(arg0) => Self::String(arg0.clone()),
-            Self::Char(arg0) => Self::Char(arg0.clone()),
-            Self::Int(arg0) => Self::Int(arg0.clone()),
-            Self::Float(arg0) => Self::Float(arg0.clone()),
-            Self::Boolean(arg0) => Self::Boolean(arg0.clone()),
-            Self::Null => Self::Null,
+impl FromStr for RoundingMode {
+    type Err = ();
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode {
+            "half-even" => Ok(RoundingMode::HalfEven),
+            "half-up" => Ok(RoundingMode::HalfUp),
+            "half-down" => Ok(RoundingMode::HalfDown),
+            "up" => Ok(RoundingMode::Up),
+            "down" => Ok(RoundingMode::Down),
+            "floor" => Ok(RoundingMode::Floor),
+            "ceiling" => Ok(RoundingMode::Ceiling),
+            _ => Err(()),
         }
     }
 }
 
+/// The settings that decide what arithmetic does with results a number
+/// type cannot hold exactly.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Arithmetic {
+    pub overflow: OverflowPolicy,
+    pub rounding: RoundingMode,
+}
 
-pub fn evaluate(syntax: SyntaxKind, variables: &mut Vec<(String, Types)>) -> EvaluationResult {
-    match syntax {
-        SyntaxKind::Expression(crate::syntax::ExpressionSyntax::BinaryExpressionSyntax {
-            left,
-            operator,
-            right,
-        }) => {
-            binary_expression_evaluator::evaluate(
-                *left,
-                *right,
-                *operator,
-                variables,
-            )
-        }
-
-        SyntaxKind::Statement(
-            crate::syntax::StatementSyntax::VariableAssignmentStatementSyntax {
-                identifier,
-                equals_token: _,
-                expression,
-            },
-        ) => {
-            let mut expression = evaluate(*expression, variables);
-
-            loop {
-                match expression {
-                    EvaluationResult::Syntax(syntax) => {
-                        expression = evaluate(syntax, variables);
-                    }
-                    _ => {
-                        break;
-                    }
-                }
+/// How many calls can be running at once before a call is stopped with a
+/// runtime error, so runaway recursion does not overflow the interpreter's
+/// own stack.
+const MAX_CALL_DEPTH: usize = 200;
+
+/// A statement that leaves the statements around it before they finish.
+enum Unwind {
+    /// Leaves the current call with a value.
+    Return(Value),
+    /// Leaves the innermost loop.
+    Break,
+    /// Goes back to the condition of the innermost loop.
+    Continue,
+}
+
+/// Runs a bound tree and computes its value, keeping global variables in the
+/// caller's map so they survive between REPL inputs.
+pub struct Evaluator<'a> {
+    variables: &'a mut HashMap<VariableSymbol, Value>,
+    functions: &'a HashMap<FunctionSymbol, BoundBlockStatement>,
+    /// The parameters and locals of each running call, innermost last.
+    /// A local declared without a value is in its frame as `None`.
+    frames: Vec<HashMap<VariableSymbol, Option<Value>>>,
+    /// The `return`, `break` or `continue` that is leaving the statements
+    /// being run.
+    unwinding: Option<Unwind>,
+    arithmetic: Arithmetic,
+}
+
+/// Runs every top level statement of `scope` and returns the value of the
+/// last one. The scope is only borrowed, so it can be evaluated again.
+/// `functions` holds the body of every function the scope can call.
+/// Evaluation stops at the first runtime error; variables declared before it
+/// are kept.
+pub fn evaluate(
+    scope: &BoundGlobalScope,
+    variables: &mut HashMap<VariableSymbol, Value>,
+    functions: &HashMap<FunctionSymbol, BoundBlockStatement>,
+    arithmetic: Arithmetic,
+) -> Result<Value, Box<ErrorKind>> {
+    let mut evaluator = Evaluator {
+        variables,
+        functions,
+        frames: Vec::new(),
+        unwinding: None,
+        arithmetic,
+    };
+    let mut result = Value::Null;
+    for statement in &scope.statements {
+        result = evaluator.evaluate_statement(statement)?;
+    }
+    Ok(result)
+}
+
+impl Evaluator<'_> {
+    fn evaluate_statement(&mut self, statement: &BoundStatement) -> Result<Value, Box<ErrorKind>> {
+        match statement {
+            BoundStatement::BoundBlockStatement(block) => self.evaluate_block_statement(block),
+            BoundStatement::BoundExpressionStatement(statement) => {
+                self.evaluate_expression(&statement.expression)
+            }
+            BoundStatement::BoundVariableDeclaration(declaration) => {
+                self.evaluate_variable_declaration(declaration)
+            }
+            BoundStatement::BoundIfStatement(statement) => self.evaluate_if_statement(statement),
+            BoundStatement::BoundReturnStatement(statement) => {
+                self.evaluate_return_statement(statement)
+            }
+            BoundStatement::BoundWhileStatement(statement) => {
+                self.evaluate_while_statement(statement)
+            }
+            BoundStatement::BoundBreakStatement(_) => {
+                self.unwinding = Some(Unwind::Break);
+                Ok(Value::Null)
+            }
+            BoundStatement::BoundContinueStatement(_) => {
+                self.unwinding = Some(Unwind::Continue);
+                Ok(Value::Null)
+            }
+            BoundStatement::BoundErrorStatement(statement) => {
+                panic!("Cannot evaluate error statement: {:?}", statement)
             }
+        }
+    }
 
-            match *identifier {
-                SyntaxKind::Token(crate::syntax::Tokens::IdentifierToken { value }) => {
-                    let mut filtered_variables: Vec<(String, Types)> = variables
-                        .iter()
-                        .filter(|(name, _)| *name != value)// Filter out elements with variable name "variable2"
-                        .cloned()
-                        .collect();
-
-                    match expression {
-                        EvaluationResult::String(s) => {
-                            filtered_variables.push((value, Types::String(s)));
-                        }
-                        EvaluationResult::Int(s) => {
-                            filtered_variables.push((value, Types::Int(s)));
-                        }
-                        EvaluationResult::Boolean(s) => {
-                            filtered_variables.push((value, Types::Boolean(s)));
-                        }
-                        EvaluationResult::Null => {
-                            filtered_variables.push((value, Types::Null));
-                        }
-                        _ => {
-                            panic!("Cannot declare variable with expression: {:?}", expression);
-                        }
-                    };
-
-                    *variables = filtered_variables;
-                }
-                _ => {
-                    panic!("Cannot declare variable with identifier: {:?}", identifier);
-                }
+    fn evaluate_block_statement(
+        &mut self,
+        block: &BoundBlockStatement,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let mut result = Value::Null;
+        for statement in &block.statements {
+            if self.unwinding.is_some() {
+                break;
             }
+            result = self.evaluate_statement(statement)?;
+        }
+        Ok(result)
+    }
 
-            EvaluationResult::Null
-        }
-
-        SyntaxKind::Statement(
-            crate::syntax::StatementSyntax::VariableDeclarationStatementSyntax {
-                keyword: _,
-                identifier,
-                equals_token: _,
-                expression,
-                semicolon: _,
-            },
-        ) => {
-            let mut expression = evaluate(*expression, variables);
-
-            loop {
-                match expression {
-                    EvaluationResult::Syntax(syntax) => {
-                        expression = evaluate(syntax, variables);
-                    }
-                    _ => {
-                        break;
-                    }
-                }
+    fn evaluate_variable_declaration(
+        &mut self,
+        declaration: &BoundVariableDeclaration,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let value = match &declaration.initializer {
+            Some(initializer) => {
+                let value = self.evaluate_expression(initializer)?;
+                check_type(
+                    declaration.variable.type_symbol(),
+                    &value,
+                    initializer.span().get_start(),
+                )?;
+                Some(value)
             }
+            None => None,
+        };
+        self.declare(&declaration.variable, value);
+        Ok(Value::Null)
+    }
 
-            match *identifier {
-                SyntaxKind::Token(crate::syntax::Tokens::IdentifierToken { value }) => {
-                    match expression {
-                        EvaluationResult::String(s) => {
-                            variables.push((value, Types::String(s)));
-                        }
-                        EvaluationResult::Int(s) => {
-                            variables.push((value, Types::Int(s)));
-                        }
-                        EvaluationResult::Float(s) => {
-                            variables.push((value, Types::Float(s)));
-                        }
-                        EvaluationResult::Char(s) => {
-                            variables.push((value, Types::Char(s)));
-                        }
-
-                        EvaluationResult::Boolean(s) => {
-                            variables.push((value, Types::Boolean(s)));
-                        }
-                        EvaluationResult::Null => {
-                            variables.push((value, Types::Null));
-                        }
-                        _ => {
-                            panic!("Cannot declare variable with expression: {:?}", expression);
-                        }
-                    }
-                }
-                _ => {
-                    panic!("Cannot declare variable with identifier: {:?}", identifier);
+    fn evaluate_return_statement(
+        &mut self,
+        statement: &BoundReturnStatement,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let value = match &statement.expression {
+            Some(expression) => self.evaluate_expression(expression)?,
+            None => Value::Null,
+        };
+        self.unwinding = Some(Unwind::Return(value));
+        Ok(Value::Null)
+    }
+
+    /// Runs the body until the condition is false or a `break` leaves it. A
+    /// `return` in the body leaves the loop and keeps unwinding.
+    fn evaluate_while_statement(
+        &mut self,
+        statement: &BoundWhileStatement,
+    ) -> Result<Value, Box<ErrorKind>> {
+        while self.evaluate_condition(&statement.condition)? {
+            self.evaluate_block_statement(&statement.body)?;
+            match self.unwinding {
+                Some(Unwind::Break) => {
+                    self.unwinding = None;
+                    break;
                 }
+                Some(Unwind::Continue) => self.unwinding = None,
+                Some(Unwind::Return(_)) => break,
+                None => {}
             }
-            EvaluationResult::Null
-        }
-
-        SyntaxKind::Expression(crate::syntax::ExpressionSyntax::LiteralExpressionSyntax {
-            expression,
-        }) => match *expression {
-            SyntaxKind::Token(crate::syntax::Tokens::LiteralToken { value })
-            => {
-                match value {
-                    syntax::LiteralToken::Int { value } => EvaluationResult::Int(value),
-                    syntax::LiteralToken::Float { value } => EvaluationResult::Float(value),
-                    syntax::LiteralToken::String { value } => EvaluationResult::String(value),
-                    syntax::LiteralToken::Char { value } => EvaluationResult::Char(value),
-                    syntax::LiteralToken::Bool { value } => EvaluationResult::Boolean(value),
-                    syntax::LiteralToken::Null => EvaluationResult::Null,
-                }
+        }
+        Ok(Value::Null)
+    }
+
+    fn evaluate_if_statement(
+        &mut self,
+        statement: &BoundIfStatement,
+    ) -> Result<Value, Box<ErrorKind>> {
+        if self.evaluate_condition(&statement.condition)? {
+            return self.evaluate_block_statement(&statement.body);
+        }
+        match &statement.else_body {
+            Some(else_body) => self.evaluate_statement(else_body),
+            None => Ok(Value::Null),
+        }
+    }
+
+    /// Evaluates the condition of an `if` or `?:`, which has to be a bool.
+    fn evaluate_condition(&mut self, condition: &BoundExpression) -> Result<bool, Box<ErrorKind>> {
+        match self.evaluate_expression(condition)? {
+            Value::Boolean(value) => Ok(value),
+            value => Err(Box::new(ErrorKind::InvalidCondition {
+                found: value.type_name(),
+                position: condition.span().get_start(),
+            })),
+        }
+    }
+
+    fn evaluate_expression(
+        &mut self,
+        expression: &BoundExpression,
+    ) -> Result<Value, Box<ErrorKind>> {
+        match expression {
+            BoundExpression::BoundLiteralExpression(expression) => Ok(expression.value.clone()),
+            BoundExpression::BoundVariableExpression(expression) => {
+                self.variable(&expression.variable, expression.span.get_start())
+            }
+            BoundExpression::BoundBinaryExpression(expression) => {
+                binary_expression_evaluator::evaluate(self, expression)
+            }
+            BoundExpression::BoundUnaryExpression(expression) => {
+                unary_expression_evaluator::evaluate(self, expression)
+            }
+            BoundExpression::BoundAssignmentExpression(expression) => {
+                assignment_expression_evaluator::evaluate(self, expression)
+            }
+            BoundExpression::BoundCompoundAssignmentExpression(expression) => {
+                assignment_expression_evaluator::evaluate_compound(self, expression)
             }
-            SyntaxKind::Token(crate::syntax::Tokens::IdentifierToken { value }) => {
-                let (_, var_type) = variables
-                    .iter()
-                    .find(|(name, _)| *name == value)
-                    .unwrap();
-
-                match var_type {
-                    Types::String(s) => EvaluationResult::String(s.clone()),
-                    Types::Int(s) => EvaluationResult::Int(*s),
-                    Types::Float(s) => EvaluationResult::Float(*s),
-                    Types::Char(s) => EvaluationResult::Char(*s),
-                    Types::Boolean(s) => EvaluationResult::Boolean(*s),
-                    Types::Null => EvaluationResult::Null,
+            BoundExpression::BoundUpdateExpression(expression) => {
+                assignment_expression_evaluator::evaluate_update(self, expression)
+            }
+            BoundExpression::BoundConditionalExpression(expression) => {
+                if self.evaluate_condition(&expression.condition)? {
+                    self.evaluate_expression(&expression.when_true)
+                } else {
+                    self.evaluate_expression(&expression.when_false)
                 }
             }
-            SyntaxKind::Keyword(crate::syntax::Keywords::TrueKeyword) => {
-                EvaluationResult::Boolean(true)
+            BoundExpression::BoundIndexExpression(expression) => {
+                let place = assignment_expression_evaluator::index_place(self, expression)?;
+                place.map_or(Ok(Value::Null), |place| place.get(self))
             }
-            SyntaxKind::Keyword(crate::syntax::Keywords::FalseKeyword) => {
-                EvaluationResult::Boolean(false)
+            BoundExpression::BoundMemberAccessExpression(expression) => {
+                let place = assignment_expression_evaluator::member_place(self, expression)?;
+                place.map_or(Ok(Value::Null), |place| place.get(self))
             }
-            SyntaxKind::Keyword(crate::syntax::Keywords::NullKeyword) => EvaluationResult::Null,
-            _ => {
-                panic!("Cannot evaluate literal expression: {:?}", expression);
+            BoundExpression::BoundCastExpression(expression) => {
+                cast_expression_evaluator::evaluate(self, expression)
+            }
+            BoundExpression::BoundCallExpression(expression) => {
+                self.evaluate_call_expression(expression)
+            }
+            BoundExpression::BoundArrayLiteralExpression(expression) => {
+                self.evaluate_array_literal_expression(expression)
             }
-        },
+            BoundExpression::BoundObjectLiteralExpression(expression) => {
+                self.evaluate_object_literal_expression(expression)
+            }
+            BoundExpression::BoundErrorExpression(expression) => {
+                panic!("Cannot evaluate error expression: {:?}", expression)
+            }
+        }
+    }
 
-        SyntaxKind::Expression(
-            crate::syntax::ExpressionSyntax::ParenthesizedExpressionSyntax {
-                open_parenthesis_token: _,
-                close_parenthesis_token: _,
-                expression,
-            },
-        ) => evaluate(*expression, variables),
+    /// The current value of a variable: a parameter or local of the running
+    /// call, or a global. The binder only lets code use variables declared
+    /// above it, but a function can be called before a global it reads has
+    /// been given its value.
+    fn variable(
+        &self,
+        variable: &VariableSymbol,
+        position: usize,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let value = match self.frames.last().and_then(|frame| frame.get(variable)) {
+            Some(value) => value.as_ref(),
+            None => self.variables.get(variable),
+        };
+        value.cloned().ok_or_else(|| {
+            Box::new(ErrorKind::UnassignedVariable {
+                name: variable.name().to_string(),
+                position,
+            })
+        })
+    }
 
-        SyntaxKind::Expression(syntax::ExpressionSyntax::UnaryExpressionSyntax {
-            operator,
-            operand,
-        }) => {
-            unary_expression_evaluator::evaluate(*operator, *operand, variables)
+    /// Gives a newly declared variable its first value, in the running call
+    /// or, outside any call, among the globals. A variable declared without
+    /// a value has none until it is assigned, even if the same declaration
+    /// gave it one on an earlier pass through a loop.
+    fn declare(&mut self, variable: &VariableSymbol, value: Option<Value>) {
+        match (self.frames.last_mut(), value) {
+            (Some(frame), value) => {
+                frame.insert(variable.clone(), value);
+            }
+            (None, Some(value)) => {
+                self.variables.insert(variable.clone(), value);
+            }
+            (None, None) => {
+                self.variables.remove(variable);
+            }
         }
+    }
 
-        _ => {
-            panic!("Cannot evaluate syntax kind: {:?}", syntax);
+    /// Stores a new value in a declared variable, wherever it was declared.
+    fn assign(&mut self, variable: &VariableSymbol, value: Value) {
+        match self.frames.last_mut() {
+            Some(frame) if frame.contains_key(variable) => {
+                frame.insert(variable.clone(), Some(value));
+            }
+            _ => {
+                self.variables.insert(variable.clone(), value);
+            }
+        }
+    }
+
+    /// Calls a function with its arguments as the parameters of a new frame.
+    /// Arguments and the returned value are checked against the function's
+    /// annotations, which the binder can only do for values whose type it
+    /// knows.
+    fn evaluate_call_expression(
+        &mut self,
+        expression: &BoundCallExpression,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let function = &expression.function;
+        let mut frame = HashMap::new();
+        for (parameter, argument) in function.parameters().iter().zip(&expression.arguments) {
+            let value = self.evaluate_expression(argument)?;
+            check_type(parameter.type_symbol(), &value, argument.span().get_start())?;
+            frame.insert(parameter.clone(), Some(value));
+        }
+        if self.frames.len() == MAX_CALL_DEPTH {
+            return Err(Box::new(ErrorKind::StackOverflow {
+                name: function.name().to_string(),
+                position: expression.span.get_start(),
+            }));
+        }
+
+        let body = &self.functions[function];
+        self.frames.push(frame);
+        let result = self.evaluate_block_statement(body);
+        self.frames.pop();
+        let value = match self.unwinding.take() {
+            Some(Unwind::Return(value)) => value,
+            _ => Value::Null,
+        };
+        result?;
+        check_type(function.return_type(), &value, expression.span.get_start())?;
+        Ok(value)
+    }
+
+    fn evaluate_array_literal_expression(
+        &mut self,
+        expression: &BoundArrayLiteralExpression,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let elements = expression
+            .elements
+            .iter()
+            .map(|element| self.evaluate_expression(element))
+            .collect::<Result<_, _>>()?;
+        Ok(Value::Array(Rc::new(RefCell::new(elements))))
+    }
+
+    fn evaluate_object_literal_expression(
+        &mut self,
+        expression: &BoundObjectLiteralExpression,
+    ) -> Result<Value, Box<ErrorKind>> {
+        let mut fields: Vec<(String, Value)> = Vec::new();
+        for (name, value) in expression.properties.iter() {
+            let value = self.evaluate_expression(value)?;
+            // A repeated name keeps its first position but takes the last value.
+            match fields.iter_mut().find(|(field, _)| field == name) {
+                Some((_, existing)) => *existing = value,
+                None => fields.push((name.clone(), value)),
+            }
         }
+        Ok(Value::Object(Rc::new(RefCell::new(fields))))
+    }
+}
+
+/// Checks a value against the type a variable, parameter or function was
+/// declared with.
+fn check_type(expected: TypeSymbol, value: &Value, position: usize) -> Result<(), Box<ErrorKind>> {
+    if expected.accepts(value.type_symbol()) {
+        Ok(())
+    } else {
+        Err(Box::new(ErrorKind::TypeMismatch {
+            expected: expected.name(),
+            found: value.type_name(),
+            position,
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        binding::bind_global_scope,
+        lexer::{source_text::SourceText, Lexer},
+        parser::Parser,
+    };
+
+    /// Runs `text`, which must bind without errors, as a single input.
+    fn run(text: &str) -> Result<Value, Box<ErrorKind>> {
+        let tokens = Lexer::new(SourceText::new(text.to_string())).lex();
+        let tree = Parser::new(tokens, text.to_string()).parse();
+        let scope = bind_global_scope(&[], &[], &tree, true);
+        assert!(
+            !scope.diagnostics.has_errors(),
+            "{}: {:?}",
+            text,
+            scope.diagnostics.errors
+        );
+        evaluate(
+            &scope,
+            &mut HashMap::new(),
+            &scope.function_bodies,
+            Arithmetic::default(),
+        )
+    }
+
+    #[test]
+    fn indexes_arrays_strings_and_objects() {
+        assert_eq!(run("let xs = [1, 2]; xs[1]").unwrap(), Value::Int(2));
+        assert_eq!(run("\"héllo\"[1]").unwrap(), Value::Char('é'));
+        assert_eq!(
+            run("let o = { a: 1 }; o[\"a\"] + o.a").unwrap(),
+            Value::Int(2)
+        );
+        assert_eq!(
+            run("let mut xs = [1, 2]; xs[0] = 5; xs")
+                .unwrap()
+                .to_string(),
+            "[5, 2]"
+        );
+    }
+
+    #[test]
+    fn index_out_of_range_is_a_runtime_error() {
+        let error = run("let xs = [1, 2]; xs[2]").unwrap_err();
+        assert!(
+            matches!(
+                *error,
+                ErrorKind::IndexOutOfRange {
+                    index: 2,
+                    length: 2,
+                    position: 20
+                }
+            ),
+            "{:?}",
+            error
+        );
+        let error = run("let xs = [1, 2]; xs[-1] = 3;").unwrap_err();
+        assert!(
+            matches!(*error, ErrorKind::IndexOutOfRange { index: -1, .. }),
+            "{:?}",
+            error
+        );
+        let error = run("\"héllo\"[5]").unwrap_err();
+        assert!(
+            matches!(
+                *error,
+                ErrorKind::IndexOutOfRange {
+                    index: 5,
+                    length: 5,
+                    ..
+                }
+            ),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn missing_field_is_a_runtime_error() {
+        let error = run("let o = { a: 1 }; o.b").unwrap_err();
+        assert!(
+            matches!(*error, ErrorKind::NoSuchField { ref name, position: 18 } if name == "b"),
+            "{:?}",
+            error
+        );
+        let error = run("let o = { a: 1 }; o[\"b\"]").unwrap_err();
+        assert!(
+            matches!(*error, ErrorKind::NoSuchField { ref name, .. } if name == "b"),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn indexing_or_reading_a_field_of_the_wrong_value_is_a_runtime_error() {
+        let error = run("let xs = [1, true]; xs[1][0]").unwrap_err();
+        assert!(
+            matches!(
+                *error,
+                ErrorKind::CannotIndex {
+                    target: "bool",
+                    index: "int",
+                    ..
+                }
+            ),
+            "{:?}",
+            error
+        );
+        let error = run("let xs = [1, true]; xs[0].name").unwrap_err();
+        assert!(
+            matches!(*error, ErrorKind::CannotAccessMember { target: "int", .. }),
+            "{:?}",
+            error
+        );
+        let error = run("let s = \"ab\"; s[0] = 'c';").unwrap_err();
+        assert!(
+            matches!(*error, ErrorKind::CharacterAssignment { .. }),
+            "{:?}",
+            error
+        );
+    }
+
+    #[test]
+    fn and_and_or_skip_their_right_side_when_the_left_decides() {
+        let fails = "fn fails(): bool { return [true][1]; }";
+        assert_eq!(
+            run(&format!("{} false && fails()", fails)).unwrap(),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            run(&format!("{} true || fails()", fails)).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            run("let xs = [1, 2]; let i = 2; i < 2 && xs[i] == 1").unwrap(),
+            Value::Boolean(false)
+        );
+        assert!(run(&format!("{} true && fails()", fails)).is_err());
+    }
+
+    #[test]
+    fn null_checks_guard_member_access() {
+        assert_eq!(
+            run("let o = null; o != null && o.x == 1").unwrap(),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            run("let o = null; o == null || o.x == 1").unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(run("let o = null; o?.x ?? 2").unwrap(), Value::Int(2));
+    }
+
+    #[test]
+    fn logical_compound_assignment_skips_the_value_when_the_target_decides() {
+        let fails = "fn fails(): bool { return [true][1]; }";
+        assert_eq!(
+            run(&format!("{} let mut b = false; b &&= fails(); b", fails)).unwrap(),
+            Value::Boolean(false)
+        );
+        assert_eq!(
+            run(&format!("{} let mut b = true; b ||= fails(); b", fails)).unwrap(),
+            Value::Boolean(true)
+        );
+        assert_eq!(
+            run("let mut b = true; b &&= false; b").unwrap(),
+            Value::Boolean(false)
+        );
+    }
+
+    #[test]
+    fn array_that_contains_itself() {
+        let value = run("let xs = [0]; xs[0] = xs; xs").unwrap();
+        assert_eq!(value.to_string(), "[[...]]");
+        assert_eq!(value, value.clone());
     }
 }
@@ -1,60 +1,65 @@
-use crate::syntax::SyntaxKind;
+use num_bigint::BigInt;
 
-use super::{EvaluationResult, Types};
+use crate::{
+    binding::BoundUnaryExpression,
+    diagnostics::ErrorKind,
+    syntax::{SyntaxKind, SyntaxToken},
+};
+
+use super::{binary_expression_evaluator::checked_int, Arithmetic, Evaluator, Value};
 
 pub fn evaluate(
-    operator: SyntaxKind,
-    operand: SyntaxKind,
-    variables: &mut Vec<(String, Types)>,
-) -> EvaluationResult {
-    let mut operand = super::evaluate(operand, variables);
+    evaluator: &mut Evaluator,
+    expression: &BoundUnaryExpression,
+) -> Result<Value, Box<ErrorKind>> {
+    let operand = evaluator.evaluate_expression(&expression.operand)?;
+    apply(&expression.operator, evaluator.arithmetic, operand)
+}
 
-    loop {
-        match operand {
-            EvaluationResult::Syntax(syntax) => {
-                operand = super::evaluate(syntax, variables);
-            }
-            _ => {
-                break;
-            }
-        }
-    }
+/// Applies the unary operator `operator` stands for to a value.
+pub fn apply(
+    operator: &SyntaxToken,
+    arithmetic: Arithmetic,
+    operand: Value,
+) -> Result<Value, Box<ErrorKind>> {
+    let invalid_operand = ErrorKind::InvalidUnaryOperand {
+        operator: operator.text().to_string(),
+        operand: operand.type_name(),
+        position: operator.position(),
+    };
 
-    match operator {
+    let result = match operator.kind() {
         SyntaxKind::Token(crate::syntax::Tokens::PlusToken) => match operand {
-            EvaluationResult::Int(operand) => EvaluationResult::Int(operand),
-            EvaluationResult::Float(operand) => EvaluationResult::Float(operand),
-            _ => {
-                panic!(
-                    "Cannot evaluate unary expression with operator: {:?}",
-                    operator
-                );
-            }
+            Value::Int(operand) => Value::Int(operand),
+            Value::BigInt(operand) => Value::BigInt(operand),
+            Value::Decimal(operand) => Value::Decimal(operand),
+            Value::Float(operand) => Value::Float(operand),
+            _ => return Err(invalid_operand.into()),
         },
         SyntaxKind::Token(crate::syntax::Tokens::MinusToken) => match operand {
-            EvaluationResult::Int(operand) => EvaluationResult::Int(-operand),
-            EvaluationResult::Float(operand) => EvaluationResult::Float(-operand),
-            _ => {
-                panic!(
-                    "Cannot evaluate unary expression with operator: {:?}",
-                    operator
-                );
-            }
+            Value::Int(operand) => checked_int(
+                arithmetic.overflow,
+                operator,
+                operand.checked_neg(),
+                || operand.wrapping_neg(),
+                || operand.saturating_neg(),
+                || -BigInt::from(operand),
+            )?,
+            Value::BigInt(operand) => Value::from_big_int(-operand),
+            Value::Decimal(operand) => Value::Decimal(-operand),
+            Value::Float(operand) => Value::Float(-operand),
+            _ => return Err(invalid_operand.into()),
         },
         SyntaxKind::Token(crate::syntax::Tokens::BangToken) => match operand {
-            EvaluationResult::Boolean(operand) => EvaluationResult::Boolean(!operand),
-            _ => {
-                panic!(
-                    "Cannot evaluate unary expression with operator: {:?}",
-                    operator
-                );
-            }
+            Value::Boolean(operand) => Value::Boolean(!operand),
+            _ => return Err(invalid_operand.into()),
+        },
+        SyntaxKind::Token(crate::syntax::Tokens::TildeToken) => match operand {
+            Value::Int(operand) => Value::Int(!operand),
+            Value::BigInt(operand) => Value::from_big_int(!operand),
+            _ => return Err(invalid_operand.into()),
         },
-        _ => {
-            panic!(
-                "Cannot evaluate unary expression with operator: {:?}",
-                operator
-            );
-        }
-    }
+        _ => return Err(invalid_operand.into()),
+    };
+    Ok(result)
 }
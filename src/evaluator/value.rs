@@ -0,0 +1,391 @@
+use std::{
+    cell::RefCell,
+    fmt::{self, Display},
+    hash::{Hash, Hasher},
+    rc::Rc,
+};
+
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+
+use crate::{symbols::TypeSymbol, syntax::LiteralToken};
+
+/// The elements of an array. Arrays are shared, so every variable holding
+/// the same array sees writes made through any of them.
+pub type ArrayElements = Rc<RefCell<Vec<Value>>>;
+
+/// The fields of an object in the order they were added. Shared like
+/// `ArrayElements`.
+pub type ObjectFields = Rc<RefCell<Vec<(String, Value)>>>;
+
+/// A value the evaluator computes, stores in a variable or returns.
+///
+/// Equality is structural: arrays and objects compare by their contents, and
+/// objects also by the order of their fields. Values of different types are
+/// never equal, so `Int(1)` is not `Float(1.0)`; the language's `==` and `!=`
+/// compare numbers across types.
+///
+/// An array or object can contain itself, as after `xs[0] = xs`. Printing,
+/// comparing and hashing keep track of the arrays and objects they are
+/// inside, and treat one they reach again by its identity instead of its
+/// contents.
+#[derive(Clone, Debug)]
+pub enum Value {
+    String(String),
+    Char(char),
+    Int(i64),
+    BigInt(BigInt),
+    Decimal(Decimal),
+    Float(f64),
+    Boolean(bool),
+    Array(ArrayElements),
+    Object(ObjectFields),
+    Null,
+}
+
+impl Value {
+    /// The name of the value's type, as shown in error messages.
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Value::String(_) => "string",
+            Value::Char(_) => "char",
+            Value::Int(_) => "int",
+            Value::BigInt(_) => "int",
+            Value::Decimal(_) => "decimal",
+            Value::Float(_) => "float",
+            Value::Boolean(_) => "bool",
+            Value::Array(_) => "array",
+            Value::Object(_) => "object",
+            Value::Null => "null",
+        }
+    }
+
+    /// The static type of the value. Big ints are ints.
+    pub fn type_symbol(&self) -> TypeSymbol {
+        match self {
+            Value::String(_) => TypeSymbol::String,
+            Value::Char(_) => TypeSymbol::Char,
+            Value::Int(_) | Value::BigInt(_) => TypeSymbol::Int,
+            Value::Decimal(_) => TypeSymbol::Decimal,
+            Value::Float(_) => TypeSymbol::Float,
+            Value::Boolean(_) => TypeSymbol::Bool,
+            Value::Array(_) => TypeSymbol::Array,
+            Value::Object(_) => TypeSymbol::Object,
+            Value::Null => TypeSymbol::Null,
+        }
+    }
+
+    /// The value a literal written in the source stands for.
+    pub fn from_literal(literal: &LiteralToken) -> Self {
+        match literal {
+            LiteralToken::Int { value } => Value::Int(*value),
+            LiteralToken::BigInt { value } => Value::BigInt(value.clone()),
+            LiteralToken::Decimal { value } => Value::Decimal(*value),
+            LiteralToken::Float { value } => Value::Float(*value),
+            LiteralToken::String { value } => Value::String(value.clone()),
+            LiteralToken::Char { value } => Value::Char(*value),
+            LiteralToken::Bool { value } => Value::Boolean(*value),
+            LiteralToken::Null => Value::Null,
+        }
+    }
+
+    /// The literal that stands for this value, if it can be written as one.
+    /// Arrays and objects cannot.
+    pub fn to_literal(&self) -> Option<LiteralToken> {
+        let literal = match self {
+            Value::String(value) => LiteralToken::String {
+                value: value.clone(),
+            },
+            Value::Char(value) => LiteralToken::Char { value: *value },
+            Value::Int(value) => LiteralToken::Int { value: *value },
+            Value::BigInt(value) => LiteralToken::BigInt {
+                value: value.clone(),
+            },
+            Value::Decimal(value) => LiteralToken::Decimal { value: *value },
+            Value::Float(value) => LiteralToken::Float { value: *value },
+            Value::Boolean(value) => LiteralToken::Bool { value: *value },
+            Value::Array(_) | Value::Object(_) => return None,
+            Value::Null => LiteralToken::Null,
+        };
+        Some(literal)
+    }
+
+    /// Writes the value the way it would be written as a literal in the
+    /// source: strings and chars quoted and decimals with their `d`.
+    pub fn literal_text(&self) -> String {
+        match self {
+            Value::String(value) => format!("{:?}", value),
+            Value::Char(value) => format!("{:?}", value),
+            Value::Decimal(value) => format!("{}d", value),
+            value => value.to_string(),
+        }
+    }
+
+    /// Wraps the result of big int arithmetic, narrowing it back to an int
+    /// when it fits. A big int value therefore never holds an `i64`.
+    pub fn from_big_int(value: BigInt) -> Self {
+        match i64::try_from(&value) {
+            Ok(value) => Value::Int(value),
+            Err(_) => Value::BigInt(value),
+        }
+    }
+
+    /// Writes the value inside the arrays and objects in `visiting`. Inside
+    /// an array or object strings and chars are quoted, and one of
+    /// `visiting` is written as `[...]` or `{...}`.
+    fn write(&self, f: &mut fmt::Formatter<'_>, visiting: &mut Vec<*const ()>) -> fmt::Result {
+        let nested = !visiting.is_empty();
+        match self {
+            Value::String(value) if nested => write!(f, "{:?}", value),
+            Value::Char(value) if nested => write!(f, "{:?}", value),
+            Value::String(value) => write!(f, "{}", value),
+            Value::Char(value) => write!(f, "{}", value),
+            Value::Int(value) => write!(f, "{}", value),
+            Value::BigInt(value) => write!(f, "{}", value),
+            Value::Decimal(value) => write!(f, "{}", value),
+            // `{:?}` keeps the `.0` of whole floats, so `1.0` does not print
+            // like the int `1`.
+            Value::Float(value) => write!(f, "{:?}", value),
+            Value::Boolean(value) => write!(f, "{}", value),
+            Value::Array(elements) => {
+                if visiting.contains(&address(elements)) {
+                    return write!(f, "[...]");
+                }
+                visiting.push(address(elements));
+                write!(f, "[")?;
+                for (index, element) in elements.borrow().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ", ")?;
+                    }
+                    element.write(f, visiting)?;
+                }
+                visiting.pop();
+                write!(f, "]")
+            }
+            Value::Object(fields) => {
+                if visiting.contains(&address(fields)) {
+                    return write!(f, "{{...}}");
+                }
+                visiting.push(address(fields));
+                write!(f, "{{")?;
+                for (index, (name, value)) in fields.borrow().iter().enumerate() {
+                    if index > 0 {
+                        write!(f, ",")?;
+                    }
+                    write!(f, " {}: ", name)?;
+                    value.write(f, visiting)?;
+                }
+                if !fields.borrow().is_empty() {
+                    write!(f, " ")?;
+                }
+                visiting.pop();
+                write!(f, "}}")
+            }
+            Value::Null => write!(f, "null"),
+        }
+    }
+
+    /// Compares the values inside the arrays and objects in `visiting`. An
+    /// array or object reached again is only equal to itself.
+    fn equals(&self, other: &Value, visiting: &mut Vec<*const ()>) -> bool {
+        match (self, other) {
+            (Value::String(left), Value::String(right)) => left == right,
+            (Value::Char(left), Value::Char(right)) => left == right,
+            (Value::Int(left), Value::Int(right)) => left == right,
+            (Value::BigInt(left), Value::BigInt(right)) => left == right,
+            (Value::Decimal(left), Value::Decimal(right)) => left == right,
+            (Value::Float(left), Value::Float(right)) => left == right,
+            (Value::Boolean(left), Value::Boolean(right)) => left == right,
+            (Value::Array(left), Value::Array(right)) => {
+                equal_contents(left, right, visiting, |left, right, visiting| {
+                    left.len() == right.len()
+                        && left
+                            .iter()
+                            .zip(right)
+                            .all(|(left, right)| left.equals(right, visiting))
+                })
+            }
+            (Value::Object(left), Value::Object(right)) => {
+                equal_contents(left, right, visiting, |left, right, visiting| {
+                    left.len() == right.len()
+                        && left.iter().zip(right).all(|(left, right)| {
+                            left.0 == right.0 && left.1.equals(&right.1, visiting)
+                        })
+                })
+            }
+            (Value::Null, Value::Null) => true,
+            _ => false,
+        }
+    }
+
+    /// Hashes the value, with the arrays and objects inside it hashed by
+    /// their kind only when `nested`. Equality follows a value that contains
+    /// itself as deep as it goes, so a hash that did too would differ for
+    /// `a = [a]` and `[a]`, which are equal.
+    fn hash_with<H: Hasher>(&self, state: &mut H, nested: bool) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            Value::String(value) => value.hash(state),
+            Value::Char(value) => value.hash(state),
+            Value::Int(value) => value.hash(state),
+            Value::BigInt(value) => value.hash(state),
+            Value::Decimal(value) => value.hash(state),
+            Value::Float(value) => {
+                let value = if *value == 0.0 { 0.0 } else { *value };
+                value.to_bits().hash(state)
+            }
+            Value::Boolean(value) => value.hash(state),
+            Value::Array(_) | Value::Object(_) if nested => {}
+            Value::Array(elements) => {
+                let elements = elements.borrow();
+                elements.len().hash(state);
+                for element in elements.iter() {
+                    element.hash_with(state, true);
+                }
+            }
+            Value::Object(fields) => {
+                let fields = fields.borrow();
+                fields.len().hash(state);
+                for (name, value) in fields.iter() {
+                    name.hash(state);
+                    value.hash_with(state, true);
+                }
+            }
+            Value::Null => {}
+        }
+    }
+}
+
+/// Identifies a shared array or object while walking a value that may
+/// contain itself.
+fn address<T>(shared: &Rc<RefCell<T>>) -> *const () {
+    Rc::as_ptr(shared) as *const ()
+}
+
+/// Compares two arrays or two objects with `compare`. If either is already
+/// being compared further out, they are equal only if they are the same one.
+fn equal_contents<T>(
+    left: &Rc<RefCell<T>>,
+    right: &Rc<RefCell<T>>,
+    visiting: &mut Vec<*const ()>,
+    compare: impl FnOnce(&T, &T, &mut Vec<*const ()>) -> bool,
+) -> bool {
+    if visiting.contains(&address(left)) || visiting.contains(&address(right)) {
+        return Rc::ptr_eq(left, right);
+    }
+    visiting.push(address(left));
+    visiting.push(address(right));
+    let equal = compare(&left.borrow(), &right.borrow(), visiting);
+    visiting.truncate(visiting.len() - 2);
+    equal
+}
+
+/// Strings and chars are written bare at the top level, so printing a string
+/// shows its text.
+impl Display for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.write(f, &mut Vec::new())
+    }
+}
+
+impl PartialEq for Value {
+    fn eq(&self, other: &Self) -> bool {
+        self.equals(other, &mut Vec::new())
+    }
+}
+
+/// Consistent with `PartialEq`: `0.0` and `-0.0` hash alike, and decimals
+/// that differ only in trailing zeros, such as `1.5d` and `1.50d`, do too.
+/// A NaN is never equal to anything, so its hash does not matter.
+impl Hash for Value {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.hash_with(state, false);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::hash_map::DefaultHasher;
+
+    use super::*;
+
+    fn array(elements: Vec<Value>) -> (ArrayElements, Value) {
+        let elements = Rc::new(RefCell::new(elements));
+        (elements.clone(), Value::Array(elements))
+    }
+
+    fn hash(value: &Value) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        value.hash(&mut hasher);
+        hasher.finish()
+    }
+
+    #[test]
+    fn prints_nested_values() {
+        let (_, value) = array(vec![
+            Value::Int(1),
+            Value::String("a".to_string()),
+            Value::Char('b'),
+            Value::Null,
+        ]);
+        assert_eq!(value.to_string(), "[1, \"a\", 'b', null]");
+        assert_eq!(Value::String("a".to_string()).to_string(), "a");
+    }
+
+    #[test]
+    fn big_ints_are_ints() {
+        let big = Value::from_big_int(BigInt::from(i64::MAX) + 1);
+        assert_eq!(big.type_name(), "int");
+        assert_eq!(big.type_symbol(), TypeSymbol::Int);
+    }
+
+    #[test]
+    fn prints_an_array_that_contains_itself() {
+        let (elements, value) = array(vec![Value::Int(0)]);
+        elements.borrow_mut()[0] = value.clone();
+        assert_eq!(value.to_string(), "[[...]]");
+    }
+
+    #[test]
+    fn prints_an_object_that_contains_itself() {
+        let fields = Rc::new(RefCell::new(Vec::new()));
+        let value = Value::Object(fields.clone());
+        fields.borrow_mut().push(("me".to_string(), value.clone()));
+        assert_eq!(value.to_string(), "{ me: {...} }");
+    }
+
+    #[test]
+    fn compares_and_hashes_values_that_contain_themselves() {
+        let (first_elements, first) = array(vec![Value::Int(0)]);
+        first_elements.borrow_mut()[0] = first.clone();
+        let (second_elements, second) = array(vec![Value::Int(0)]);
+        second_elements.borrow_mut()[0] = second.clone();
+
+        assert_eq!(first, first.clone());
+        assert_eq!(hash(&first), hash(&first.clone()));
+        // Each is only equal to itself once it is reached again.
+        assert_ne!(first, second);
+        assert_ne!(first, array(vec![Value::Int(0)]).1);
+
+        // `[first]` is equal to `first`, whose only element is `first`.
+        let wrapped = array(vec![first.clone()]).1;
+        assert_eq!(first, wrapped);
+        assert_eq!(hash(&first), hash(&wrapped));
+    }
+
+    #[test]
+    fn equal_values_hash_alike() {
+        assert_eq!(
+            array(vec![Value::Int(1), Value::Float(0.0)]).1,
+            array(vec![Value::Int(1), Value::Float(-0.0)]).1
+        );
+        assert_eq!(hash(&Value::Float(0.0)), hash(&Value::Float(-0.0)));
+        let (one_and_a_half, trailing_zero) = (
+            Value::Decimal(Decimal::new(15, 1)),
+            Value::Decimal(Decimal::new(150, 2)),
+        );
+        assert_eq!(one_and_a_half, trailing_zero);
+        assert_eq!(hash(&one_and_a_half), hash(&trailing_zero));
+        assert_ne!(Value::Int(1), Value::Float(1.0));
+    }
+}
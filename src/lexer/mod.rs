@@ -2,20 +2,29 @@
 
 pub mod source_text;
 
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+use unicode_xid::UnicodeXID;
+
 use crate::{
     diagnostics::{self, Diagnostics},
     syntax::{
         Keywords, LiteralToken,
         SyntaxKind::{Keyword, Token},
-        SyntaxKindDescriptor, Tokens,
+        SyntaxToken, SyntaxTrivia, Tokens,
     },
 };
 
 #[derive(Debug, Clone)]
 pub struct LexerError {
-    pub descriptor: SyntaxKindDescriptor,
+    pub descriptor: SyntaxToken,
     pub message: String,
 }
+
+/// Turns a `SourceText` into tokens.
+///
+/// `position` is a byte offset into the source and always sits on a char
+/// boundary, so reading the current character and advancing are both O(1).
 pub struct Lexer {
     input: source_text::SourceText,
     position: usize,
@@ -32,12 +41,13 @@ impl Lexer {
         }
     }
 
+    /// Returns the character `offset` characters after the cursor, or `'\0'`
+    /// past the end of the input.
     fn peek(&self, offset: usize) -> char {
-        let position = self.position + offset;
-        if position >= self.input.text.len() {
-            return '\0';
-        }
-        self.input.text.chars().nth(position).unwrap()
+        self.input.text[self.position..]
+            .chars()
+            .nth(offset)
+            .unwrap_or('\0')
     }
 
     fn current(&self) -> char {
@@ -48,119 +58,253 @@ impl Lexer {
         self.peek(1)
     }
 
+    fn is_at_end(&self) -> bool {
+        self.position >= self.input.text.len()
+    }
+
     fn next(&mut self) {
-        self.position += 1;
+        if let Some(character) = self.input.text[self.position..].chars().next() {
+            self.position += character.len_utf8();
+        }
     }
 
-    fn next_token(&mut self) -> SyntaxKindDescriptor {
+    fn text_from(&self, start: usize) -> &str {
+        &self.input.text[start..self.position]
+    }
+
+    fn next_token(&mut self) -> SyntaxToken {
         let start = self.position;
 
         match self.current() {
-            '\0' => SyntaxKindDescriptor::new(start, Token(Tokens::EndOfFileToken)),
-            ' ' | '\n' | '\t' => {
-                while self.current() == ' ' || self.current() == '\n' || self.current() == '\t' {
+            '\0' if self.is_at_end() => SyntaxToken::new(start, Token(Tokens::EndOfFileToken)),
+            '\r' | '\n' => {
+                if self.current() == '\r' && self.lookahead() == '\n' {
                     self.next();
                 }
-                return SyntaxKindDescriptor::new(start, Token(Tokens::WhiteSpaceToken));
+                self.next();
+                SyntaxToken::new(start, Token(Tokens::NewLineToken))
             }
-            '#' => {
-                let mut comment = String::from("");
-                while self.current() != '\n' && self.current() != '\0' {
-                    comment.push(self.current());
+            c if c.is_whitespace() => {
+                while self.current().is_whitespace() && !matches!(self.current(), '\r' | '\n') {
                     self.next();
                 }
-                return SyntaxKindDescriptor::new(
-                    start,
-                    Token(Tokens::CommentToken { value: comment }),
-                );
+                SyntaxToken::new(start, Token(Tokens::WhiteSpaceToken))
             }
             '+' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::PlusToken));
+                if self.current() == '+' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::PlusPlusToken));
+                }
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::PlusEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::PlusToken))
             }
             '-' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::MinusToken));
+                if self.current() == '-' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::MinusMinusToken));
+                }
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::MinusEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::MinusToken))
             }
             '*' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::StarToken));
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::StarEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::StarToken))
             }
+            '/' if self.lookahead() == '/' => self.lex_line_comment(start),
+            '/' if self.lookahead() == '*' => self.lex_block_comment(start),
             '/' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::SlashToken));
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::SlashEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::SlashToken))
             }
             '%' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::PercentToken));
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::PercentEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::PercentToken))
             }
             '!' => {
                 self.next();
-                if self.lookahead() == '=' {
+                if self.current() == '=' {
                     self.next();
-                    return SyntaxKindDescriptor::new(start, Token(Tokens::BangEqualsToken));
+                    return SyntaxToken::new(start, Token(Tokens::BangEqualsToken));
                 }
-                return SyntaxKindDescriptor::new(start, Token(Tokens::BangToken));
+                SyntaxToken::new(start, Token(Tokens::BangToken))
             }
             '=' => {
                 self.next();
-                if self.lookahead() == '=' {
+                if self.current() == '=' {
                     self.next();
-                    return SyntaxKindDescriptor::new(start, Token(Tokens::EqualsEqualsToken));
+                    return SyntaxToken::new(start, Token(Tokens::EqualsEqualsToken));
                 }
-                return SyntaxKindDescriptor::new(start, Token(Tokens::EqualsToken));
+                SyntaxToken::new(start, Token(Tokens::EqualsToken))
+            }
+            '#' => {
+                self.next();
+                SyntaxToken::new(start, Token(Tokens::HashToken))
+            }
+            '@' => {
+                self.next();
+                SyntaxToken::new(start, Token(Tokens::AtToken))
             }
             '(' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::OpenParenthesisToken));
+                SyntaxToken::new(start, Token(Tokens::OpenParenthesisToken))
             }
             ')' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::CloseParenthesisToken));
+                SyntaxToken::new(start, Token(Tokens::CloseParenthesisToken))
             }
             ';' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::SemiColonToken));
+                SyntaxToken::new(start, Token(Tokens::SemiColonToken))
             }
             ':' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::ColonToken));
+                SyntaxToken::new(start, Token(Tokens::ColonToken))
+            }
+            ',' => {
+                self.next();
+                SyntaxToken::new(start, Token(Tokens::CommaToken))
+            }
+            '.' => {
+                self.next();
+                SyntaxToken::new(start, Token(Tokens::DotToken))
             }
             '{' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::OpenBraceToken));
+                SyntaxToken::new(start, Token(Tokens::OpenBraceToken))
             }
             '}' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::CloseBraceToken));
+                SyntaxToken::new(start, Token(Tokens::CloseBraceToken))
             }
             '[' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::OpenBracketToken));
+                SyntaxToken::new(start, Token(Tokens::OpenBracketToken))
             }
             ']' => {
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::CloseBracketToken));
+                SyntaxToken::new(start, Token(Tokens::CloseBracketToken))
             }
             '&' => {
-                
-                if self.lookahead() == '&' {
-                    self.position += 2;
-                    return SyntaxKindDescriptor::new(
-                        start,
-                        Token(Tokens::AmpersandAmpersandToken),
-                    );
-                }
                 self.next();
-                return SyntaxKindDescriptor::new(start, Token(Tokens::AmpersandToken));
+                if self.current() == '&' {
+                    self.next();
+                    if self.current() == '=' {
+                        self.next();
+                        return SyntaxToken::new(
+                            start,
+                            Token(Tokens::AmpersandAmpersandEqualsToken),
+                        );
+                    }
+                    return SyntaxToken::new(start, Token(Tokens::AmpersandAmpersandToken));
+                }
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::AmpersandEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::AmpersandToken))
             }
             '|' => {
                 self.next();
-                if self.lookahead() == '|' {
+                if self.current() == '|' {
                     self.next();
-                    return SyntaxKindDescriptor::new(start, Token(Tokens::PipePipeToken));
+                    if self.current() == '=' {
+                        self.next();
+                        return SyntaxToken::new(start, Token(Tokens::PipePipeEqualsToken));
+                    }
+                    return SyntaxToken::new(start, Token(Tokens::PipePipeToken));
+                }
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::PipeEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::PipeToken))
+            }
+            '?' => {
+                self.next();
+                match self.current() {
+                    '?' => {
+                        self.next();
+                        SyntaxToken::new(start, Token(Tokens::QuestionMarkQuestionMarkToken))
+                    }
+                    '.' => {
+                        self.next();
+                        SyntaxToken::new(start, Token(Tokens::QuestionMarkDotToken))
+                    }
+                    // Only `?[` written without a space is optional indexing,
+                    // so `cond ? [a] : [b]` still reads as a conditional.
+                    '[' => {
+                        self.next();
+                        SyntaxToken::new(start, Token(Tokens::QuestionMarkOpenBracketToken))
+                    }
+                    _ => SyntaxToken::new(start, Token(Tokens::QuestionMarkToken)),
+                }
+            }
+            '^' => {
+                self.next();
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::CaretEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::CaretToken))
+            }
+            '~' => {
+                self.next();
+                SyntaxToken::new(start, Token(Tokens::TildeToken))
+            }
+            '<' => {
+                self.next();
+                if self.current() == '<' {
+                    self.next();
+                    if self.current() == '=' {
+                        self.next();
+                        return SyntaxToken::new(start, Token(Tokens::LessThanLessThanEqualsToken));
+                    }
+                    return SyntaxToken::new(start, Token(Tokens::LessThanLessThanToken));
                 }
-                return SyntaxKindDescriptor::new(start, Token(Tokens::PipeToken));
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::LessThanEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::LessThanToken))
+            }
+            '>' => {
+                self.next();
+                if self.current() == '>' {
+                    self.next();
+                    if self.current() == '=' {
+                        self.next();
+                        return SyntaxToken::new(
+                            start,
+                            Token(Tokens::GreaterThanGreaterThanEqualsToken),
+                        );
+                    }
+                    return SyntaxToken::new(start, Token(Tokens::GreaterThanGreaterThanToken));
+                }
+                if self.current() == '=' {
+                    self.next();
+                    return SyntaxToken::new(start, Token(Tokens::GreaterThanEqualsToken));
+                }
+                SyntaxToken::new(start, Token(Tokens::GreaterThanToken))
             }
 
             '\'' => {
@@ -172,12 +316,9 @@ impl Lexer {
                 if closing != '\'' {
                     self.diagnostics
                         .add_error(diagnostics::ErrorKind::ExpectedToken {
-                            expected: SyntaxKindDescriptor::new(
-                                start,
-                                Token(Tokens::SingleQuoteToken),
-                            ),
+                            expected: SyntaxToken::new(start, Token(Tokens::SingleQuoteToken)),
 
-                            found: SyntaxKindDescriptor::new(
+                            found: SyntaxToken::new(
                                 self.position,
                                 Token(Tokens::LiteralToken {
                                     value: LiteralToken::Char { value: character },
@@ -185,76 +326,127 @@ impl Lexer {
                             ),
                             position: self.position,
                         });
+                } else {
+                    self.next();
                 }
 
-                return SyntaxKindDescriptor::new(
-                    self.position,
+                SyntaxToken::new(
+                    start,
                     Token(Tokens::LiteralToken {
                         value: LiteralToken::Char { value: character },
                     }),
-                );
+                )
             }
             '"' => {
                 self.next();
-                let position = start;
-                let mut value = String::new();
-                while self.current() != '"' {
-                    value.push(self.current());
+                let content_start = self.position;
+                while self.current() != '"' && !self.is_at_end() {
                     self.next();
                 }
-                self.next();
-                return SyntaxKindDescriptor::new(
-                    position,
+                let value = self.text_from(content_start).to_string();
+
+                if self.is_at_end() {
+                    self.diagnostics
+                        .add_error(diagnostics::ErrorKind::ExpectedToken {
+                            expected: SyntaxToken::new(
+                                self.position,
+                                Token(Tokens::DoubleQuoteToken),
+                            ),
+                            found: SyntaxToken::new(self.position, Token(Tokens::EndOfFileToken)),
+                            position: self.position,
+                        });
+                } else {
+                    self.next();
+                }
+
+                SyntaxToken::new(
+                    start,
                     Token(Tokens::LiteralToken {
                         value: LiteralToken::String { value },
                     }),
-                );
+                )
             }
             '0'..='9' => {
-                let mut value = String::new();
+                // Digits, then a fraction and an exponent, each at most once.
+                // A `.` only starts the fraction when a digit follows it, so
+                // in `1.2.3` the literal ends before the second `.`.
+                self.skip_digits();
                 let mut is_float = false;
-                while self.current().is_numeric()
-                    || self.current() == '.'
-                    || self.current() == 'e'
-                    || self.current() == '-'
-                    || self.current() == '+'
-                {
-                    if self.current() == '.' {
-                        is_float = true;
+                if self.current() == '.' && self.lookahead().is_ascii_digit() {
+                    is_float = true;
+                    self.next();
+                    self.skip_digits();
+                }
+                if matches!(self.current(), 'e' | 'E') {
+                    is_float = true;
+                    self.next();
+                    if matches!(self.current(), '+' | '-') {
+                        self.next();
                     }
-                    value.push(self.current());
+                    if !self.current().is_ascii_digit() {
+                        self.diagnostics
+                            .add_error(diagnostics::ErrorKind::InvalidNumberLiteral {
+                                position: start,
+                            });
+                    }
+                    self.skip_digits();
+                }
+                // A `d` suffix makes the number a decimal, as in `12.34d`.
+                let is_decimal = self.current() == 'd'
+                    && !(self.lookahead() == '_' || self.lookahead().is_xid_continue());
+                if is_decimal {
                     self.next();
                 }
 
-                if is_float {
-                    return SyntaxKindDescriptor::new(
+                let value = self.text_from(start);
+                if is_decimal {
+                    let digits = &value[..value.len() - 1];
+                    let parsed = if digits.contains(['e', 'E']) {
+                        Decimal::from_scientific(digits)
+                    } else {
+                        Decimal::from_str_exact(digits)
+                    };
+                    let value = parsed.unwrap_or_else(|_| {
+                        self.diagnostics
+                            .add_error(diagnostics::ErrorKind::InvalidDecimalLiteral {
+                                position: start,
+                            });
+                        Decimal::ZERO
+                    });
+                    SyntaxToken::new(
                         start,
                         Token(Tokens::LiteralToken {
-                            value: LiteralToken::Float {
-                                value: value.parse::<f64>().unwrap(),
-                            },
+                            value: LiteralToken::Decimal { value },
                         }),
-                    );
-                } else {
-                    return SyntaxKindDescriptor::new(
+                    )
+                } else if is_float {
+                    // An exponent without digits was reported above.
+                    let value = value.parse::<f64>().unwrap_or(0.0);
+                    SyntaxToken::new(
                         start,
                         Token(Tokens::LiteralToken {
-                            value: LiteralToken::Int {
-                                value: value.parse::<i64>().unwrap(),
-                            },
+                            value: LiteralToken::Float { value },
                         }),
-                    );
+                    )
+                } else {
+                    // Literals too large for an int become big ints. A run of
+                    // digits always parses as one of the two.
+                    let value = match value.parse::<i64>() {
+                        Ok(value) => LiteralToken::Int { value },
+                        Err(_) => LiteralToken::BigInt {
+                            value: value.parse::<BigInt>().unwrap(),
+                        },
+                    };
+                    SyntaxToken::new(start, Token(Tokens::LiteralToken { value }))
                 }
             }
-            'a'..='z' | 'A'..='Z' | '_' => {
-                let mut value = String::new();
-                let position = start;
-                while self.current().is_alphanumeric() || self.current() == '_' {
-                    value.push(self.current());
+            c if c == '_' || c.is_xid_start() => {
+                while self.current() == '_' || self.current().is_xid_continue() {
                     self.next();
                 }
 
-                self.match_keywords_and_string_literals(value.as_str(), position)
+                let value = self.text_from(start).to_string();
+                self.match_keywords_and_string_literals(value.as_str(), start)
             }
             _ => {
                 let token = Tokens::UnknownToken {
@@ -262,50 +454,122 @@ impl Lexer {
                 };
                 self.diagnostics
                     .add_error(diagnostics::ErrorKind::UnknownToken {
-                        token: SyntaxKindDescriptor::new(self.position, Token(token.clone())),
-                        position: self.position,
+                        token: SyntaxToken::new(start, Token(token.clone())),
+                        position: start,
                     });
-                return SyntaxKindDescriptor::new(self.position, Token(token));
+                self.next();
+                SyntaxToken::new(start, Token(token))
             }
         }
     }
 
-    fn match_keywords_and_string_literals(
-        &self,
-        token: &str,
-        position: usize,
-    ) -> SyntaxKindDescriptor {
+    fn skip_digits(&mut self) {
+        while self.current().is_ascii_digit() {
+            self.next();
+        }
+    }
+
+    /// Lexes a `//` comment up to the end of the line. Comments starting with
+    /// exactly three slashes are doc comments and keep their text without the
+    /// `///` marker so the parser can attach them to the next declaration.
+    fn lex_line_comment(&mut self, start: usize) -> SyntaxToken {
+        while self.current() != '\n' && !self.is_at_end() {
+            self.next();
+        }
+
+        let text = self.text_from(start).trim_end_matches('\r');
+        if text.starts_with("///") && !text.starts_with("////") {
+            let value = text[3..]
+                .strip_prefix(' ')
+                .unwrap_or(&text[3..])
+                .to_string();
+            return SyntaxToken::new(start, Token(Tokens::DocCommentToken { value }));
+        }
+
+        SyntaxToken::new(
+            start,
+            Token(Tokens::CommentToken {
+                value: text.to_string(),
+            }),
+        )
+    }
+
+    /// Lexes a `/* ... */` comment. Block comments nest, so every `/*` inside
+    /// the comment needs its own matching `*/`.
+    fn lex_block_comment(&mut self, start: usize) -> SyntaxToken {
+        self.next();
+        self.next();
+        let mut depth = 1;
+
+        while depth > 0 {
+            if self.is_at_end() {
+                self.diagnostics
+                    .add_error(diagnostics::ErrorKind::UnterminatedComment { position: start });
+                break;
+            }
+
+            if self.current() == '/' && self.lookahead() == '*' {
+                self.next();
+                self.next();
+                depth += 1;
+            } else if self.current() == '*' && self.lookahead() == '/' {
+                self.next();
+                self.next();
+                depth -= 1;
+            } else {
+                self.next();
+            }
+        }
+
+        SyntaxToken::new(
+            start,
+            Token(Tokens::CommentToken {
+                value: self.text_from(start).to_string(),
+            }),
+        )
+    }
+
+    fn match_keywords_and_string_literals(&self, token: &str, position: usize) -> SyntaxToken {
         match token {
-            "let" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::LetKeyword)),
-            "if" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::IfKeyword)),
-            "else" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::ElseKeyword)),
-            "for" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::ForKeyword)),
-            "loop" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::LoopKeyword)),
-            "break" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::BreakKeyword)),
-            "continue" => {
-                return SyntaxKindDescriptor::new(position, Keyword(Keywords::ContinueKeyword))
-            }
-            "match" => return SyntaxKindDescriptor::new(position, Keyword(Keywords::MatchKeyword)),
-            "true" =>  SyntaxKindDescriptor::new(
-                self.position,
+            "let" => SyntaxToken::new(position, Keyword(Keywords::LetKeyword)),
+            "mut" => SyntaxToken::new(position, Keyword(Keywords::MutKeyword)),
+            "const" => SyntaxToken::new(position, Keyword(Keywords::ConstKeyword)),
+            "if" => SyntaxToken::new(position, Keyword(Keywords::IfKeyword)),
+            "else" => SyntaxToken::new(position, Keyword(Keywords::ElseKeyword)),
+            "while" => SyntaxToken::new(position, Keyword(Keywords::WhileKeyword)),
+            "for" => SyntaxToken::new(position, Keyword(Keywords::ForKeyword)),
+            "loop" => SyntaxToken::new(position, Keyword(Keywords::LoopKeyword)),
+            "break" => SyntaxToken::new(position, Keyword(Keywords::BreakKeyword)),
+            "continue" => SyntaxToken::new(position, Keyword(Keywords::ContinueKeyword)),
+            "match" => SyntaxToken::new(position, Keyword(Keywords::MatchKeyword)),
+            "div" => SyntaxToken::new(position, Keyword(Keywords::DivKeyword)),
+            "as" => SyntaxToken::new(position, Keyword(Keywords::AsKeyword)),
+            "fn" => SyntaxToken::new(position, Keyword(Keywords::FnKeyword)),
+            "return" => SyntaxToken::new(position, Keyword(Keywords::ReturnKeyword)),
+            "true" => SyntaxToken::new(
+                position,
                 Token(Tokens::LiteralToken {
-                    value: LiteralToken::Bool { value: true},
-                })
+                    value: LiteralToken::Bool { value: true },
+                }),
             ),
-            "false" =>  SyntaxKindDescriptor::new(
-                self.position,
+            "false" => SyntaxToken::new(
+                position,
                 Token(Tokens::LiteralToken {
-                    value: LiteralToken::Bool { value: false},
-                })
+                    value: LiteralToken::Bool { value: false },
+                }),
+            ),
+            "null" => SyntaxToken::new(
+                position,
+                Token(Tokens::LiteralToken {
+                    value: LiteralToken::Null,
+                }),
+            ),
+            _ => SyntaxToken::new(
+                position,
+                Token(Tokens::IdentifierToken {
+                    value: token.to_string(),
+                }),
             ),
-            _ => {
-                return SyntaxKindDescriptor::new(
-                    position,
-                    Token(Tokens::IdentifierToken {
-                        value: token.to_string(),
-                    }),
-                );
-            }
         }
     }
 
@@ -313,25 +577,171 @@ impl Lexer {
         self.diagnostics.clone()
     }
 
-    pub fn lex(&mut self) -> Vec<SyntaxKindDescriptor> {
-        let mut tokens: Vec<SyntaxKindDescriptor> = vec![];
+    fn is_trivia_start(&self) -> bool {
+        match self.current() {
+            '/' => matches!(self.lookahead(), '/' | '*'),
+            c => c.is_whitespace(),
+        }
+    }
+
+    fn is_doc_comment_start(&self) -> bool {
+        self.current() == '/'
+            && self.lookahead() == '/'
+            && self.peek(2) == '/'
+            && self.peek(3) != '/'
+    }
+
+    /// Reads the trivia around a token. Leading trivia takes everything up to
+    /// the next token; trailing trivia stops after the first line break, so a
+    /// comment on its own line belongs to the token below it. Doc comments are
+    /// always leading trivia of the declaration they document.
+    fn read_trivia(&mut self, leading: bool) -> Vec<SyntaxTrivia> {
+        let mut trivia = Vec::new();
+        while self.is_trivia_start() {
+            if !leading && self.is_doc_comment_start() {
+                break;
+            }
+
+            let start = self.position;
+            let kind = match self.next_token().kind() {
+                Token(kind) => kind.clone(),
+                Keyword(_) => unreachable!("trivia is never lexed as a keyword"),
+            };
+            let is_line_break = kind == Tokens::NewLineToken;
+            trivia.push(SyntaxTrivia::new(
+                kind,
+                start,
+                self.text_from(start).to_string(),
+            ));
+
+            if !leading && is_line_break {
+                break;
+            }
+        }
+        trivia
+    }
+
+    pub fn lex(&mut self) -> Vec<SyntaxToken> {
+        let mut tokens: Vec<SyntaxToken> = vec![];
         loop {
+            let leading_trivia = self.read_trivia(true);
+            let start = self.position;
             let token = self.next_token();
-            let syntax = token.syntax();
+            let text = self.text_from(start).to_string();
+            let trailing_trivia = self.read_trivia(false);
 
-            match syntax {
-                Token(Tokens::EndOfFileToken) => {
-                    tokens.push(token);
-                    break;
-                }
-                Token(Tokens::WhiteSpaceToken) => {
-                    continue;
-                }
-                _ => {
-                    tokens.push(token);
-                }
-            };
+            let is_end_of_file = token.kind() == &Token(Tokens::EndOfFileToken);
+            tokens.push(SyntaxToken::with_trivia(
+                token.position(),
+                token.kind().clone(),
+                text,
+                leading_trivia,
+                trailing_trivia,
+            ));
+
+            if is_end_of_file {
+                break;
+            }
         }
         tokens
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{diagnostics::ErrorKind, syntax::SyntaxKind};
+
+    fn lex(text: &str) -> (Vec<SyntaxKind>, Diagnostics) {
+        let mut lexer = Lexer::new(source_text::SourceText::new(text.to_string()));
+        let kinds = lexer
+            .lex()
+            .iter()
+            .map(|token| token.kind().clone())
+            .filter(|kind| kind != &Token(Tokens::EndOfFileToken))
+            .collect();
+        (kinds, lexer.diagnostics())
+    }
+
+    fn literal(value: LiteralToken) -> SyntaxKind {
+        Token(Tokens::LiteralToken { value })
+    }
+
+    #[test]
+    fn number_ends_at_a_second_dot() {
+        let (kinds, diagnostics) = lex("1.2.3");
+        assert_eq!(
+            kinds,
+            vec![
+                literal(LiteralToken::Float { value: 1.2 }),
+                Token(Tokens::DotToken),
+                literal(LiteralToken::Int { value: 3 }),
+            ]
+        );
+        assert!(!diagnostics.has_errors());
+    }
+
+    #[test]
+    fn dot_without_digits_after_it_is_member_access() {
+        let (kinds, _) = lex("5.foo");
+        assert_eq!(
+            kinds,
+            vec![
+                literal(LiteralToken::Int { value: 5 }),
+                Token(Tokens::DotToken),
+                Token(Tokens::IdentifierToken {
+                    value: "foo".to_string()
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn exponent_with_digits() {
+        assert_eq!(
+            lex("1e3").0,
+            vec![literal(LiteralToken::Float { value: 1000.0 })]
+        );
+        assert_eq!(
+            lex("2.5E-1").0,
+            vec![literal(LiteralToken::Float { value: 0.25 })]
+        );
+    }
+
+    #[test]
+    fn exponent_without_digits_is_an_error() {
+        for text in ["1e", "1e+", "2.5E-;"] {
+            let (_, diagnostics) = lex(text);
+            assert!(
+                matches!(
+                    diagnostics.errors.as_slice(),
+                    [ErrorKind::InvalidNumberLiteral { position: 0 }]
+                ),
+                "{}: {:?}",
+                text,
+                diagnostics.errors
+            );
+        }
+    }
+
+    #[test]
+    fn comparison_operators() {
+        let (kinds, _) = lex("< <= << <<= > >= >> >>= == !=");
+        assert_eq!(
+            kinds,
+            [
+                Tokens::LessThanToken,
+                Tokens::LessThanEqualsToken,
+                Tokens::LessThanLessThanToken,
+                Tokens::LessThanLessThanEqualsToken,
+                Tokens::GreaterThanToken,
+                Tokens::GreaterThanEqualsToken,
+                Tokens::GreaterThanGreaterThanToken,
+                Tokens::GreaterThanGreaterThanEqualsToken,
+                Tokens::EqualsEqualsToken,
+                Tokens::BangEqualsToken,
+            ]
+            .map(Token)
+        );
+    }
+}
@@ -1,4 +1,3 @@
-
 #[derive(Debug, Clone)]
 pub struct SourceText {
     pub text: String,
@@ -7,31 +6,24 @@ pub struct SourceText {
 
 impl SourceText {
     pub fn new(text: String) -> SourceText {
-        let mut source = SourceText {
-            text,
-            lines: Vec::new(),
-        };
-
-        source.lines = source.parse_lines(source.text.clone());
-        source
-
+        let lines = Self::parse_lines(&text);
+        SourceText { text, lines }
     }
 
-    fn parse_lines(&self, text: String) -> Vec<TextLine> {
+    fn parse_lines(text: &str) -> Vec<TextLine> {
         let mut lines = Vec::new();
         let mut position = 0;
         let mut line_start = 0;
 
         while position < text.len() {
-            let line_break_width = self.get_line_break_width(&text, position);
+            let line_break_width = Self::get_line_break_width(text, position);
             if line_break_width == 0 {
                 position += 1;
             } else {
                 lines.push(TextLine::new(
-                    self,
                     line_start,
                     position - line_start,
-                    line_break_width,
+                    position - line_start + line_break_width,
                 ));
                 position += line_break_width;
                 line_start = position;
@@ -39,65 +31,74 @@ impl SourceText {
         }
 
         if position >= line_start {
-            lines.push(TextLine::new(self, line_start, position - line_start, 0));
+            lines.push(TextLine::new(
+                line_start,
+                position - line_start,
+                position - line_start,
+            ));
         }
 
         lines
     }
 
+    pub fn lines(&self) -> &[TextLine] {
+        &self.lines
+    }
 
-    pub fn span_to_string(&self, span: TextSpan) -> String {
-        self.bounds_to_string(span.start, span.end)
+    /// Returns the index of the line containing the byte offset `position`.
+    pub fn get_line_index(&self, position: usize) -> usize {
+        match self
+            .lines
+            .binary_search_by(|line| line.get_start().cmp(&position))
+        {
+            Ok(index) => index,
+            Err(index) => index.saturating_sub(1),
+        }
     }
 
-    pub fn to_string(&self) -> String {
-        self.text.clone()
+    pub fn span_to_string(&self, span: TextSpan) -> String {
+        self.bounds_to_string(span.start, span.end)
     }
 
     pub fn bounds_to_string(&self, start: usize, end: usize) -> String {
         self.text[start..end].to_string()
     }
 
-    fn get_line_break_width(&self, text: &String, position: usize) -> usize {
-        // get character at position of text
-        let c = text.as_bytes()[position] as char;
+    /// Line breaks are ASCII, so scanning bytes is safe: no byte of a
+    /// multibyte UTF-8 sequence can be mistaken for `\r` or `\n`.
+    fn get_line_break_width(text: &str, position: usize) -> usize {
+        let bytes = text.as_bytes();
+        let c = bytes[position];
+        let l = bytes.get(position + 1).copied().unwrap_or(b'\0');
 
-        let l = if position + 1 >= text.len() {
-            '\0'
-        } else {
-            text.as_bytes()[position + 1] as char
-        };
-
-        if c == '\r' && l == '\n' {
+        if c == b'\r' && l == b'\n' {
             return 2;
         }
 
-        if c == '\r' || c == '\n' {
+        if c == b'\r' || c == b'\n' {
             return 1;
         }
 
-        return 0;
+        0
     }
 }
 
+impl std::fmt::Display for SourceText {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.text)
+    }
+}
 
 #[derive(Debug, Clone)]
-struct TextLine {
-    source: SourceText,
+pub struct TextLine {
     start: usize,
     length: usize,
     length_with_line_breaks: usize,
 }
 
 impl TextLine {
-    pub fn new(
-        source: &SourceText,
-        start: usize,
-        length: usize,
-        length_with_line_breaks: usize,
-    ) -> Self {
+    pub fn new(start: usize, length: usize, length_with_line_breaks: usize) -> Self {
         Self {
-            source: source.clone(),
             start,
             length,
             length_with_line_breaks,
@@ -128,19 +129,20 @@ impl TextLine {
         TextSpan::new(self.start, self.length_with_line_breaks)
     }
 
-    pub fn to_string(&self) -> String {
-        self.source.span_to_string(self.get_span())
+    pub fn text(&self, source: &SourceText) -> String {
+        source.span_to_string(self.get_span())
     }
 }
 
-struct TextSpan {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TextSpan {
     start: usize,
     length: usize,
     end: usize,
 }
 
 impl TextSpan {
-    fn new(start: usize, length: usize) -> Self {
+    pub fn new(start: usize, length: usize) -> Self {
         Self {
             start,
             length,
@@ -148,15 +150,19 @@ impl TextSpan {
         }
     }
 
-    fn get_start(&self) -> usize {
+    pub fn from_bounds(start: usize, end: usize) -> Self {
+        Self::new(start, end - start)
+    }
+
+    pub fn get_start(&self) -> usize {
         self.start
     }
 
-    fn get_length(&self) -> usize {
+    pub fn get_length(&self) -> usize {
         self.length
     }
 
-    fn get_end(&self) -> usize {
+    pub fn get_end(&self) -> usize {
         self.end
     }
 }
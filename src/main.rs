@@ -1,21 +1,93 @@
-
 #![allow(dead_code)]
 
-use evaluator::Types;
+use std::collections::HashMap;
+
+use binding::BoundBlockStatement;
+use diagnostics::{Lint, LintLevels, Severity};
+use evaluator::{Arithmetic, OverflowPolicy, RoundingMode, Value};
+use symbols::{FunctionSymbol, VariableSymbol};
 
+mod binding;
 mod diagnostics;
 mod evaluator;
 mod lexer;
+mod optimizer;
 mod parser;
+mod symbols;
 mod syntax;
+
+/// Settings that apply to every input, changed with command line flags or
+/// `#` commands in the REPL.
+struct Options {
+    show_tree: bool,
+    show_stack: bool,
+    /// Whether type errors are reported before running. Without it, type
+    /// annotations are still checked when values are stored.
+    type_check: bool,
+    /// Whether constant expressions are folded before running.
+    fold_constants: bool,
+    arithmetic: Arithmetic,
+    lints: LintLevels,
+}
+
+impl Default for Options {
+    fn default() -> Self {
+        Self {
+            show_tree: false,
+            show_stack: false,
+            type_check: true,
+            fold_constants: true,
+            arithmetic: Arithmetic::default(),
+            lints: LintLevels::default(),
+        }
+    }
+}
+
+/// What earlier inputs left behind: the global variables and functions they
+/// declared, in declaration order, the values of every variable that has
+/// one and the body of every function.
+#[derive(Default)]
+struct Session {
+    globals: Vec<VariableSymbol>,
+    variables: HashMap<VariableSymbol, Value>,
+    functions: Vec<FunctionSymbol>,
+    function_bodies: HashMap<FunctionSymbol, BoundBlockStatement>,
+}
+
 fn main() {
+    let mut options = Options::default();
+    let mut session = Session::default();
+
+    let mut path = None;
+    for argument in std::env::args().skip(1) {
+        if let Some(policy) = argument.strip_prefix("--overflow=") {
+            options.arithmetic.overflow = parse_overflow_policy(policy);
+        } else if let Some(mode) = argument.strip_prefix("--rounding=") {
+            options.arithmetic.rounding = parse_rounding_mode(mode);
+        } else if argument == "--no-type-check" {
+            options.type_check = false;
+        } else if argument == "--no-fold" {
+            options.fold_constants = false;
+        } else if argument == "--deny-warnings" {
+            options.lints.warnings_as_errors = true;
+        } else if let Some((level, name)) = argument.strip_prefix("--").and_then(parse_lint_flag) {
+            set_lint_level(&mut options.lints, name, level);
+        } else {
+            path = Some(argument);
+        }
+    }
+
+    // `balance <file>` runs a whole file once instead of starting the REPL.
+    if let Some(path) = path {
+        let input = std::fs::read_to_string(&path)
+            .unwrap_or_else(|error| panic!("Could not read {}: {}", path, error));
+        run(&input, &mut session, &options);
+        println!();
+        return;
+    }
 
-    let mut show_tree = false;
-    let mut show_stack = false;
-    let mut variables: Vec<(String, Types)> = Vec::new();
     clearscreen::clear().expect("Could not clear screen");
     loop {
-
         println!("> ");
 
         let mut input = String::new();
@@ -24,7 +96,7 @@ fn main() {
 
         if input == "#clear" {
             clearscreen::clear().expect("Could not clear screen");
-            variables.clear();
+            session = Session::default();
             continue;
         }
 
@@ -32,60 +104,263 @@ fn main() {
             break;
         }
         if input == "#show_tree" {
-            show_tree = !show_tree;
+            options.show_tree = !options.show_tree;
             continue;
         }
 
         if input == "#show_stack" {
-            show_stack = !show_stack;
+            options.show_stack = !options.show_stack;
             continue;
         }
 
-        let source_text = lexer::source_text::SourceText::new(input.to_string());
-        let mut lexer = lexer::Lexer::new(source_text);
-        let tokens = lexer.lex();
+        if input == "#type_check" {
+            options.type_check = !options.type_check;
+            continue;
+        }
 
-        if show_tree {
-            print!("Lexer tokens: \n");
-            for token in &tokens {
-                token.print();
-                print!("\n");
-            }
+        if input == "#fold" {
+            options.fold_constants = !options.fold_constants;
+            continue;
         }
 
-        let mut parser = parser::Parser::new(tokens, input.to_string());
-        let tree = parser.parse();
+        if input == "#deny_warnings" {
+            options.lints.warnings_as_errors = !options.lints.warnings_as_errors;
+            continue;
+        }
 
-        if show_tree {
-            print!("\n\nSyntax tree: \n");
-            tree.print();
+        if input == "#lints" {
+            show_lints(&options.lints);
+            continue;
         }
 
+        // `#allow unused-variable` turns a lint off; `#hint`, `#info`,
+        // `#warn` and `#deny` report it at that level.
+        if let Some((level, name)) = input.strip_prefix('#').and_then(parse_lint_flag) {
+            set_lint_level(&mut options.lints, name.trim(), level);
+            continue;
+        }
 
-        let mut diagnostics = lexer.diagnostics();
-        diagnostics.merge(parser.diagnostics());
+        // `#overflow wrap` picks what int arithmetic does on overflow.
+        if let Some(policy) = input.strip_prefix("#overflow ") {
+            options.arithmetic.overflow = parse_overflow_policy(policy.trim());
+            continue;
+        }
 
-        if diagnostics.has_errors() {
-            diagnostics.print();
+        // `#rounding half-up` picks how decimal division rounds.
+        if let Some(mode) = input.strip_prefix("#rounding ") {
+            options.arithmetic.rounding = parse_rounding_mode(mode.trim());
             continue;
         }
 
-        if show_tree {
-            print!("\n\nSyntax tree: \n");
-            tree.print();
+        // `#type x + 1` shows the type of an expression without running it.
+        if let Some(expression) = input.strip_prefix("#type ") {
+            show_type(expression, &session, &options);
+            continue;
         }
 
+        run(input, &mut session, &options);
+    }
+}
 
+/// Binds `input` and prints the inferred type of its value, or its errors.
+fn show_type(input: &str, session: &Session, options: &Options) {
+    let source_text = lexer::source_text::SourceText::new(input.to_string());
+    let mut lexer = lexer::Lexer::new(source_text);
+    let mut parser = parser::Parser::new(lexer.lex(), input.to_string());
+    let tree = parser.parse();
+    let scope = binding::bind_global_scope(
+        &session.globals,
+        &session.functions,
+        &tree,
+        options.type_check,
+    );
 
-        
-        let evaluator = evaluator::evaluate(tree.syntax(), &mut variables);
+    let mut diagnostics = lexer.diagnostics();
+    diagnostics.merge(parser.diagnostics());
+    diagnostics.merge(scope.diagnostics.clone());
+    diagnostics.apply_levels(&options.lints);
+    if diagnostics.has_errors() {
+        diagnostics.print();
+        return;
+    }
+    match scope.statements.last() {
+        Some(binding::BoundStatement::BoundExpressionStatement(statement)) => {
+            println!("{}", statement.expression.type_symbol().name())
+        }
+        _ => println!("Not an expression."),
+    }
+}
 
-        print!("\n\nResult: \n{:?}", evaluator);
+fn parse_overflow_policy(policy: &str) -> OverflowPolicy {
+    policy.parse().unwrap_or_else(|_| {
+        println!(
+            "Unknown overflow policy {}, expected error, wrap, saturate or promote. Using error.",
+            policy
+        );
+        OverflowPolicy::Error
+    })
+}
+
+fn parse_rounding_mode(mode: &str) -> RoundingMode {
+    mode.parse().unwrap_or_else(|_| {
+        println!(
+            "Unknown rounding mode {}, expected half-even, half-up, half-down, up, down, floor or ceiling. Using half-even.",
+            mode
+        );
+        RoundingMode::HalfEven
+    })
+}
+
+/// Splits a lint flag such as `allow=unused-variable` or `deny shadowing`
+/// into the level it sets and the lint it names. `None` for any other flag.
+fn parse_lint_flag(flag: &str) -> Option<(Option<Severity>, &str)> {
+    let (level, name) = flag.split_once(['=', ' '])?;
+    let level = match level {
+        "allow" => None,
+        "hint" => Some(Severity::Hint),
+        "info" => Some(Severity::Info),
+        "warn" => Some(Severity::Warning),
+        "deny" => Some(Severity::Error),
+        _ => return None,
+    };
+    Some((level, name))
+}
+
+fn set_lint_level(lints: &mut LintLevels, name: &str, level: Option<Severity>) {
+    match name.parse::<Lint>() {
+        Ok(lint) => lints.set(lint, level),
+        Err(()) => println!(
+            "Unknown lint {}, expected one of {}.",
+            name,
+            Lint::ALL.map(|lint| lint.name()).join(", ")
+        ),
+    }
+}
+
+/// Prints every lint with the level it is reported at.
+fn show_lints(lints: &LintLevels) {
+    for lint in Lint::ALL {
+        let level = match lints.level(lint) {
+            Some(severity) => severity.label().to_string(),
+            None => "Off".to_string(),
+        };
+        println!("{} ({}): {}", lint.name(), level, lint.description());
+    }
+}
+
+/// Lexes, parses, binds, folds and evaluates one input. Errors are printed
+/// instead of evaluating, and the variables are kept for the next input.
+fn run(input: &str, session: &mut Session, options: &Options) {
+    let source_text = lexer::source_text::SourceText::new(input.to_string());
+    let mut lexer = lexer::Lexer::new(source_text);
+    let tokens = lexer.lex();
+
+    if options.show_tree {
+        println!("Lexer tokens: ");
+        for token in &tokens {
+            token.print();
+            println!();
+        }
+    }
+
+    let mut parser = parser::Parser::new(tokens, input.to_string());
+    let tree = parser.parse();
+
+    if options.show_tree {
+        print!("\n\nSyntax tree: \n");
+        tree.print();
+    }
+
+    let scope = binding::bind_global_scope(
+        &session.globals,
+        &session.functions,
+        &tree,
+        options.type_check,
+    );
+
+    let mut diagnostics = lexer.diagnostics();
+    diagnostics.merge(parser.diagnostics());
+    diagnostics.merge(scope.diagnostics.clone());
+    diagnostics.apply_levels(&options.lints);
+
+    // Without errors this prints only what lints found, and the input still
+    // runs.
+    diagnostics.print();
+    if diagnostics.has_errors() {
+        return;
+    }
+
+    // The tree is folded only once it binds without errors, so errors in
+    // branches folding removes are still reported. Folding keeps every
+    // expression's type, so binding the folded tree should find no new ones,
+    // but if it does they are reported and the input does not run.
+    let scope = if options.fold_constants {
+        let (folded, folding_diagnostics) = optimizer::fold_constants(&tree, options.arithmetic);
+        if options.show_tree {
+            print!("\n\nFolded syntax tree: \n");
+            folded.print();
+        }
+        if folding_diagnostics.has_errors() {
+            folding_diagnostics.print();
+            return;
+        }
+        let folded_scope = binding::bind_global_scope(
+            &session.globals,
+            &session.functions,
+            &folded,
+            options.type_check,
+        );
+        if folded_scope.diagnostics.has_errors() {
+            // Its lints were already reported for the tree as it was written.
+            let mut diagnostics = folded_scope.diagnostics;
+            diagnostics.lints.clear();
+            diagnostics.print();
+            return;
+        }
+        folded_scope
+    } else {
+        scope
+    };
+
+    // Functions are kept even if running the input fails, since declaring
+    // one runs nothing.
+    session.functions.extend(scope.functions.iter().cloned());
+    session
+        .function_bodies
+        .extend(scope.function_bodies.clone());
+    let result = evaluator::evaluate(
+        &scope,
+        &mut session.variables,
+        &session.function_bodies,
+        options.arithmetic,
+    );
+    // Only globals whose declaration ran are kept, so a runtime error does
+    // not leave later inputs able to name a variable without a value. An
+    // input that ran to the end ran every declaration, including those
+    // without a value, which later inputs can still assign.
+    let completed = result.is_ok();
+    session.globals.extend(
+        scope
+            .variables
+            .into_iter()
+            .filter(|variable| completed || session.variables.contains_key(variable)),
+    );
+    match result {
+        Ok(result) => print!("\n\nResult: \n{}", result),
+        Err(error) => {
+            let mut diagnostics = diagnostics::Diagnostics::new(input.to_string());
+            diagnostics.add_error(*error);
+            diagnostics.print();
+        }
+    }
 
-        if show_stack {
-            print!("\n\nStack: \n");
-            for (name, value) in &variables {
-                print!("{}: {:?}\n", name, value);
+    if options.show_stack {
+        print!("\n\nStack: \n");
+        // A global declared without a value has none until it is assigned.
+        for variable in &session.globals {
+            match session.variables.get(variable) {
+                Some(value) => println!("{}: {}", variable.name(), value),
+                None => println!("{}: <no value>", variable.name()),
             }
         }
     }
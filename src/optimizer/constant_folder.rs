@@ -0,0 +1,377 @@
+use crate::{
+    diagnostics::{Diagnostics, ErrorKind},
+    evaluator::{apply_binary_operator, apply_unary_operator, short_circuit, Arithmetic, Value},
+    syntax::{
+        rewriter::{self, SyntaxRewriter},
+        BinaryExpressionSyntax, BlockStatementSyntax, ConditionalExpressionSyntax, ElseBodySyntax,
+        ElseClauseSyntax, ExpressionId, ExpressionSyntax, IfStatementSyntax,
+        LiteralExpressionSyntax, ParenthesizedExpressionSyntax, StatementId, StatementSyntax,
+        SyntaxArena, SyntaxKind, SyntaxToken, SyntaxTree, SyntaxWalker, Tokens,
+        UnaryExpressionSyntax, WhileStatementSyntax,
+    },
+};
+
+/// Rewrites `tree` with every operator whose operands are literals replaced
+/// by the literal it evaluates to, parentheses removed, and every `if` whose
+/// condition is a literal replaced by the block it would run. Arithmetic
+/// follows `arithmetic`, as it would when the tree runs.
+///
+/// An operator that would fail when it runs, such as a division by a
+/// literal zero, is reported and left as it is. Operators applied to the
+/// wrong types are left for the binder to report.
+pub fn fold_constants(tree: &SyntaxTree, arithmetic: Arithmetic) -> (SyntaxTree, Diagnostics) {
+    let mut folder = ConstantFolder {
+        arithmetic,
+        diagnostics: Diagnostics::new(tree.full_text()),
+    };
+    let tree = folder.rewrite_tree(tree);
+    (tree, folder.diagnostics)
+}
+
+struct ConstantFolder {
+    arithmetic: Arithmetic,
+    diagnostics: Diagnostics,
+}
+
+impl ConstantFolder {
+    /// The value of an operator applied to literals, or `None` if it fails.
+    fn fold(&mut self, result: Result<Value, Box<ErrorKind>>) -> Option<Value> {
+        match result {
+            Ok(value) => Some(value),
+            Err(error) => {
+                if !matches!(
+                    *error,
+                    ErrorKind::InvalidBinaryOperands { .. } | ErrorKind::InvalidUnaryOperand { .. }
+                ) {
+                    self.diagnostics
+                        .add_error(ErrorKind::FailsWhenRun { error });
+                }
+                None
+            }
+        }
+    }
+
+    /// Picks the branch of an `if` with a literal condition, or rewrites both
+    /// branches. An `else if` is folded the same way, so the result is an
+    /// `if` or, when every condition on the way was a literal, a block.
+    fn fold_if_statement(
+        &mut self,
+        arena: &mut SyntaxArena,
+        statement: &IfStatementSyntax,
+    ) -> StatementSyntax {
+        let condition = self.rewrite_expression(arena, statement.condition);
+        match literal_value(arena, condition) {
+            Some(Value::Boolean(true)) => StatementSyntax::BlockStatementSyntax(
+                self.rewrite_block_statement(arena, &statement.body),
+            ),
+            Some(Value::Boolean(false)) => match &statement.else_clause {
+                Some(else_clause) => match &else_clause.body {
+                    ElseBodySyntax::Block(block) => StatementSyntax::BlockStatementSyntax(
+                        self.rewrite_block_statement(arena, block),
+                    ),
+                    ElseBodySyntax::If(statement) => self.fold_if_statement(arena, statement),
+                },
+                None => StatementSyntax::BlockStatementSyntax(BlockStatementSyntax::new(
+                    statement.body.open_brace.clone(),
+                    Vec::new(),
+                    statement.body.close_brace.clone(),
+                )),
+            },
+            _ => {
+                let body = self.rewrite_block_statement(arena, &statement.body);
+                let else_clause = statement.else_clause.as_ref().map(|else_clause| {
+                    let body = match &else_clause.body {
+                        ElseBodySyntax::Block(block) => {
+                            ElseBodySyntax::Block(self.rewrite_block_statement(arena, block))
+                        }
+                        ElseBodySyntax::If(statement) => {
+                            match self.fold_if_statement(arena, statement) {
+                                StatementSyntax::IfStatementSyntax(statement) => {
+                                    ElseBodySyntax::If(Box::new(statement))
+                                }
+                                StatementSyntax::BlockStatementSyntax(block) => {
+                                    ElseBodySyntax::Block(block)
+                                }
+                                statement => {
+                                    unreachable!("an if folds to an if or a block: {:?}", statement)
+                                }
+                            }
+                        }
+                    };
+                    ElseClauseSyntax::new(else_clause.else_keyword.clone(), body)
+                });
+                StatementSyntax::IfStatementSyntax(IfStatementSyntax::new(
+                    statement.keyword.clone(),
+                    statement.open_parenthesis.clone(),
+                    condition,
+                    statement.close_parenthesis.clone(),
+                    body,
+                    else_clause,
+                ))
+            }
+        }
+    }
+}
+
+impl SyntaxRewriter for ConstantFolder {
+    fn rewrite_statement(&mut self, arena: &mut SyntaxArena, id: StatementId) -> StatementId {
+        match arena[id].clone() {
+            StatementSyntax::IfStatementSyntax(statement) => {
+                let folded = self.fold_if_statement(arena, &statement);
+                if folded == StatementSyntax::IfStatementSyntax(statement) {
+                    return id;
+                }
+                arena.alloc_statement(folded)
+            }
+            // The body of `while (false)` never runs, so it is kept as it is.
+            StatementSyntax::WhileStatementSyntax(statement) => {
+                let condition = self.rewrite_expression(arena, statement.condition);
+                let body = match literal_value(arena, condition) {
+                    Some(Value::Boolean(false)) => statement.body.clone(),
+                    _ => self.rewrite_block_statement(arena, &statement.body),
+                };
+                if condition == statement.condition && body == statement.body {
+                    return id;
+                }
+                arena.alloc_statement(StatementSyntax::WhileStatementSyntax(
+                    WhileStatementSyntax::new(
+                        statement.while_keyword,
+                        statement.open_parenthesis,
+                        condition,
+                        statement.close_parenthesis,
+                        body,
+                    ),
+                ))
+            }
+            _ => rewriter::rewrite_statement(self, arena, id),
+        }
+    }
+
+    fn rewrite_binary_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &BinaryExpressionSyntax,
+    ) -> ExpressionId {
+        let left = self.rewrite_expression(arena, expression.left);
+        // The right side of `false && _`, `true || _` and a `??` on a value
+        // that is not null never runs, so it is kept as it is.
+        let decided = match (expression.operator.kind(), literal_value(arena, left)) {
+            (SyntaxKind::Token(operator), Some(left)) => short_circuit(operator, &left),
+            _ => None,
+        };
+        let right = match decided {
+            Some(_) => expression.right,
+            None => self.rewrite_expression(arena, expression.right),
+        };
+        if let (Some(left), Some(right)) = (literal_value(arena, left), literal_value(arena, right))
+        {
+            let result = match (decided, expression.operator.kind()) {
+                (Some(value), _) => Ok(value),
+                (None, SyntaxKind::Token(Tokens::QuestionMarkQuestionMarkToken)) => Ok(right),
+                (None, _) => {
+                    apply_binary_operator(&expression.operator, self.arithmetic, left, right)
+                }
+            };
+            if let Some(literal) = self
+                .fold(result)
+                .and_then(|value| replace_with_literal(arena, id, value))
+            {
+                return literal;
+            }
+        }
+        if left == expression.left && right == expression.right {
+            return id;
+        }
+        let rewritten =
+            BinaryExpressionSyntax::new(arena, left, expression.operator.clone(), right);
+        arena.alloc_expression(ExpressionSyntax::BinaryExpressionSyntax(rewritten))
+    }
+
+    fn rewrite_unary_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &UnaryExpressionSyntax,
+    ) -> ExpressionId {
+        let operand = self.rewrite_expression(arena, expression.operand);
+        if let Some(value) = literal_value(arena, operand) {
+            let result = apply_unary_operator(&expression.operator, self.arithmetic, value);
+            if let Some(literal) = self
+                .fold(result)
+                .and_then(|value| replace_with_literal(arena, id, value))
+            {
+                return literal;
+            }
+        }
+        if operand == expression.operand {
+            return id;
+        }
+        let rewritten = UnaryExpressionSyntax::new(arena, expression.operator.clone(), operand);
+        arena.alloc_expression(ExpressionSyntax::UnaryExpressionSyntax(rewritten))
+    }
+
+    /// Only the arm a literal condition picks is folded. The other never
+    /// runs, so it is kept as it is and nothing that would fail in it is
+    /// reported, as for the branch of an `if` folding drops. Both arms stay
+    /// so the expression keeps its type.
+    fn rewrite_conditional_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &ConditionalExpressionSyntax,
+    ) -> ExpressionId {
+        let condition = self.rewrite_expression(arena, expression.condition);
+        let (when_true, when_false) = match literal_value(arena, condition) {
+            Some(Value::Boolean(true)) => (
+                self.rewrite_expression(arena, expression.when_true),
+                expression.when_false,
+            ),
+            Some(Value::Boolean(false)) => (
+                expression.when_true,
+                self.rewrite_expression(arena, expression.when_false),
+            ),
+            _ => (
+                self.rewrite_expression(arena, expression.when_true),
+                self.rewrite_expression(arena, expression.when_false),
+            ),
+        };
+        if condition == expression.condition
+            && when_true == expression.when_true
+            && when_false == expression.when_false
+        {
+            return id;
+        }
+        let rewritten = ConditionalExpressionSyntax::new(
+            arena,
+            condition,
+            expression.question_mark_token.clone(),
+            when_true,
+            expression.colon_token.clone(),
+            when_false,
+        );
+        arena.alloc_expression(ExpressionSyntax::ConditionalExpressionSyntax(rewritten))
+    }
+
+    /// The tree already encodes how operators group, so parentheses are
+    /// only kept in the source text.
+    fn rewrite_parenthesized_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        _id: ExpressionId,
+        expression: &ParenthesizedExpressionSyntax,
+    ) -> ExpressionId {
+        self.rewrite_expression(arena, expression.expression)
+    }
+}
+
+/// The value of a literal expression.
+fn literal_value(arena: &SyntaxArena, id: ExpressionId) -> Option<Value> {
+    match &arena[id] {
+        ExpressionSyntax::LiteralExpressionSyntax(expression) => {
+            Some(Value::from_literal(&expression.value))
+        }
+        _ => None,
+    }
+}
+
+/// Allocates the literal for `value` to replace the expression `id`. The
+/// literal starts where the expression did and keeps the trivia around it.
+/// Values no literal can write give `None`.
+fn replace_with_literal(
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    value: Value,
+) -> Option<ExpressionId> {
+    let literal = value.to_literal()?;
+    let mut edges = EdgeTokens::default();
+    edges.visit_expression(arena, id);
+    let (first, last) = (edges.first?, edges.last?);
+    let token = SyntaxToken::with_trivia(
+        first.position(),
+        SyntaxKind::Token(Tokens::LiteralToken {
+            value: literal.clone(),
+        }),
+        value.literal_text(),
+        first.leading_trivia().to_vec(),
+        last.trailing_trivia().to_vec(),
+    );
+    let expression = LiteralExpressionSyntax::new(token, literal);
+    Some(arena.alloc_expression(ExpressionSyntax::LiteralExpressionSyntax(expression)))
+}
+
+/// Finds the first and last token of a node.
+#[derive(Default)]
+struct EdgeTokens {
+    first: Option<SyntaxToken>,
+    last: Option<SyntaxToken>,
+}
+
+impl SyntaxWalker for EdgeTokens {
+    fn visit_token(&mut self, token: &SyntaxToken) {
+        if self.first.is_none() {
+            self.first = Some(token.clone());
+        }
+        self.last = Some(token.clone());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lexer::{source_text::SourceText, Lexer},
+        parser::Parser,
+    };
+
+    /// The folded source of `text` and the errors folding it reported.
+    fn fold(text: &str) -> (String, Vec<ErrorKind>) {
+        let tokens = Lexer::new(SourceText::new(text.to_string())).lex();
+        let tree = Parser::new(tokens, text.to_string()).parse();
+        let (folded, diagnostics) = fold_constants(&tree, Arithmetic::default());
+        (folded.full_text(), diagnostics.errors)
+    }
+
+    #[test]
+    fn folds_operators_on_literals() {
+        let (text, errors) = fold("60 * 60 * 24;");
+        assert_eq!(text, "86400;");
+        assert!(errors.is_empty(), "{:?}", errors);
+        assert_eq!(fold("x + (1 + 2)").0, "x + 3");
+        assert_eq!(fold("true ? 1 + 1 : 2").0, "true ? 2 : 2");
+    }
+
+    #[test]
+    fn reports_operators_that_fail_when_run() {
+        let (text, errors) = fold("1 div 0");
+        assert_eq!(text, "1 div 0");
+        assert!(
+            matches!(
+                errors.as_slice(),
+                [ErrorKind::FailsWhenRun { error }]
+                    if matches!(**error, ErrorKind::DivisionByZero { position: 2 })
+            ),
+            "{:?}",
+            errors
+        );
+    }
+
+    #[test]
+    fn skips_code_a_literal_condition_drops() {
+        let inputs = [
+            "false ? 1 div 0 : 2",
+            "true ? 2 : 1 div 0",
+            "if (false) { 1 div 0; }",
+            "if (true) { } else { 1 div 0; }",
+            "false && 1 div 0 == 1",
+            "true || 1 div 0 == 1",
+            "1 ?? 1 div 0",
+            "while (false) { let x = 1 div 0; }",
+            "while (1 > 2) { 1 div 0; }",
+        ];
+        for input in inputs {
+            let (_, errors) = fold(input);
+            assert!(errors.is_empty(), "{}: {:?}", input, errors);
+        }
+    }
+}
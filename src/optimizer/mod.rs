@@ -0,0 +1,3 @@
+mod constant_folder;
+
+pub use constant_folder::*;
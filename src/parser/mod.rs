@@ -1,404 +1,827 @@
 mod precedence;
 
-use core::panic;
-
 use crate::{
-    diagnostics::Diagnostics,
+    diagnostics::{Diagnostics, ErrorKind},
     syntax::{
-        ExpressionSyntax::{BinaryExpressionSyntax, UnaryExpressionSyntax},
-        Keywords, LiteralToken,
+        ArrayLiteralExpressionSyntax, AssignmentExpressionSyntax, AttributeArgumentSyntax,
+        AttributeSyntax, AttributedStatementSyntax, BadExpressionSyntax, BinaryExpressionSyntax,
+        BlockStatementSyntax, BreakStatementSyntax, CallExpressionSyntax, CastExpressionSyntax,
+        CompilationUnitSyntax, CompoundAssignmentExpressionSyntax, ConditionalExpressionSyntax,
+        ContinueStatementSyntax, ElseBodySyntax, ElseClauseSyntax, ErrorStatementSyntax,
+        ExpressionId, ExpressionStatementSyntax, ExpressionSyntax, FunctionDeclarationSyntax,
+        IdentifierExpressionSyntax, IfStatementSyntax, IndexExpressionSyntax, InitializerSyntax,
+        Keywords, LiteralExpressionSyntax, LiteralToken, MemberAccessExpressionSyntax,
+        ObjectLiteralExpressionSyntax, ObjectPropertySyntax, ParameterSyntax,
+        ParenthesizedExpressionSyntax, ReturnStatementSyntax, SeparatedSyntaxList, StatementId,
+        StatementSyntax, SyntaxArena,
         SyntaxKind::{self, Keyword, Token},
-        SyntaxKindDescriptor, Tokens,
+        SyntaxToken, SyntaxTree, Tokens, TypeClauseSyntax, UnaryExpressionSyntax,
+        UpdateExpressionSyntax, VariableDeclarationStatementSyntax, WhileStatementSyntax,
     },
 };
 
 #[derive(Clone, Debug, PartialEq)]
 pub struct ParserError {
-    pub descriptor: SyntaxKindDescriptor,
+    pub descriptor: SyntaxToken,
     pub message: String,
 }
 pub struct Parser {
-    tokens: Vec<SyntaxKindDescriptor>,
+    tokens: Vec<SyntaxToken>,
     position: usize,
+    arena: SyntaxArena,
     diagnostics: Diagnostics,
+    /// Set once an error is reported in the current statement. Further errors
+    /// are dropped until the parser synchronizes, since they are almost always
+    /// consequences of the first one.
+    recovering: bool,
 }
 
 impl Parser {
-    pub fn new(tokens: Vec<SyntaxKindDescriptor>, input: String) -> Parser {
+    pub fn new(tokens: Vec<SyntaxToken>, input: String) -> Parser {
         Parser {
             tokens,
             position: 0,
+            arena: SyntaxArena::new(),
             diagnostics: Diagnostics::new(input),
+            recovering: false,
         }
     }
 
-    fn peek(&self, offset: usize) -> SyntaxKindDescriptor {
-        let position = self.position + offset;
-        if position >= self.tokens.len() {
-            return SyntaxKindDescriptor::new(self.position, Token(Tokens::EndOfFileToken));
-        }
-        self.tokens[position].clone()
+    /// Returns the token `offset` tokens ahead. The lexer always ends the
+    /// stream with an end of file token, which is returned for anything past
+    /// the end.
+    fn peek(&self, offset: usize) -> &SyntaxToken {
+        let position = (self.position + offset).min(self.tokens.len() - 1);
+        &self.tokens[position]
     }
 
-    fn current(&self) -> SyntaxKindDescriptor {
+    fn current(&self) -> &SyntaxToken {
         self.peek(0)
     }
 
+    fn is_at_end(&self) -> bool {
+        self.current().kind() == &Token(Tokens::EndOfFileToken)
+    }
+
     fn next(&mut self) {
         self.position += 1;
     }
 
-    fn next_token(&mut self) -> SyntaxKindDescriptor {
+    /// Consumes the current token. The end of file token is never consumed,
+    /// so it stays available for the compilation unit.
+    fn next_token(&mut self) -> SyntaxToken {
         let current = self.current().clone();
-        self.next();
+        if !self.is_at_end() {
+            self.next();
+        }
         current
     }
 
-    fn match_token(&mut self, descriptor: SyntaxKindDescriptor) -> SyntaxKindDescriptor {
-        let kind = descriptor.syntax();
-        if self.current().syntax().matches(&kind) {
+    /// Reports a syntax error at the current token, unless the parser is
+    /// already recovering from one. Unknown tokens were reported by the lexer,
+    /// so they only put the parser into recovery.
+    fn report(&mut self, error: ErrorKind) {
+        let already_reported = matches!(self.current().kind(), Token(Tokens::UnknownToken { .. }));
+        if !self.recovering && !already_reported {
+            self.diagnostics.add_error(error);
+        }
+        self.recovering = true;
+    }
+
+    /// Consumes a token of `kind`. If the current token is anything else, an
+    /// error is reported and a missing token is fabricated at the source
+    /// offset of the current token.
+    fn match_token(&mut self, kind: SyntaxKind) -> SyntaxToken {
+        if self.current().kind().matches(&kind) {
             return self.next_token();
         }
 
-        self.diagnostics
-            .add_error(crate::diagnostics::ErrorKind::UnexpectedToken {
-                expected: descriptor,
-                found: self.current(),
-                position: self.current().position(),
-            });
+        let position = self.current().position();
+        self.report(ErrorKind::UnexpectedToken {
+            expected: SyntaxToken::new(position, kind.clone()),
+            found: self.current().clone(),
+            position,
+        });
 
-        SyntaxKindDescriptor::new(self.position, kind)
+        SyntaxToken::new(position, kind)
     }
 
-    fn parse_expression(&mut self, parent_precedence: Option<usize>) -> SyntaxKindDescriptor {
-        let parent_precedence = parent_precedence.unwrap_or(0);
-        let current = self.current().clone();
-        let unary_precedence = precedence::unary_operator_precedence(current.syntax());
-        let mut left = if unary_precedence != 0 && unary_precedence >= parent_precedence {
-            let operator = self.next_token().syntax();
-            let operand = self.parse_expression(None);
-            SyntaxKindDescriptor::new(
-                current.position(),
-                SyntaxKind::Expression(UnaryExpressionSyntax {
-                    operand: Box::new(operand.syntax()),
-                    operator: Box::new(operator),
-                }),
+    fn match_identifier(&mut self) -> SyntaxToken {
+        if let Token(Tokens::IdentifierToken { value: _ }) = self.current().kind() {
+            return self.next_token();
+        }
+
+        self.match_token(Token(Tokens::IdentifierToken {
+            value: String::new(),
+        }))
+    }
+
+    fn parse_compilation_unit(&mut self) -> CompilationUnitSyntax {
+        let statements = self.parse_statements(None);
+        let end_of_file_token = self.match_token(Token(Tokens::EndOfFileToken));
+        CompilationUnitSyntax::new(&self.arena, statements, end_of_file_token)
+    }
+
+    /// Parses statements until `terminator` or the end of the input.
+    fn parse_statements(&mut self, terminator: Option<SyntaxKind>) -> Vec<StatementId> {
+        let mut statements = Vec::new();
+        while !self.is_at_end() && terminator.as_ref() != Some(self.current().kind()) {
+            let start = self.position;
+            self.recovering = false;
+            let statement = self.parse_statement();
+            let made_progress = self.position != start;
+            if made_progress {
+                statements.push(self.arena.alloc_statement(statement));
+            }
+
+            if self.recovering || !made_progress {
+                if let Some(error) = self.synchronize(made_progress) {
+                    statements.push(error);
+                }
+            }
+        }
+        statements
+    }
+
+    /// Panic mode recovery: skips tokens up to the next point where a
+    /// statement can start, which is after a `;`, before a `}` or before a
+    /// statement keyword. The skipped tokens are kept in an error statement so
+    /// the tree still covers the whole source. A statement that consumed
+    /// nothing always gives up its first token, so the parser cannot get stuck.
+    fn synchronize(&mut self, made_progress: bool) -> Option<StatementId> {
+        self.recovering = false;
+
+        // A statement that still reached its closing `;` or `}` left the
+        // parser at a statement boundary already.
+        let previous = self.tokens[..self.position]
+            .last()
+            .map(|token| token.kind());
+        if made_progress
+            && matches!(
+                previous,
+                Some(Token(Tokens::SemiColonToken) | Token(Tokens::CloseBraceToken))
             )
-        } else {
-            self.parse_primary_expression()
-        };
+        {
+            return None;
+        }
 
-        loop {
-            let precedence = precedence::binary_operator_precedence(self.current().syntax());
-            if precedence == 0 || precedence <= parent_precedence {
-                break;
+        let mut tokens = Vec::new();
+        if !made_progress && !self.is_at_end() {
+            tokens.push(self.next_token());
+        }
+
+        while !self.is_at_end() {
+            match self.current().kind() {
+                Token(Tokens::SemiColonToken) => {
+                    tokens.push(self.next_token());
+                    break;
+                }
+                Token(Tokens::CloseBraceToken)
+                | Token(Tokens::AtToken)
+                | Keyword(Keywords::LetKeyword)
+                | Keyword(Keywords::ConstKeyword)
+                | Keyword(Keywords::IfKeyword)
+                | Keyword(Keywords::WhileKeyword)
+                | Keyword(Keywords::FnKeyword)
+                | Keyword(Keywords::ReturnKeyword)
+                | Keyword(Keywords::ForKeyword)
+                | Keyword(Keywords::LoopKeyword)
+                | Keyword(Keywords::BreakKeyword)
+                | Keyword(Keywords::ContinueKeyword)
+                | Keyword(Keywords::MatchKeyword) => break,
+                _ => tokens.push(self.next_token()),
             }
+        }
 
-            let operator = self.next_token().syntax();
-            let right = self.parse_expression(Some(precedence));
-            left = SyntaxKindDescriptor::new(
-                current.position(),
-                SyntaxKind::Expression(BinaryExpressionSyntax {
-                    left: Box::new(left.syntax()),
-                    operator: Box::new(operator),
-                    right: Box::new(right.syntax()),
-                }),
-            );
+        if tokens.is_empty() {
+            return None;
         }
+        let statement = StatementSyntax::ErrorStatementSyntax(ErrorStatementSyntax::new(tokens));
+        Some(self.arena.alloc_statement(statement))
+    }
 
-        left
+    fn parse_statement(&mut self) -> StatementSyntax {
+        match self.current().kind() {
+            Token(Tokens::OpenBraceToken) => {
+                StatementSyntax::BlockStatementSyntax(self.parse_block_statement())
+            }
+            Keyword(Keywords::LetKeyword) | Keyword(Keywords::ConstKeyword) => {
+                self.parse_variable_declaration()
+            }
+            Keyword(Keywords::IfKeyword) => {
+                StatementSyntax::IfStatementSyntax(self.parse_if_statement())
+            }
+            Keyword(Keywords::FnKeyword) => self.parse_function_declaration(),
+            Keyword(Keywords::ReturnKeyword) => self.parse_return_statement(),
+            Keyword(Keywords::WhileKeyword) => self.parse_while_statement(),
+            Keyword(Keywords::BreakKeyword) => {
+                let keyword = self.next_token();
+                let semicolon = self.match_token(Token(Tokens::SemiColonToken));
+                StatementSyntax::BreakStatementSyntax(BreakStatementSyntax::new(keyword, semicolon))
+            }
+            Keyword(Keywords::ContinueKeyword) => {
+                let keyword = self.next_token();
+                let semicolon = self.match_token(Token(Tokens::SemiColonToken));
+                StatementSyntax::ContinueStatementSyntax(ContinueStatementSyntax::new(
+                    keyword, semicolon,
+                ))
+            }
+            Token(Tokens::AtToken) => self.parse_attributed_statement(),
+            _ => self.parse_expression_statement(),
+        }
     }
 
-    fn parse_primary_expression(&mut self) -> SyntaxKindDescriptor {
-        let current = self.current().clone();
-        let result = match current.syntax() {
-            Token(Tokens::OpenParenthesisToken) => {
-                let left = self.next_token();
-                let expression = self.parse_expression(None);
-                let right = self.match_token(SyntaxKindDescriptor::new(
-                    self.position,
-                    Token(Tokens::CloseParenthesisToken),
-                ));
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Expression(
-                        crate::syntax::ExpressionSyntax::ParenthesizedExpressionSyntax {
-                            open_parenthesis_token: Box::new(left.syntax()),
-                            expression: Box::new(expression.syntax()),
-                            close_parenthesis_token: Box::new(right.syntax()),
-                        },
-                    ),
-                )
+    fn parse_attributed_statement(&mut self) -> StatementSyntax {
+        let mut attributes = Vec::new();
+        while self.current().kind() == &Token(Tokens::AtToken) {
+            attributes.push(self.parse_attribute());
+        }
+        let statement = self.parse_statement();
+        let statement = self.arena.alloc_statement(statement);
+        StatementSyntax::AttributedStatementSyntax(AttributedStatementSyntax::new(
+            &self.arena,
+            attributes,
+            statement,
+        ))
+    }
+
+    fn parse_attribute(&mut self) -> AttributeSyntax {
+        let at_token = self.match_token(Token(Tokens::AtToken));
+        let identifier = self.match_identifier();
+        let open_parenthesis = self.match_token(Token(Tokens::OpenParenthesisToken));
+        let arguments = self.parse_separated_list(Tokens::CloseParenthesisToken, |parser| {
+            let mut tokens = vec![parser.match_identifier()];
+            while parser.current().kind() == &Token(Tokens::MinusToken) {
+                tokens.push(parser.next_token());
+                tokens.push(parser.match_identifier());
             }
-            Token(Tokens::LiteralToken {
-                value: LiteralToken::Int { value: _ },
-            }) => {
-                let number = self.next_token();
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Expression(
-                        crate::syntax::ExpressionSyntax::LiteralExpressionSyntax {
-                            expression: Box::new(number.syntax()),
-                        },
-                    ),
-                )
+            AttributeArgumentSyntax::new(tokens)
+        });
+        let close_parenthesis = self.match_token(Token(Tokens::CloseParenthesisToken));
+        AttributeSyntax::new(
+            at_token,
+            identifier,
+            open_parenthesis,
+            arguments,
+            close_parenthesis,
+        )
+    }
+
+    fn parse_block_statement(&mut self) -> BlockStatementSyntax {
+        let open_brace = self.match_token(Token(Tokens::OpenBraceToken));
+        let statements = self.parse_statements(Some(Token(Tokens::CloseBraceToken)));
+        let close_brace = self.match_token(Token(Tokens::CloseBraceToken));
+        BlockStatementSyntax::new(open_brace, statements, close_brace)
+    }
+
+    fn parse_expression_statement(&mut self) -> StatementSyntax {
+        let expression = self.parse_expression();
+        let semicolon = match self.current().kind() {
+            Token(Tokens::SemiColonToken) => Some(self.next_token()),
+            _ => None,
+        };
+        StatementSyntax::ExpressionStatementSyntax(ExpressionStatementSyntax::new(
+            &self.arena,
+            expression,
+            semicolon,
+        ))
+    }
+
+    fn parse_variable_declaration(&mut self) -> StatementSyntax {
+        let keyword = match self.current().kind() {
+            Keyword(Keywords::ConstKeyword) => self.next_token(),
+            _ => self.match_token(Keyword(Keywords::LetKeyword)),
+        };
+        let mut_keyword = match (keyword.kind(), self.current().kind()) {
+            (Keyword(Keywords::LetKeyword), Keyword(Keywords::MutKeyword)) => {
+                Some(self.next_token())
             }
-            Token(Tokens::LiteralToken {
-                value: LiteralToken::Float { value: _ },
-            }) => {
-                let number = self.next_token();
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Expression(
-                        crate::syntax::ExpressionSyntax::LiteralExpressionSyntax {
-                            expression: Box::new(number.syntax()),
-                        },
-                    ),
-                )
+            _ => None,
+        };
+        let identifier = self.match_identifier();
+        let type_clause = self.parse_optional_type_clause();
+        let initializer = match self.current().kind() {
+            Token(Tokens::SemiColonToken) => None,
+            _ => {
+                let equals_token = self.match_token(Token(Tokens::EqualsToken));
+                let expression = self.parse_expression();
+                Some(InitializerSyntax::new(
+                    &self.arena,
+                    equals_token,
+                    expression,
+                ))
             }
+        };
+        let semicolon = self.match_token(Token(Tokens::SemiColonToken));
+        StatementSyntax::VariableDeclarationStatementSyntax(
+            VariableDeclarationStatementSyntax::new(
+                keyword,
+                mut_keyword,
+                identifier,
+                type_clause,
+                initializer,
+                semicolon,
+            ),
+        )
+    }
 
-            Token(Tokens::LiteralToken {
-                value: LiteralToken::String { value: _ },
-            }) => {
-                let string = self.next_token();
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Expression(
-                        crate::syntax::ExpressionSyntax::LiteralExpressionSyntax {
-                            expression: Box::new(string.syntax()),
-                        },
-                    ),
-                )
+    /// Parses a `: type` or `: type?` annotation if the current token starts
+    /// one.
+    fn parse_optional_type_clause(&mut self) -> Option<TypeClauseSyntax> {
+        if self.current().kind() != &Token(Tokens::ColonToken) {
+            return None;
+        }
+        let colon_token = self.next_token();
+        let type_token = self.match_identifier();
+        let question_mark_token = match self.current().kind() {
+            Token(Tokens::QuestionMarkToken) => Some(self.next_token()),
+            _ => None,
+        };
+        Some(TypeClauseSyntax::new(
+            colon_token,
+            type_token,
+            question_mark_token,
+        ))
+    }
+
+    fn parse_function_declaration(&mut self) -> StatementSyntax {
+        let fn_keyword = self.match_token(Keyword(Keywords::FnKeyword));
+        let identifier = self.match_identifier();
+        let open_parenthesis = self.match_token(Token(Tokens::OpenParenthesisToken));
+        let parameters = self.parse_separated_list(Tokens::CloseParenthesisToken, |parser| {
+            let identifier = parser.match_identifier();
+            let type_clause = parser.parse_optional_type_clause();
+            ParameterSyntax::new(identifier, type_clause)
+        });
+        let close_parenthesis = self.match_token(Token(Tokens::CloseParenthesisToken));
+        let return_type = self.parse_optional_type_clause();
+        let body = self.parse_block_statement();
+        StatementSyntax::FunctionDeclarationSyntax(FunctionDeclarationSyntax::new(
+            fn_keyword,
+            identifier,
+            open_parenthesis,
+            parameters,
+            close_parenthesis,
+            return_type,
+            body,
+        ))
+    }
+
+    fn parse_return_statement(&mut self) -> StatementSyntax {
+        let return_keyword = self.match_token(Keyword(Keywords::ReturnKeyword));
+        let expression = match self.current().kind() {
+            Token(Tokens::SemiColonToken) => None,
+            _ => Some(self.parse_expression()),
+        };
+        let semicolon = self.match_token(Token(Tokens::SemiColonToken));
+        StatementSyntax::ReturnStatementSyntax(ReturnStatementSyntax::new(
+            return_keyword,
+            expression,
+            semicolon,
+        ))
+    }
+
+    fn parse_while_statement(&mut self) -> StatementSyntax {
+        let while_keyword = self.match_token(Keyword(Keywords::WhileKeyword));
+        let open_parenthesis = self.match_token(Token(Tokens::OpenParenthesisToken));
+        let condition = self.parse_expression();
+        let close_parenthesis = self.match_token(Token(Tokens::CloseParenthesisToken));
+        let body = self.parse_block_statement();
+        StatementSyntax::WhileStatementSyntax(WhileStatementSyntax::new(
+            while_keyword,
+            open_parenthesis,
+            condition,
+            close_parenthesis,
+            body,
+        ))
+    }
+
+    fn parse_if_statement(&mut self) -> IfStatementSyntax {
+        let keyword = self.match_token(Keyword(Keywords::IfKeyword));
+        let open_parenthesis = self.match_token(Token(Tokens::OpenParenthesisToken));
+        let condition = self.parse_expression();
+        let close_parenthesis = self.match_token(Token(Tokens::CloseParenthesisToken));
+        let body = self.parse_block_statement();
+
+        let else_clause = match self.current().kind() {
+            Keyword(Keywords::ElseKeyword) => {
+                let else_keyword = self.next_token();
+                let body = match self.current().kind() {
+                    Keyword(Keywords::IfKeyword) => {
+                        ElseBodySyntax::If(Box::new(self.parse_if_statement()))
+                    }
+                    _ => ElseBodySyntax::Block(self.parse_block_statement()),
+                };
+                Some(ElseClauseSyntax::new(else_keyword, body))
             }
+            _ => None,
+        };
 
-            Token(Tokens::LiteralToken {
-                value: LiteralToken::Bool { value: _ },
-            }) => {
-                let boolean = self.next_token();
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Expression(
-                        crate::syntax::ExpressionSyntax::LiteralExpressionSyntax {
-                            expression: Box::new(boolean.syntax()),
-                        },
+        IfStatementSyntax::new(
+            keyword,
+            open_parenthesis,
+            condition,
+            close_parenthesis,
+            body,
+            else_clause,
+        )
+    }
+
+    fn parse_expression(&mut self) -> ExpressionId {
+        self.parse_assignment_expression()
+    }
+
+    /// Assignment binds loosest and groups to the right, so `a = b = 3`
+    /// assigns 3 to `b` and then to `a`. The target is parsed as an ordinary
+    /// expression and then checked to be something that can be assigned to.
+    fn parse_assignment_expression(&mut self) -> ExpressionId {
+        let target = self.parse_binary_expression(0);
+        let binary_operator = compound_assignment_operator(self.current().kind());
+        if self.current().kind() != &Token(Tokens::EqualsToken) && binary_operator.is_none() {
+            return target;
+        }
+
+        self.check_assignable(target);
+        let operator_token = self.next_token();
+        let value = self.parse_assignment_expression();
+        let expression =
+            match binary_operator {
+                Some(binary_operator) => ExpressionSyntax::CompoundAssignmentExpressionSyntax(
+                    CompoundAssignmentExpressionSyntax::new(
+                        &self.arena,
+                        target,
+                        operator_token,
+                        binary_operator,
+                        value,
                     ),
-                )
-            }
+                ),
+                None => ExpressionSyntax::AssignmentExpressionSyntax(
+                    AssignmentExpressionSyntax::new(&self.arena, target, operator_token, value),
+                ),
+            };
+        self.arena.alloc_expression(expression)
+    }
 
-            Token(Tokens::LiteralToken { value: _ }) => {
-                let left = self.next_token();
-                let operator = self.next_token();
-                let right = self.parse_expression(None);
-
-                match operator.syntax() {
-                    Token(Tokens::PlusToken)
-                    | Token(Tokens::MinusToken)
-                    | Token(Tokens::StarToken)
-                    | Token(Tokens::SlashToken)
-                    | Token(Tokens::PercentToken) => SyntaxKindDescriptor::new(
-                        current.position(),
-                        SyntaxKind::Expression(BinaryExpressionSyntax {
-                            left: Box::new(left.syntax()),
-                            operator: Box::new(operator.syntax()),
-                            right: Box::new(right.syntax()),
-                        }),
+    fn check_assignable(&mut self, target: ExpressionId) {
+        if !self.arena[target].is_assignable() {
+            let position = self.arena[target].span().get_start();
+            self.report(ErrorKind::InvalidAssignmentTarget { position });
+        }
+    }
+
+    fn parse_binary_expression(&mut self, parent_precedence: usize) -> ExpressionId {
+        let unary_precedence = precedence::unary_operator_precedence(self.current().kind());
+        let mut left = if let Some(binary_operator) = update_operator(self.current().kind()) {
+            let operator_token = self.next_token();
+            let operand = self.parse_postfix_expression();
+            self.check_assignable(operand);
+            self.arena
+                .alloc_expression(ExpressionSyntax::UpdateExpressionSyntax(
+                    UpdateExpressionSyntax::new(
+                        &self.arena,
+                        operator_token,
+                        binary_operator,
+                        operand,
+                        true,
                     ),
-                    Token(Tokens::EqualsToken) => SyntaxKindDescriptor::new(
-                        current.position(),
-                        SyntaxKind::Statement(
-                            crate::syntax::StatementSyntax::VariableAssignmentStatementSyntax {
-                                identifier: Box::new(left.syntax()),
-                                equals_token: Box::new(operator.syntax()),
-                                expression: Box::new(right.syntax()),
-                            },
+                ))
+        } else if unary_precedence != 0 && unary_precedence >= parent_precedence {
+            let operator = self.next_token();
+            let operand = self.parse_binary_expression(unary_precedence);
+            self.arena
+                .alloc_expression(ExpressionSyntax::UnaryExpressionSyntax(
+                    UnaryExpressionSyntax::new(&self.arena, operator, operand),
+                ))
+        } else {
+            self.parse_postfix_expression()
+        };
+
+        loop {
+            let precedence = precedence::binary_operator_precedence(self.current().kind());
+            if precedence == 0 || precedence <= parent_precedence {
+                break;
+            }
+
+            if self.current().kind() == &Token(Tokens::QuestionMarkToken) {
+                left = self.parse_conditional_expression(left, precedence);
+                continue;
+            }
+            if self.current().kind() == &Keyword(Keywords::AsKeyword) {
+                left = self.parse_cast_expression(left);
+                continue;
+            }
+
+            let operator = self.next_token();
+            let right = self.parse_binary_expression(precedence);
+            left = self
+                .arena
+                .alloc_expression(ExpressionSyntax::BinaryExpressionSyntax(
+                    BinaryExpressionSyntax::new(&self.arena, left, operator, right),
+                ));
+        }
+
+        left
+    }
+
+    /// Parses the `? when_true : when_false` that follows `condition`. The
+    /// middle is a full expression, as it is closed by the `:`. The last part
+    /// is parsed one level looser than `?`, which makes `a ? b : c ? d : e`
+    /// group as `a ? b : (c ? d : e)`.
+    fn parse_conditional_expression(
+        &mut self,
+        condition: ExpressionId,
+        precedence: usize,
+    ) -> ExpressionId {
+        let question_mark_token = self.next_token();
+        let when_true = self.parse_expression();
+        let colon_token = self.match_token(Token(Tokens::ColonToken));
+        let when_false = self.parse_binary_expression(precedence - 1);
+        self.arena
+            .alloc_expression(ExpressionSyntax::ConditionalExpressionSyntax(
+                ConditionalExpressionSyntax::new(
+                    &self.arena,
+                    condition,
+                    question_mark_token,
+                    when_true,
+                    colon_token,
+                    when_false,
+                ),
+            ))
+    }
+
+    /// Parses the `as type` that follows `expression`. The type is a plain
+    /// name, checked when the cast is evaluated.
+    fn parse_cast_expression(&mut self, expression: ExpressionId) -> ExpressionId {
+        let as_keyword = self.next_token();
+        let type_token = self.match_identifier();
+        self.arena
+            .alloc_expression(ExpressionSyntax::CastExpressionSyntax(
+                CastExpressionSyntax::new(&self.arena, expression, as_keyword, type_token),
+            ))
+    }
+
+    /// Parses a primary expression followed by any number of `[index]`,
+    /// `.name`, `++` and `--` suffixes. `?[index]` and `?.name` are the
+    /// null-conditional forms of the first two.
+    fn parse_postfix_expression(&mut self) -> ExpressionId {
+        let mut expression = self.parse_primary_expression();
+        loop {
+            let postfix = match self.current().kind() {
+                Token(Tokens::OpenBracketToken | Tokens::QuestionMarkOpenBracketToken) => {
+                    let open_bracket_token = self.next_token();
+                    let index = self.parse_expression();
+                    let close_bracket_token = self.match_token(Token(Tokens::CloseBracketToken));
+                    ExpressionSyntax::IndexExpressionSyntax(IndexExpressionSyntax::new(
+                        &self.arena,
+                        expression,
+                        open_bracket_token,
+                        index,
+                        close_bracket_token,
+                    ))
+                }
+                Token(Tokens::DotToken | Tokens::QuestionMarkDotToken) => {
+                    let dot_token = self.next_token();
+                    let name_token = self.match_identifier();
+                    ExpressionSyntax::MemberAccessExpressionSyntax(
+                        MemberAccessExpressionSyntax::new(
+                            &self.arena,
+                            expression,
+                            dot_token,
+                            name_token,
                         ),
-                    ),
-                    Token(Tokens::AmpersandAmpersandToken) => {
-                        let right = self.parse_expression(None);
-                        SyntaxKindDescriptor::new(
-                            current.position(),
-                            SyntaxKind::Expression(BinaryExpressionSyntax {
-                                left: Box::new(left.syntax()),
-                                operator: Box::new(operator.syntax()),
-                                right: Box::new(right.syntax()),
-                            }),
-                        )
+                    )
+                }
+                kind => match update_operator(kind) {
+                    Some(binary_operator) => {
+                        self.check_assignable(expression);
+                        let operator_token = self.next_token();
+                        ExpressionSyntax::UpdateExpressionSyntax(UpdateExpressionSyntax::new(
+                            &self.arena,
+                            operator_token,
+                            binary_operator,
+                            expression,
+                            false,
+                        ))
                     }
+                    None => break,
+                },
+            };
+            expression = self.arena.alloc_expression(postfix);
+        }
+        expression
+    }
 
-                    _ => {
-                        self.diagnostics.add_error(
-                            crate::diagnostics::ErrorKind::UnexpectedToken {
-                                expected: SyntaxKindDescriptor::new(
-                                    self.position,
-                                    Token(Tokens::BinaryOperatorToken),
-                                ),
-                                position: operator.position(),
-                                found: operator,
-                            },
-                        );
-                        SyntaxKindDescriptor::new(
-                            current.position(),
-                            SyntaxKind::Token(Tokens::BadToken {
-                                value: "Bad Token".to_string(),
-                            }),
-                        )
-                    }
-                }
+    /// Parses items separated by commas up to `close`, which is left for the
+    /// caller to match. A trailing comma is allowed.
+    fn parse_separated_list<T>(
+        &mut self,
+        close: Tokens,
+        mut parse_item: impl FnMut(&mut Self) -> T,
+    ) -> SeparatedSyntaxList<T> {
+        let mut nodes = Vec::new();
+        let mut separators = Vec::new();
+        while !self.is_at_end() && self.current().kind() != &Token(close.clone()) {
+            let start = self.position;
+            nodes.push(parse_item(self));
+            if self.current().kind() != &Token(Tokens::CommaToken) || self.position == start {
+                break;
             }
+            separators.push(self.next_token());
+        }
+        SeparatedSyntaxList::new(nodes, separators)
+    }
 
-            Token(Tokens::IdentifierToken { value }) => {
-                let identifier = self.next_token();
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Expression(
-                        crate::syntax::ExpressionSyntax::LiteralExpressionSyntax {
-                            expression: Box::new(identifier.syntax()),
-                        },
-                    ),
-                )
+    fn parse_call_expression(&mut self) -> ExpressionSyntax {
+        let identifier = self.match_identifier();
+        let open_parenthesis_token = self.match_token(Token(Tokens::OpenParenthesisToken));
+        let arguments = self.parse_separated_list(Tokens::CloseParenthesisToken, |parser| {
+            parser.parse_expression()
+        });
+        let close_parenthesis_token = self.match_token(Token(Tokens::CloseParenthesisToken));
+        ExpressionSyntax::CallExpressionSyntax(CallExpressionSyntax::new(
+            identifier,
+            open_parenthesis_token,
+            arguments,
+            close_parenthesis_token,
+        ))
+    }
+
+    fn parse_array_literal(&mut self) -> ExpressionSyntax {
+        let open_bracket_token = self.match_token(Token(Tokens::OpenBracketToken));
+        let elements = self.parse_separated_list(Tokens::CloseBracketToken, |parser| {
+            parser.parse_expression()
+        });
+        let close_bracket_token = self.match_token(Token(Tokens::CloseBracketToken));
+        ExpressionSyntax::ArrayLiteralExpressionSyntax(ArrayLiteralExpressionSyntax::new(
+            open_bracket_token,
+            elements,
+            close_bracket_token,
+        ))
+    }
+
+    fn parse_object_literal(&mut self) -> ExpressionSyntax {
+        let open_brace_token = self.match_token(Token(Tokens::OpenBraceToken));
+        let properties = self.parse_separated_list(Tokens::CloseBraceToken, |parser| {
+            parser.parse_object_property()
+        });
+        let close_brace_token = self.match_token(Token(Tokens::CloseBraceToken));
+        ExpressionSyntax::ObjectLiteralExpressionSyntax(ObjectLiteralExpressionSyntax::new(
+            open_brace_token,
+            properties,
+            close_brace_token,
+        ))
+    }
+
+    fn parse_object_property(&mut self) -> ObjectPropertySyntax {
+        let name_token = match self.current().kind() {
+            Token(Tokens::LiteralToken {
+                value: LiteralToken::String { .. },
+            }) => self.next_token(),
+            _ => self.match_identifier(),
+        };
+        let colon_token = self.match_token(Token(Tokens::ColonToken));
+        let value = self.parse_expression();
+        ObjectPropertySyntax::new(&self.arena, name_token, colon_token, value)
+    }
+
+    fn parse_primary_expression(&mut self) -> ExpressionId {
+        let expression = match self.current().kind() {
+            Token(Tokens::OpenParenthesisToken) => {
+                let left = self.next_token();
+                let expression = self.parse_expression();
+                let right = self.match_token(Token(Tokens::CloseParenthesisToken));
+                ExpressionSyntax::ParenthesizedExpressionSyntax(ParenthesizedExpressionSyntax::new(
+                    left, expression, right,
+                ))
             }
 
-            Token(Tokens::OpenBraceToken) => {
-                let statements = self.parse_primary_expression();
-                let close_bracket = self.match_token(SyntaxKindDescriptor::new(
-                    self.position,
-                    Token(Tokens::CloseBraceToken),
-                ));
+            Token(Tokens::LiteralToken { value }) => {
+                let value = value.clone();
+                let literal_token = self.next_token();
+                ExpressionSyntax::LiteralExpressionSyntax(LiteralExpressionSyntax::new(
+                    literal_token,
+                    value,
+                ))
+            }
 
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Statement(crate::syntax::StatementSyntax::BlockStatementSyntax {
-                        open_brace: Box::new(current.syntax()),
-                        statements: Box::new(statements.syntax()),
-                        close_brace: Box::new(close_bracket.syntax()),
-                    }),
-                )
+            Token(Tokens::IdentifierToken { value: _ })
+                if self.peek(1).kind() == &Token(Tokens::OpenParenthesisToken) =>
+            {
+                self.parse_call_expression()
             }
 
-            Keyword(Keywords::LetKeyword) => {
-                let let_keyword = self.next_token();
+            Token(Tokens::IdentifierToken { value: _ }) => {
                 let identifier = self.next_token();
-                let identifier_syntax = identifier.syntax();
-
-                match identifier_syntax {
-                    SyntaxKind::Token(Tokens::IdentifierToken { value: _ }) => {
-                        let equals_token = self.match_token(SyntaxKindDescriptor::new(
-                            self.position,
-                            Token(Tokens::EqualsToken),
-                        ));
-                        let expression = self.parse_expression(None);
-                        let semicolon_token = self.match_token(SyntaxKindDescriptor::new(
-                            self.position,
-                            Token(Tokens::SemiColonToken),
-                        ));
-                        SyntaxKindDescriptor::new(
-                            current.position(),
-                            SyntaxKind::Statement(crate::syntax::StatementSyntax::VariableDeclarationStatementSyntax {
-                                keyword: Box::new(let_keyword.syntax()),
-                                identifier: Box::new(identifier.syntax()),
-                                equals_token: Box::new(equals_token.syntax()),
-                                expression: Box::new(expression.syntax()),
-                                semicolon: Box::new(semicolon_token.syntax()),
-                            }),
-                        )
-                    }
-                    _ => {
-                        self.diagnostics.add_error(
-                            crate::diagnostics::ErrorKind::UnexpectedToken {
-                                expected: SyntaxKindDescriptor::new(
-                                    identifier.position(),
-                                    Token(Tokens::LiteralToken {
-                                        value: crate::syntax::LiteralToken::String {
-                                            value: "id".to_string(),
-                                        },
-                                    }),
-                                ),
-                                position: identifier.position(),
-                                found: identifier,
-                            },
-                        );
-
-                        SyntaxKindDescriptor::new(
-                            current.position(),
-                            SyntaxKind::Token(Tokens::BadToken {
-                                value: "Bad Token".to_string(),
-                            }),
-                        )
-                    }
-                }
-            }
-            Keyword(Keywords::IfKeyword) => {
-                let if_keyword = self.next_token();
-                let open_parenthesis = self.match_token(SyntaxKindDescriptor::new(
-                    self.position,
-                    Token(Tokens::OpenParenthesisToken),
-                ));
-                let condition = self.parse_expression(None);
-                let close_parenthesis = self.match_token(SyntaxKindDescriptor::new(
-                    self.position,
-                    Token(Tokens::CloseParenthesisToken),
-                ));
-                let open_braces = self.match_token(SyntaxKindDescriptor::new(
-                    self.position,
-                    Token(Tokens::OpenBraceToken),
-                ));
-                let body = self.parse_expression(None);
-                let close_braces = self.match_token(SyntaxKindDescriptor::new(
-                    self.position,
-                    Token(Tokens::CloseBraceToken),
-                ));
-                SyntaxKindDescriptor::new(
-                    current.position(),
-                    SyntaxKind::Statement(crate::syntax::StatementSyntax::IfStatementSyntax {
-                        keyword: Box::new(if_keyword.syntax()),
-                        open_brace: Box::new(open_braces.syntax()),
-                        close_brace: Box::new(close_braces.syntax()),
-                        open_parenthesis: Box::new(open_parenthesis.syntax()),
-                        close_parenthesis: Box::new(close_parenthesis.syntax()),
-                        condition: Box::new(condition.syntax()),
-                        body: Box::new(body.syntax()),
-                    }),
-                )
+                ExpressionSyntax::IdentifierExpressionSyntax(IdentifierExpressionSyntax::new(
+                    identifier,
+                ))
             }
 
+            Token(Tokens::OpenBracketToken) => self.parse_array_literal(),
+
+            Token(Tokens::OpenBraceToken) => self.parse_object_literal(),
+
             _ => {
-                let descriptor = self.current();
-                let position = descriptor.position();
-                self.diagnostics
-                    .add_error(crate::diagnostics::ErrorKind::UnexpectedToken {
-                        expected: SyntaxKindDescriptor::new(
-                            self.position,
-                            Token(Tokens::LiteralToken {
-                                value: crate::syntax::LiteralToken::String {
-                                    value: "identifier".to_string(),
-                                },
-                            }),
-                        ),
-                        position,
-                        found: descriptor,
-                    });
+                let found = self.current().clone();
+                let position = found.position();
+                self.report(ErrorKind::ExpectedExpression { found, position });
 
-                SyntaxKindDescriptor::new(
+                ExpressionSyntax::BadExpressionSyntax(BadExpressionSyntax::new(
                     position,
-                    SyntaxKind::Token(Tokens::BadToken {
-                        value: "Bad Token".to_string(),
-                    }),
-                )
+                    Vec::new(),
+                ))
             }
         };
-        result
+        self.arena.alloc_expression(expression)
     }
 
-    pub fn parse(&mut self) -> SyntaxKindDescriptor {
-        let expression = self.parse_expression(None);
-        self.match_token(SyntaxKindDescriptor::new(
-            self.position,
-            Token(Tokens::EndOfFileToken),
-        ));
-        expression
+    pub fn parse(&mut self) -> SyntaxTree {
+        let root = self.parse_compilation_unit();
+        SyntaxTree::new(std::mem::take(&mut self.arena), root)
     }
 
     pub fn diagnostics(&self) -> Diagnostics {
         self.diagnostics.clone()
     }
 }
+
+/// The binary operator a compound assignment token stands for, so that
+/// `a += b` can be run as `a = a + b`.
+fn compound_assignment_operator(kind: &SyntaxKind) -> Option<Tokens> {
+    match kind {
+        Token(Tokens::PlusEqualsToken) => Some(Tokens::PlusToken),
+        Token(Tokens::MinusEqualsToken) => Some(Tokens::MinusToken),
+        Token(Tokens::StarEqualsToken) => Some(Tokens::StarToken),
+        Token(Tokens::SlashEqualsToken) => Some(Tokens::SlashToken),
+        Token(Tokens::PercentEqualsToken) => Some(Tokens::PercentToken),
+        Token(Tokens::AmpersandAmpersandEqualsToken) => Some(Tokens::AmpersandAmpersandToken),
+        Token(Tokens::PipePipeEqualsToken) => Some(Tokens::PipePipeToken),
+        Token(Tokens::AmpersandEqualsToken) => Some(Tokens::AmpersandToken),
+        Token(Tokens::PipeEqualsToken) => Some(Tokens::PipeToken),
+        Token(Tokens::CaretEqualsToken) => Some(Tokens::CaretToken),
+        Token(Tokens::LessThanLessThanEqualsToken) => Some(Tokens::LessThanLessThanToken),
+        Token(Tokens::GreaterThanGreaterThanEqualsToken) => {
+            Some(Tokens::GreaterThanGreaterThanToken)
+        }
+        _ => None,
+    }
+}
+
+/// The binary operator `++` or `--` applies with 1.
+fn update_operator(kind: &SyntaxKind) -> Option<Tokens> {
+    match kind {
+        Token(Tokens::PlusPlusToken) => Some(Tokens::PlusToken),
+        Token(Tokens::MinusMinusToken) => Some(Tokens::MinusToken),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::lexer::{source_text::SourceText, Lexer};
+
+    fn parse(text: &str) -> SyntaxTree {
+        let tokens = Lexer::new(SourceText::new(text.to_string())).lex();
+        Parser::new(tokens, text.to_string()).parse()
+    }
+
+    #[test]
+    fn full_text_round_trips() {
+        let inputs = [
+            "",
+            "1 + 2 * 3",
+            "  let x = 5;  // trailing\n\t{ x }\n",
+            "/// Doc.\nlet y = /* nested /* block */ */ (2);\n",
+            "let s = \"unterminated",
+            "1 + ) * let",
+        ];
+        for input in inputs {
+            assert_eq!(parse(input).full_text(), input);
+        }
+    }
+
+    /// The operator at the root of the only statement's expression.
+    fn root_operator(text: &str) -> String {
+        let tree = parse(text);
+        let StatementSyntax::ExpressionStatementSyntax(statement) =
+            &tree.arena()[tree.root().statements[0]]
+        else {
+            panic!("{} is not an expression statement", text);
+        };
+        let ExpressionSyntax::BinaryExpressionSyntax(expression) =
+            &tree.arena()[statement.expression]
+        else {
+            panic!("{} is not a binary expression", text);
+        };
+        expression.operator.text().to_string()
+    }
+
+    #[test]
+    fn comparison_precedence() {
+        assert_eq!(root_operator("1 < 2 == 2 > 1"), "==");
+        assert_eq!(root_operator("a | b <= c"), "<=");
+        assert_eq!(root_operator("a < b && c >= d"), "&&");
+        assert_eq!(root_operator("x == y ?? z"), "??");
+    }
+}
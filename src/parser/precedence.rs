@@ -1,32 +1,59 @@
-use crate::{
-    syntax::{
-        SyntaxKind::{self, Token},
-        Tokens
-    },
+use crate::syntax::{
+    Keywords,
+    SyntaxKind::{self, Keyword, Token},
+    Tokens,
 };
 
-pub fn binary_operator_precedence(kind: SyntaxKind) -> usize {
+/// Higher binds tighter. As in Rust, the bitwise operators bind tighter than
+/// the comparisons and looser than arithmetic, so `a + b & mask` masks the
+/// sum. `<` and the other orderings bind tighter than `==` and `!=`, as in C.
+/// `??` binds looser than `||`, as in C#. The conditional `?` binds loosest
+/// and is right-associative; the parser reads its `: else` part itself. `as`
+/// binds tighter than any binary operator but looser than the unary ones, so
+/// `-x as float` converts `-x`.
+pub fn binary_operator_precedence(kind: &SyntaxKind) -> usize {
     match kind {
-        Token(Tokens::PlusToken) => 1,
-        Token(Tokens::MinusToken) => 1,
+        Keyword(Keywords::AsKeyword) => 13,
 
-        Token(Tokens::AmpersandAmpersandToken) => 2,
-        Token(Tokens::PipePipeToken) => 2,
+        Token(Tokens::StarToken) => 12,
+        Token(Tokens::SlashToken) => 12,
+        Token(Tokens::PercentToken) => 12,
+        Keyword(Keywords::DivKeyword) => 12,
 
-        Token(Tokens::StarToken) => 3,
-        Token(Tokens::SlashToken) => 3,
+        Token(Tokens::PlusToken) => 11,
+        Token(Tokens::MinusToken) => 11,
 
-        Token(Tokens::PercentToken) => 4,
-        Token(Tokens::BangEqualsToken) => 4,
+        Token(Tokens::LessThanLessThanToken) => 10,
+        Token(Tokens::GreaterThanGreaterThanToken) => 10,
+
+        Token(Tokens::AmpersandToken) => 9,
+        Token(Tokens::CaretToken) => 8,
+        Token(Tokens::PipeToken) => 7,
+
+        Token(Tokens::LessThanToken) => 6,
+        Token(Tokens::LessThanEqualsToken) => 6,
+        Token(Tokens::GreaterThanToken) => 6,
+        Token(Tokens::GreaterThanEqualsToken) => 6,
+
+        Token(Tokens::EqualsEqualsToken) => 5,
+        Token(Tokens::BangEqualsToken) => 5,
+
+        Token(Tokens::AmpersandAmpersandToken) => 4,
+        Token(Tokens::PipePipeToken) => 3,
+
+        Token(Tokens::QuestionMarkQuestionMarkToken) => 2,
+
+        Token(Tokens::QuestionMarkToken) => 1,
         _ => 0,
     }
 }
 
-pub fn unary_operator_precedence(kind: SyntaxKind) -> usize {
+pub fn unary_operator_precedence(kind: &SyntaxKind) -> usize {
     match kind {
-        Token(Tokens::BangToken) => 6,
-        Token(Tokens::PlusToken) => 5,
-        Token(Tokens::MinusToken) => 5,
+        Token(Tokens::BangToken) => 14,
+        Token(Tokens::TildeToken) => 14,
+        Token(Tokens::PlusToken) => 14,
+        Token(Tokens::MinusToken) => 14,
         _ => 0,
     }
 }
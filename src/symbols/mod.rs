@@ -0,0 +1,191 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+static NEXT_VARIABLE_ID: AtomicUsize = AtomicUsize::new(0);
+static NEXT_FUNCTION_ID: AtomicUsize = AtomicUsize::new(0);
+
+/// A declared variable. Every declaration makes a new symbol, so a variable
+/// that shadows another of the same name is still a different variable.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct VariableSymbol {
+    name: String,
+    type_symbol: TypeSymbol,
+    kind: VariableKind,
+    id: usize,
+}
+
+impl VariableSymbol {
+    pub fn new(name: &str, type_symbol: TypeSymbol, kind: VariableKind) -> Self {
+        Self {
+            name: name.to_string(),
+            type_symbol,
+            kind,
+            id: NEXT_VARIABLE_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn type_symbol(&self) -> TypeSymbol {
+        self.type_symbol
+    }
+
+    pub fn kind(&self) -> VariableKind {
+        self.kind
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        self.kind == VariableKind::Mutable
+    }
+}
+
+/// Whether and how a variable can change after its declaration.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VariableKind {
+    /// `let` and parameters: only ever holds the value it starts with.
+    Immutable,
+    /// `let mut`: can be assigned again.
+    Mutable,
+    /// `const`: immutable, starts with a value known before anything runs
+    /// and can never be shadowed.
+    Constant,
+}
+
+/// A declared function. Like variables, a function declared again in a later
+/// input is a new symbol that shadows the earlier one.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FunctionSymbol {
+    name: String,
+    parameters: Vec<VariableSymbol>,
+    return_type: TypeSymbol,
+    id: usize,
+}
+
+impl FunctionSymbol {
+    pub fn new(name: &str, parameters: Vec<VariableSymbol>, return_type: TypeSymbol) -> Self {
+        Self {
+            name: name.to_string(),
+            parameters,
+            return_type,
+            id: NEXT_FUNCTION_ID.fetch_add(1, Ordering::Relaxed),
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn parameters(&self) -> &[VariableSymbol] {
+        &self.parameters
+    }
+
+    pub fn return_type(&self) -> TypeSymbol {
+        self.return_type
+    }
+}
+
+/// The static type of an expression, as inferred by the binder or written in
+/// an annotation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum TypeSymbol {
+    /// Ints of any size; a big int is only how a large int is stored.
+    Int,
+    Float,
+    Decimal,
+    String,
+    Char,
+    Bool,
+    Array,
+    Object,
+    Null,
+    /// A value whose type is only known at runtime, such as an array
+    /// element. Operations on it are checked when they run.
+    Any,
+    /// The type of an expression that could not be bound. Its error has
+    /// already been reported, so nothing using it reports another.
+    Error,
+    /// `int?` and the like: the type, or null. Made with `nullable`.
+    Nullable(&'static TypeSymbol),
+}
+
+impl TypeSymbol {
+    pub fn name(self) -> &'static str {
+        match self {
+            TypeSymbol::Int => "int",
+            TypeSymbol::Float => "float",
+            TypeSymbol::Decimal => "decimal",
+            TypeSymbol::String => "string",
+            TypeSymbol::Char => "char",
+            TypeSymbol::Bool => "bool",
+            TypeSymbol::Array => "array",
+            TypeSymbol::Object => "object",
+            TypeSymbol::Null => "null",
+            TypeSymbol::Any => "any",
+            TypeSymbol::Error => "?",
+            TypeSymbol::Nullable(inner) => match inner {
+                TypeSymbol::Int => "int?",
+                TypeSymbol::Float => "float?",
+                TypeSymbol::Decimal => "decimal?",
+                TypeSymbol::String => "string?",
+                TypeSymbol::Char => "char?",
+                TypeSymbol::Bool => "bool?",
+                TypeSymbol::Array => "array?",
+                TypeSymbol::Object => "object?",
+                inner => unreachable!("{:?} is never made nullable", inner),
+            },
+        }
+    }
+
+    /// The type that also allows null. `null`, `any` and types that already
+    /// allow null stay as they are.
+    pub fn nullable(self) -> TypeSymbol {
+        match self {
+            TypeSymbol::Int => TypeSymbol::Nullable(&TypeSymbol::Int),
+            TypeSymbol::Float => TypeSymbol::Nullable(&TypeSymbol::Float),
+            TypeSymbol::Decimal => TypeSymbol::Nullable(&TypeSymbol::Decimal),
+            TypeSymbol::String => TypeSymbol::Nullable(&TypeSymbol::String),
+            TypeSymbol::Char => TypeSymbol::Nullable(&TypeSymbol::Char),
+            TypeSymbol::Bool => TypeSymbol::Nullable(&TypeSymbol::Bool),
+            TypeSymbol::Array => TypeSymbol::Nullable(&TypeSymbol::Array),
+            TypeSymbol::Object => TypeSymbol::Nullable(&TypeSymbol::Object),
+            TypeSymbol::Null | TypeSymbol::Any | TypeSymbol::Error | TypeSymbol::Nullable(_) => {
+                self
+            }
+        }
+    }
+
+    /// The type a name written in the source refers to.
+    pub fn lookup(name: &str) -> Option<TypeSymbol> {
+        match name {
+            "int" => Some(TypeSymbol::Int),
+            "float" => Some(TypeSymbol::Float),
+            "decimal" => Some(TypeSymbol::Decimal),
+            "string" => Some(TypeSymbol::String),
+            "char" => Some(TypeSymbol::Char),
+            "bool" => Some(TypeSymbol::Bool),
+            "array" => Some(TypeSymbol::Array),
+            "object" => Some(TypeSymbol::Object),
+            "any" => Some(TypeSymbol::Any),
+            _ => None,
+        }
+    }
+
+    pub fn is_numeric(self) -> bool {
+        matches!(
+            self,
+            TypeSymbol::Int | TypeSymbol::Float | TypeSymbol::Decimal
+        )
+    }
+
+    /// Whether a value of type `from` may be stored where this type is
+    /// expected. There are no implicit conversions, so an int is not a
+    /// float; `any` on either side is checked at runtime instead. A nullable
+    /// type also accepts null and its non-null type.
+    pub fn accepts(self, from: TypeSymbol) -> bool {
+        self == from
+            || matches!(self, TypeSymbol::Any | TypeSymbol::Error)
+            || matches!(from, TypeSymbol::Any | TypeSymbol::Error)
+            || matches!(self, TypeSymbol::Nullable(inner) if from == TypeSymbol::Null || from == *inner)
+    }
+}
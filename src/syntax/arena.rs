@@ -0,0 +1,60 @@
+use std::ops::Index;
+
+use super::{ExpressionSyntax, StatementSyntax};
+
+/// Refers to an expression stored in a `SyntaxArena`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ExpressionId(usize);
+
+/// Refers to a statement stored in a `SyntaxArena`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct StatementId(usize);
+
+/// Owns every expression and statement of a syntax tree. Nodes point at
+/// their children by id, so the tree can be walked by reference and never
+/// needs to be cloned to be evaluated.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct SyntaxArena {
+    expressions: Vec<ExpressionSyntax>,
+    statements: Vec<StatementSyntax>,
+}
+
+impl SyntaxArena {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn alloc_expression(&mut self, expression: ExpressionSyntax) -> ExpressionId {
+        self.expressions.push(expression);
+        ExpressionId(self.expressions.len() - 1)
+    }
+
+    pub fn alloc_statement(&mut self, statement: StatementSyntax) -> StatementId {
+        self.statements.push(statement);
+        StatementId(self.statements.len() - 1)
+    }
+
+    pub fn expression(&self, id: ExpressionId) -> &ExpressionSyntax {
+        &self.expressions[id.0]
+    }
+
+    pub fn statement(&self, id: StatementId) -> &StatementSyntax {
+        &self.statements[id.0]
+    }
+}
+
+impl Index<ExpressionId> for SyntaxArena {
+    type Output = ExpressionSyntax;
+
+    fn index(&self, id: ExpressionId) -> &Self::Output {
+        self.expression(id)
+    }
+}
+
+impl Index<StatementId> for SyntaxArena {
+    type Output = StatementSyntax;
+
+    fn index(&self, id: StatementId) -> &Self::Output {
+        self.statement(id)
+    }
+}
@@ -0,0 +1,534 @@
+use crate::lexer::source_text::TextSpan;
+
+use super::{
+    ExpressionId, LiteralToken, SeparatedSyntaxList, SyntaxArena, SyntaxKind, SyntaxToken, Tokens,
+};
+
+/// Span covering everything from the start of `first` to the end of `last`.
+pub(crate) fn span_between(first: TextSpan, last: TextSpan) -> TextSpan {
+    let end = last.get_end().max(first.get_start());
+    TextSpan::from_bounds(first.get_start(), end)
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ExpressionSyntax {
+    BinaryExpressionSyntax(BinaryExpressionSyntax),
+    ParenthesizedExpressionSyntax(ParenthesizedExpressionSyntax),
+    UnaryExpressionSyntax(UnaryExpressionSyntax),
+    LiteralExpressionSyntax(LiteralExpressionSyntax),
+    IdentifierExpressionSyntax(IdentifierExpressionSyntax),
+    AssignmentExpressionSyntax(AssignmentExpressionSyntax),
+    CompoundAssignmentExpressionSyntax(CompoundAssignmentExpressionSyntax),
+    UpdateExpressionSyntax(UpdateExpressionSyntax),
+    ConditionalExpressionSyntax(ConditionalExpressionSyntax),
+    IndexExpressionSyntax(IndexExpressionSyntax),
+    MemberAccessExpressionSyntax(MemberAccessExpressionSyntax),
+    CastExpressionSyntax(CastExpressionSyntax),
+    CallExpressionSyntax(CallExpressionSyntax),
+    ArrayLiteralExpressionSyntax(ArrayLiteralExpressionSyntax),
+    ObjectLiteralExpressionSyntax(ObjectLiteralExpressionSyntax),
+    BadExpressionSyntax(BadExpressionSyntax),
+}
+
+impl ExpressionSyntax {
+    pub fn span(&self) -> TextSpan {
+        match self {
+            ExpressionSyntax::BinaryExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::ParenthesizedExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::UnaryExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::LiteralExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::IdentifierExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::AssignmentExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::CompoundAssignmentExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::UpdateExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::ConditionalExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::IndexExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::MemberAccessExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::CastExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::CallExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::ArrayLiteralExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::ObjectLiteralExpressionSyntax(expression) => expression.span,
+            ExpressionSyntax::BadExpressionSyntax(expression) => expression.span,
+        }
+    }
+
+    /// Whether the expression names a place a value can be stored in, and so
+    /// may appear on the left of an assignment.
+    pub fn is_assignable(&self) -> bool {
+        matches!(
+            self,
+            ExpressionSyntax::IdentifierExpressionSyntax(_)
+                | ExpressionSyntax::IndexExpressionSyntax(_)
+                | ExpressionSyntax::MemberAccessExpressionSyntax(_)
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BinaryExpressionSyntax {
+    pub left: ExpressionId,
+    pub operator: SyntaxToken,
+    pub right: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl BinaryExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        left: ExpressionId,
+        operator: SyntaxToken,
+        right: ExpressionId,
+    ) -> Self {
+        let span = span_between(arena[left].span(), arena[right].span());
+        Self {
+            left,
+            operator,
+            right,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParenthesizedExpressionSyntax {
+    pub open_parenthesis_token: SyntaxToken,
+    pub expression: ExpressionId,
+    pub close_parenthesis_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl ParenthesizedExpressionSyntax {
+    pub fn new(
+        open_parenthesis_token: SyntaxToken,
+        expression: ExpressionId,
+        close_parenthesis_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(
+            open_parenthesis_token.span(),
+            close_parenthesis_token.span(),
+        );
+        Self {
+            open_parenthesis_token,
+            expression,
+            close_parenthesis_token,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct UnaryExpressionSyntax {
+    pub operator: SyntaxToken,
+    pub operand: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl UnaryExpressionSyntax {
+    pub fn new(arena: &SyntaxArena, operator: SyntaxToken, operand: ExpressionId) -> Self {
+        let span = span_between(operator.span(), arena[operand].span());
+        Self {
+            operator,
+            operand,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct LiteralExpressionSyntax {
+    pub literal_token: SyntaxToken,
+    pub value: LiteralToken,
+    pub span: TextSpan,
+}
+
+impl LiteralExpressionSyntax {
+    pub fn new(literal_token: SyntaxToken, value: LiteralToken) -> Self {
+        let span = literal_token.span();
+        Self {
+            literal_token,
+            value,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IdentifierExpressionSyntax {
+    pub identifier: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl IdentifierExpressionSyntax {
+    pub fn new(identifier: SyntaxToken) -> Self {
+        let span = identifier.span();
+        Self { identifier, span }
+    }
+
+    pub fn name(&self) -> &str {
+        self.identifier.text()
+    }
+}
+
+/// `target = value`. Assignment is an expression whose value is the value
+/// assigned, which is what makes `a = b = 3` work.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AssignmentExpressionSyntax {
+    pub target: ExpressionId,
+    pub equals_token: SyntaxToken,
+    pub value: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl AssignmentExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        target: ExpressionId,
+        equals_token: SyntaxToken,
+        value: ExpressionId,
+    ) -> Self {
+        let span = span_between(arena[target].span(), arena[value].span());
+        Self {
+            target,
+            equals_token,
+            value,
+            span,
+        }
+    }
+}
+
+/// `target += value` and the other compound assignments. The parser desugars
+/// the operator token into the binary operator it applies, so `x += 1` runs
+/// as `x = x + 1` with `x` evaluated only once.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompoundAssignmentExpressionSyntax {
+    pub target: ExpressionId,
+    pub operator_token: SyntaxToken,
+    pub binary_operator: Tokens,
+    pub value: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl CompoundAssignmentExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        target: ExpressionId,
+        operator_token: SyntaxToken,
+        binary_operator: Tokens,
+        value: ExpressionId,
+    ) -> Self {
+        let span = span_between(arena[target].span(), arena[value].span());
+        Self {
+            target,
+            operator_token,
+            binary_operator,
+            value,
+            span,
+        }
+    }
+}
+
+/// `++operand`, `--operand`, `operand++` or `operand--`. Like a compound
+/// assignment of 1, except that the postfix forms give back the value from
+/// before the update.
+#[derive(Clone, Debug, PartialEq)]
+pub struct UpdateExpressionSyntax {
+    pub operator_token: SyntaxToken,
+    pub binary_operator: Tokens,
+    pub operand: ExpressionId,
+    pub is_prefix: bool,
+    pub span: TextSpan,
+}
+
+impl UpdateExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        operator_token: SyntaxToken,
+        binary_operator: Tokens,
+        operand: ExpressionId,
+        is_prefix: bool,
+    ) -> Self {
+        let span = if is_prefix {
+            span_between(operator_token.span(), arena[operand].span())
+        } else {
+            span_between(arena[operand].span(), operator_token.span())
+        };
+        Self {
+            operator_token,
+            binary_operator,
+            operand,
+            is_prefix,
+            span,
+        }
+    }
+}
+
+/// `condition ? when_true : when_false`. Only the chosen branch is
+/// evaluated.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ConditionalExpressionSyntax {
+    pub condition: ExpressionId,
+    pub question_mark_token: SyntaxToken,
+    pub when_true: ExpressionId,
+    pub colon_token: SyntaxToken,
+    pub when_false: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl ConditionalExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        condition: ExpressionId,
+        question_mark_token: SyntaxToken,
+        when_true: ExpressionId,
+        colon_token: SyntaxToken,
+        when_false: ExpressionId,
+    ) -> Self {
+        let span = span_between(arena[condition].span(), arena[when_false].span());
+        Self {
+            condition,
+            question_mark_token,
+            when_true,
+            colon_token,
+            when_false,
+            span,
+        }
+    }
+}
+
+/// `target[index]`, or `target?[index]` which gives null when `target` is
+/// null.
+#[derive(Clone, Debug, PartialEq)]
+pub struct IndexExpressionSyntax {
+    pub target: ExpressionId,
+    pub open_bracket_token: SyntaxToken,
+    pub index: ExpressionId,
+    pub close_bracket_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl IndexExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        target: ExpressionId,
+        open_bracket_token: SyntaxToken,
+        index: ExpressionId,
+        close_bracket_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(arena[target].span(), close_bracket_token.span());
+        Self {
+            target,
+            open_bracket_token,
+            index,
+            close_bracket_token,
+            span,
+        }
+    }
+
+    /// Whether this is a null-conditional `?[`.
+    pub fn is_conditional(&self) -> bool {
+        self.open_bracket_token.kind() == &SyntaxKind::Token(Tokens::QuestionMarkOpenBracketToken)
+    }
+}
+
+/// `target.name`, or `target?.name` which gives null when `target` is null.
+#[derive(Clone, Debug, PartialEq)]
+pub struct MemberAccessExpressionSyntax {
+    pub target: ExpressionId,
+    pub dot_token: SyntaxToken,
+    pub name_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl MemberAccessExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        target: ExpressionId,
+        dot_token: SyntaxToken,
+        name_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(arena[target].span(), name_token.span());
+        Self {
+            target,
+            dot_token,
+            name_token,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.name_token.text()
+    }
+
+    /// Whether this is a null-conditional `?.`.
+    pub fn is_conditional(&self) -> bool {
+        self.dot_token.kind() == &SyntaxKind::Token(Tokens::QuestionMarkDotToken)
+    }
+}
+
+/// `expression as type`, which converts a number to another numeric type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CastExpressionSyntax {
+    pub expression: ExpressionId,
+    pub as_keyword: SyntaxToken,
+    pub type_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl CastExpressionSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        expression: ExpressionId,
+        as_keyword: SyntaxToken,
+        type_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(arena[expression].span(), type_token.span());
+        Self {
+            expression,
+            as_keyword,
+            type_token,
+            span,
+        }
+    }
+
+    pub fn type_name(&self) -> &str {
+        self.type_token.text()
+    }
+}
+
+/// `[a, b, c]`
+#[derive(Clone, Debug, PartialEq)]
+pub struct ArrayLiteralExpressionSyntax {
+    pub open_bracket_token: SyntaxToken,
+    pub elements: SeparatedSyntaxList<ExpressionId>,
+    pub close_bracket_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl ArrayLiteralExpressionSyntax {
+    pub fn new(
+        open_bracket_token: SyntaxToken,
+        elements: SeparatedSyntaxList<ExpressionId>,
+        close_bracket_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(open_bracket_token.span(), close_bracket_token.span());
+        Self {
+            open_bracket_token,
+            elements,
+            close_bracket_token,
+            span,
+        }
+    }
+}
+
+/// `{ name: value, "other name": value }`. Only parsed where an expression is
+/// expected; a `{` that starts a statement is always a block.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectLiteralExpressionSyntax {
+    pub open_brace_token: SyntaxToken,
+    pub properties: SeparatedSyntaxList<ObjectPropertySyntax>,
+    pub close_brace_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl ObjectLiteralExpressionSyntax {
+    pub fn new(
+        open_brace_token: SyntaxToken,
+        properties: SeparatedSyntaxList<ObjectPropertySyntax>,
+        close_brace_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(open_brace_token.span(), close_brace_token.span());
+        Self {
+            open_brace_token,
+            properties,
+            close_brace_token,
+            span,
+        }
+    }
+}
+
+/// `name(arguments)`. Functions are not values, so the callee is always a
+/// name.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CallExpressionSyntax {
+    pub identifier: SyntaxToken,
+    pub open_parenthesis_token: SyntaxToken,
+    pub arguments: SeparatedSyntaxList<ExpressionId>,
+    pub close_parenthesis_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl CallExpressionSyntax {
+    pub fn new(
+        identifier: SyntaxToken,
+        open_parenthesis_token: SyntaxToken,
+        arguments: SeparatedSyntaxList<ExpressionId>,
+        close_parenthesis_token: SyntaxToken,
+    ) -> Self {
+        let span = span_between(identifier.span(), close_parenthesis_token.span());
+        Self {
+            identifier,
+            open_parenthesis_token,
+            arguments,
+            close_parenthesis_token,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.identifier.text()
+    }
+}
+
+/// One `name: value` entry of an object literal. The name is an identifier
+/// or a string literal.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectPropertySyntax {
+    pub name_token: SyntaxToken,
+    pub colon_token: SyntaxToken,
+    pub value: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl ObjectPropertySyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        name_token: SyntaxToken,
+        colon_token: SyntaxToken,
+        value: ExpressionId,
+    ) -> Self {
+        let span = span_between(name_token.span(), arena[value].span());
+        Self {
+            name_token,
+            colon_token,
+            value,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        match self.name_token.kind() {
+            SyntaxKind::Token(Tokens::LiteralToken {
+                value: LiteralToken::String { value },
+            }) => value,
+            _ => self.name_token.text(),
+        }
+    }
+}
+
+/// Stands in for an expression the parser could not make sense of. It keeps
+/// the tokens it consumed so the tree still reproduces the source.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BadExpressionSyntax {
+    pub tokens: Vec<SyntaxToken>,
+    pub span: TextSpan,
+}
+
+impl BadExpressionSyntax {
+    pub fn new(position: usize, tokens: Vec<SyntaxToken>) -> Self {
+        let span = match (tokens.first(), tokens.last()) {
+            (Some(first), Some(last)) => span_between(first.span(), last.span()),
+            _ => TextSpan::new(position, 0),
+        };
+        Self { tokens, span }
+    }
+}
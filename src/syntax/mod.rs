@@ -1,9 +1,32 @@
 #![allow(dead_code)]
+#![allow(clippy::enum_variant_names)]
+#![allow(clippy::large_enum_variant)]
 
+mod arena;
+mod expression_syntax;
+mod printer;
+pub mod rewriter;
+mod statement_syntax;
+pub mod visitor;
+pub mod walker;
+
+pub use arena::*;
+pub use expression_syntax::*;
+pub use statement_syntax::*;
+pub use walker::SyntaxWalker;
+
+use printer::{FullTextWriter, SyntaxPrinter};
+
+use num_bigint::BigInt;
+use rust_decimal::Decimal;
+
+use crate::lexer::source_text::TextSpan;
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum LiteralToken {
     Int { value: i64 },
+    BigInt { value: BigInt },
+    Decimal { value: Decimal },
     Float { value: f64 },
     String { value: String },
     Char { value: char },
@@ -20,6 +43,7 @@ pub enum Tokens {
     NumberToken { value: f64 },
     IdentifierToken { value: String },
     CommentToken { value: String },
+    DocCommentToken { value: String },
     PlusToken,
     MinusToken,
     StarToken,
@@ -27,6 +51,20 @@ pub enum Tokens {
     PercentToken,
     EqualsToken,
     EqualsEqualsToken,
+    PlusEqualsToken,
+    MinusEqualsToken,
+    StarEqualsToken,
+    SlashEqualsToken,
+    PercentEqualsToken,
+    AmpersandAmpersandEqualsToken,
+    PipePipeEqualsToken,
+    AmpersandEqualsToken,
+    PipeEqualsToken,
+    CaretEqualsToken,
+    LessThanLessThanEqualsToken,
+    GreaterThanGreaterThanEqualsToken,
+    PlusPlusToken,
+    MinusMinusToken,
     OpenParenthesisToken,
     CloseParenthesisToken,
     SemiColonToken,
@@ -41,8 +79,15 @@ pub enum Tokens {
     CaretToken,
     TildeToken,
     QuestionMarkToken,
+    QuestionMarkQuestionMarkToken,
+    QuestionMarkDotToken,
+    QuestionMarkOpenBracketToken,
     LessThanToken,
+    LessThanEqualsToken,
+    LessThanLessThanToken,
     GreaterThanToken,
+    GreaterThanEqualsToken,
+    GreaterThanGreaterThanToken,
     HashToken,
     AtToken,
     DollarToken,
@@ -57,20 +102,21 @@ pub enum Tokens {
     OpenBraceToken,
     CloseBraceToken,
 
-
     // These are only here to be represented in diagnostics
     BinaryOperatorToken,
     UnaryOperatorToken,
-    UnknownToken {value: String},
+    UnknownToken { value: String },
     BangEqualsToken,
-    
 }
 
 #[derive(Clone, Debug, PartialEq)]
 pub enum Keywords {
     LetKeyword,
+    MutKeyword,
+    ConstKeyword,
     IfKeyword,
     ElseKeyword,
+    WhileKeyword,
     ForKeyword,
     LoopKeyword,
     BreakKeyword,
@@ -79,233 +125,225 @@ pub enum Keywords {
     TrueKeyword,
     FalseKeyword,
     NullKeyword,
+    DivKeyword,
+    AsKeyword,
+    FnKeyword,
+    ReturnKeyword,
 }
 
-#[derive(Clone, Debug, PartialEq)]
-pub enum ExpressionSyntax {
-    BinaryExpressionSyntax {
-        left: Box<SyntaxKind>,
-        operator: Box<SyntaxKind>,
-        right: Box<SyntaxKind>,
-    },
-    ParenthesizedExpressionSyntax {
-        open_parenthesis_token: Box<SyntaxKind>,
-        expression: Box<SyntaxKind>,
-        close_parenthesis_token: Box<SyntaxKind>,
-    },
-    UnaryExpressionSyntax {
-        operator: Box<SyntaxKind>,
-        operand: Box<SyntaxKind>,
-    },
-    LiteralExpressionSyntax {
-        expression: Box<SyntaxKind>,
-    },
-    IdentifierExpressionSyntax { identifier: Box<SyntaxKind> },
-}
-
-#[derive(Clone, Debug, PartialEq)]
-pub enum StatementSyntax {
-    BlockStatementSyntax {
-        open_brace: Box<SyntaxKind>,
-        statements: Box<SyntaxKind>,
-        close_brace: Box<SyntaxKind>,
-    },
-    ExpressionStatementSyntax {
-        expression: Box<SyntaxKind>,
-        semicolon: Box<SyntaxKind>,
-    },
-    VariableAssignmentStatementSyntax {
-        identifier: Box<SyntaxKind>,
-        equals_token: Box<SyntaxKind>,
-        expression: Box<SyntaxKind>,
-    },
-    VariableDeclarationStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        identifier: Box<SyntaxKind>,
-        equals_token: Box<SyntaxKind>,
-        expression: Box<SyntaxKind>,
-        semicolon: Box<SyntaxKind>,
-    },
-    IfStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        open_parenthesis: Box<SyntaxKind>,
-        condition: Box<SyntaxKind>,
-        close_parenthesis: Box<SyntaxKind>,
-        open_brace: Box<SyntaxKind>,
-        body: Box<SyntaxKind>,
-        close_brace: Box<SyntaxKind>,
-    },
-    WhileStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        open_parenthesis: Box<SyntaxKind>,
-        condition: Box<SyntaxKind>,
-        close_parenthesis: Box<SyntaxKind>,
-        open_brace: Box<SyntaxKind>,
-        body: Box<SyntaxKind>,
-        close_brace: Box<SyntaxKind>,
-    },
-    ForStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        open_parenthesis: Box<SyntaxKind>,
-        initializer: Box<SyntaxKind>,
-        first_semicolon: Box<SyntaxKind>,
-        condition: Box<SyntaxKind>,
-        second_semicolon: Box<SyntaxKind>,
-        incrementor: Box<SyntaxKind>,
-        close_parenthesis: Box<SyntaxKind>,
-        open_brace: Box<SyntaxKind>,
-        body: Box<SyntaxKind>,
-        close_brace: Box<SyntaxKind>,
-    },
-    BreakStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        label: Option<Box<SyntaxKind>>,
-        semicolon: Box<SyntaxKind>,
-    },
-    ContinueStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        label: Option<Box<SyntaxKind>>,
-        semicolon: Box<SyntaxKind>,
-    },
-    ReturnStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        expression: Option<Box<SyntaxKind>>,
-        semicolon: Box<SyntaxKind>,
-    },
-    MatchStatementSyntax {
-        keyword: Box<SyntaxKind>,
-        open_parenthesis: Box<SyntaxKind>,
-        expression: Box<SyntaxKind>,
-        close_parenthesis: Box<SyntaxKind>,
-        open_brace: Box<SyntaxKind>,
-        arms: Box<SyntaxKind>,
-        close_brace: Box<SyntaxKind>,
-    },
-}
-
+/// The kind of a token. Nodes have their own types, so a kind only ever
+/// describes a leaf of the tree.
 #[derive(Clone, Debug, PartialEq)]
 pub enum SyntaxKind {
     Token(Tokens),
-    Expression(ExpressionSyntax),
-    Statement(StatementSyntax),
     Keyword(Keywords),
 }
 
 impl SyntaxKind {
     pub fn matches(&self, other: &SyntaxKind) -> bool {
-        return self.eq(other);
+        self.eq(other)
     }
+}
 
-    pub fn from(kind: Box<SyntaxKind>) -> SyntaxKind {
-        *kind.clone()
+/// A piece of source text that carries no meaning for the parser, such as
+/// whitespace, line breaks, comments or tokens the parser had to skip.
+/// Trivia hangs off the token it precedes or follows so that no text is lost.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxTrivia {
+    pub kind: Tokens,
+    pub position: usize,
+    pub text: String,
+}
+
+impl SyntaxTrivia {
+    pub fn new(kind: Tokens, position: usize, text: String) -> Self {
+        Self {
+            kind,
+            position,
+            text,
+        }
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
-pub struct SyntaxKindDescriptor {
+pub struct SyntaxToken {
     position: usize,
-    syntax: SyntaxKind,
+    kind: SyntaxKind,
+    text: String,
+    leading_trivia: Vec<SyntaxTrivia>,
+    trailing_trivia: Vec<SyntaxTrivia>,
 }
 
-impl SyntaxKindDescriptor {
-    pub fn new(position: usize, syntax: SyntaxKind) -> Self {
-        Self { position, syntax }
+impl SyntaxToken {
+    /// Creates a token with no source text, used for tokens the parser
+    /// expected but did not find.
+    pub fn new(position: usize, kind: SyntaxKind) -> Self {
+        Self {
+            position,
+            kind,
+            text: String::new(),
+            leading_trivia: Vec::new(),
+            trailing_trivia: Vec::new(),
+        }
+    }
+
+    /// Creates a token that owns its source text and the trivia around it.
+    pub fn with_trivia(
+        position: usize,
+        kind: SyntaxKind,
+        text: String,
+        leading_trivia: Vec<SyntaxTrivia>,
+        trailing_trivia: Vec<SyntaxTrivia>,
+    ) -> Self {
+        Self {
+            position,
+            kind,
+            text,
+            leading_trivia,
+            trailing_trivia,
+        }
     }
 
     pub fn position(&self) -> usize {
         self.position
     }
 
-    pub fn syntax(&self) -> SyntaxKind {
-        self.syntax.clone()
+    pub fn kind(&self) -> &SyntaxKind {
+        &self.kind
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    /// The span of the token itself, without its trivia.
+    pub fn span(&self) -> TextSpan {
+        TextSpan::new(self.position, self.text.len())
+    }
+
+    pub fn leading_trivia(&self) -> &[SyntaxTrivia] {
+        &self.leading_trivia
+    }
+
+    pub fn trailing_trivia(&self) -> &[SyntaxTrivia] {
+        &self.trailing_trivia
+    }
+
+    pub fn leading_trivia_mut(&mut self) -> &mut Vec<SyntaxTrivia> {
+        &mut self.leading_trivia
+    }
+
+    /// Returns the exact source text of the token, including all trivia.
+    pub fn full_text(&self) -> String {
+        let mut text = String::new();
+        self.write_full_text(&mut text);
+        text
+    }
+
+    pub(crate) fn write_full_text(&self, text: &mut String) {
+        for trivia in &self.leading_trivia {
+            text.push_str(&trivia.text);
+        }
+        text.push_str(&self.text);
+        for trivia in &self.trailing_trivia {
+            text.push_str(&trivia.text);
+        }
+    }
+
+    /// Returns the `///` doc comment in this token's leading trivia, which is
+    /// where doc comments for a declaration live.
+    pub fn documentation(&self) -> Option<String> {
+        let lines: Vec<&str> = self
+            .leading_trivia
+            .iter()
+            .filter_map(|trivia| match &trivia.kind {
+                Tokens::DocCommentToken { value } => Some(value.as_str()),
+                _ => None,
+            })
+            .collect();
+
+        if lines.is_empty() {
+            return None;
+        }
+        Some(lines.join("\n"))
     }
 
     pub fn print(&self) {
-        let syntax = self.syntax();
-        self.print_syntax_kind(syntax, None);
+        self.print_with_prefix("");
     }
 
-    fn print_syntax_kind(&self, kind: SyntaxKind, prefix: Option<String>) {
-        let prefix = match prefix {
-            Some(prefix) => prefix,
-            None => String::from(""),
-        };
-        match kind {
-            SyntaxKind::Expression(ExpressionSyntax::BinaryExpressionSyntax { left, operator, right }) => {
-                println!("{}BinaryExpressionSyntax", prefix);
-                self.print_syntax_kind(*left, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*operator, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*right, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Expression(ExpressionSyntax::ParenthesizedExpressionSyntax { open_parenthesis_token, expression, close_parenthesis_token }) => {
-                println!("{}ParenthesizedExpressionSyntax", prefix);
-                self.print_syntax_kind(*open_parenthesis_token, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*expression, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*close_parenthesis_token, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Expression(ExpressionSyntax::UnaryExpressionSyntax { operator, operand }) => {
-                println!("{}UnaryExpressionSyntax", prefix);
-                self.print_syntax_kind(*operator, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*operand, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Expression(ExpressionSyntax::LiteralExpressionSyntax { expression }) => {
-                println!("{}LiteralExpressionSyntax", prefix);
-                self.print_syntax_kind(*expression, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Statement(StatementSyntax::BlockStatementSyntax { open_brace, statements, close_brace }) => {
-                println!("{}BlockStatementSyntax", prefix);
-                self.print_syntax_kind(*open_brace, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*statements, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*close_brace, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Statement(StatementSyntax::ExpressionStatementSyntax { expression, semicolon }) => {
-                println!("{}ExpressionStatementSyntax", prefix);
-                self.print_syntax_kind(*expression, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*semicolon, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Statement(StatementSyntax::VariableAssignmentStatementSyntax { identifier, equals_token, expression }) => {
-                println!("{}VariableAssignmentStatementSyntax", prefix);
-                self.print_syntax_kind(*identifier, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*equals_token, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*expression, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Statement(StatementSyntax::IfStatementSyntax { keyword, open_parenthesis, condition, close_parenthesis, open_brace, body, close_brace }) => {
-                println!("{}IfStatementSyntax", prefix);
-                self.print_syntax_kind(*keyword, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*open_parenthesis, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*condition, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*close_parenthesis, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*open_brace, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*body, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*close_brace, Some(format!("{}\t", prefix)));
-            }
-            SyntaxKind::Statement(StatementSyntax::ForStatementSyntax { keyword, open_parenthesis, initializer, first_semicolon, condition, second_semicolon, incrementor, close_parenthesis, open_brace, body, close_brace }) => {
-                println!("{}ForStatementSyntax", prefix);
-                self.print_syntax_kind(*keyword, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*open_parenthesis, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*initializer, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*first_semicolon, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*condition, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*second_semicolon, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*incrementor, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*close_parenthesis, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*open_brace, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*body, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*close_brace, Some(format!("{}\t", prefix)));
-            }
-
-            SyntaxKind::Statement(StatementSyntax::VariableDeclarationStatementSyntax { keyword, identifier, equals_token, expression, semicolon }) => {
-                println!("{}VariableDeclarationStatementSyntax", prefix);
-                self.print_syntax_kind(*keyword, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*identifier, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*equals_token, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*expression, Some(format!("{}\t", prefix)));
-                self.print_syntax_kind(*semicolon, Some(format!("{}\t", prefix)));
-            }
-
-            _ => {
-                println!("{}{:?}", prefix, kind);
-            }
+    pub(crate) fn print_with_prefix(&self, prefix: &str) {
+        match &self.kind {
+            SyntaxKind::Token(token) => println!("{}Token({:?})", prefix, token),
+            SyntaxKind::Keyword(keyword) => println!("{}Keyword({:?})", prefix, keyword),
         }
     }
 }
+
+/// A list of nodes with the separator tokens between them, such as the
+/// elements of `[1, 2, 3]`. The separators are kept so the tree reproduces
+/// the source; a trailing separator is allowed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SeparatedSyntaxList<T> {
+    nodes: Vec<T>,
+    separators: Vec<SyntaxToken>,
+}
+
+impl<T> SeparatedSyntaxList<T> {
+    pub fn new(nodes: Vec<T>, separators: Vec<SyntaxToken>) -> Self {
+        Self { nodes, separators }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+
+    pub fn nodes(&self) -> &[T] {
+        &self.nodes
+    }
+
+    pub fn separators(&self) -> &[SyntaxToken] {
+        &self.separators
+    }
+
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.nodes.iter()
+    }
+}
+
+/// The result of parsing a whole input: the root node and the arena that
+/// owns every node below it.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SyntaxTree {
+    arena: SyntaxArena,
+    root: CompilationUnitSyntax,
+}
+
+impl SyntaxTree {
+    pub fn new(arena: SyntaxArena, root: CompilationUnitSyntax) -> Self {
+        Self { arena, root }
+    }
+
+    pub fn arena(&self) -> &SyntaxArena {
+        &self.arena
+    }
+
+    pub fn root(&self) -> &CompilationUnitSyntax {
+        &self.root
+    }
+
+    /// Returns the source text the tree was parsed from, byte for byte.
+    pub fn full_text(&self) -> String {
+        let mut writer = FullTextWriter::default();
+        writer.visit_compilation_unit(&self.arena, &self.root);
+        writer.text
+    }
+
+    pub fn print(&self) {
+        SyntaxPrinter::default().visit_compilation_unit(&self.arena, &self.root);
+    }
+}
@@ -0,0 +1,368 @@
+use super::{
+    walker::{self, SyntaxWalker},
+    ArrayLiteralExpressionSyntax, AssignmentExpressionSyntax, AttributeArgumentSyntax,
+    AttributeSyntax, AttributedStatementSyntax, BadExpressionSyntax, BinaryExpressionSyntax,
+    BlockStatementSyntax, BreakStatementSyntax, CallExpressionSyntax, CastExpressionSyntax,
+    CompilationUnitSyntax, CompoundAssignmentExpressionSyntax, ConditionalExpressionSyntax,
+    ContinueStatementSyntax, ElseBodySyntax, ErrorStatementSyntax, ExpressionStatementSyntax,
+    FunctionDeclarationSyntax, IdentifierExpressionSyntax, IfStatementSyntax,
+    IndexExpressionSyntax, InitializerSyntax, LiteralExpressionSyntax,
+    MemberAccessExpressionSyntax, ObjectLiteralExpressionSyntax, ObjectPropertySyntax,
+    ParameterSyntax, ParenthesizedExpressionSyntax, ReturnStatementSyntax, SyntaxArena,
+    SyntaxToken, TypeClauseSyntax, UnaryExpressionSyntax, UpdateExpressionSyntax,
+    VariableDeclarationStatementSyntax, WhileStatementSyntax,
+};
+
+/// Prints one line per node and token, indenting children under their
+/// parent.
+#[derive(Default)]
+pub(crate) struct SyntaxPrinter {
+    prefix: String,
+}
+
+impl SyntaxPrinter {
+    fn node(
+        &mut self,
+        name: &str,
+        arena: &SyntaxArena,
+        walk: impl FnOnce(&mut Self, &SyntaxArena),
+    ) {
+        println!("{}{}", self.prefix, name);
+        self.prefix.push('\t');
+        walk(self, arena);
+        self.prefix.pop();
+    }
+}
+
+impl SyntaxWalker for SyntaxPrinter {
+    fn visit_compilation_unit(&mut self, arena: &SyntaxArena, unit: &CompilationUnitSyntax) {
+        self.node("CompilationUnitSyntax", arena, |printer, arena| {
+            walker::walk_compilation_unit(printer, arena, unit)
+        });
+    }
+
+    fn visit_block_statement(&mut self, arena: &SyntaxArena, block: &BlockStatementSyntax) {
+        self.node("BlockStatementSyntax", arena, |printer, arena| {
+            walker::walk_block_statement(printer, arena, block)
+        });
+    }
+
+    fn visit_expression_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ExpressionStatementSyntax,
+    ) {
+        self.node("ExpressionStatementSyntax", arena, |printer, arena| {
+            walker::walk_expression_statement(printer, arena, statement)
+        });
+    }
+
+    fn visit_variable_declaration(
+        &mut self,
+        arena: &SyntaxArena,
+        declaration: &VariableDeclarationStatementSyntax,
+    ) {
+        self.node(
+            "VariableDeclarationStatementSyntax",
+            arena,
+            |printer, arena| {
+                if let Some(documentation) = declaration.documentation() {
+                    println!("{}Documentation: {:?}", printer.prefix, documentation);
+                }
+                walker::walk_variable_declaration(printer, arena, declaration)
+            },
+        );
+    }
+
+    fn visit_if_statement(&mut self, arena: &SyntaxArena, statement: &IfStatementSyntax) {
+        self.node("IfStatementSyntax", arena, |printer, arena| {
+            printer.visit_token(&statement.keyword);
+            printer.visit_token(&statement.open_parenthesis);
+            printer.visit_expression(arena, statement.condition);
+            printer.visit_token(&statement.close_parenthesis);
+            printer.visit_block_statement(arena, &statement.body);
+            if let Some(else_clause) = &statement.else_clause {
+                printer.node("ElseClauseSyntax", arena, |printer, arena| {
+                    printer.visit_token(&else_clause.else_keyword);
+                    match &else_clause.body {
+                        ElseBodySyntax::Block(block) => printer.visit_block_statement(arena, block),
+                        ElseBodySyntax::If(statement) => {
+                            printer.visit_if_statement(arena, statement)
+                        }
+                    }
+                });
+            }
+        });
+    }
+
+    fn visit_function_declaration(
+        &mut self,
+        arena: &SyntaxArena,
+        declaration: &FunctionDeclarationSyntax,
+    ) {
+        self.node("FunctionDeclarationSyntax", arena, |printer, arena| {
+            if let Some(documentation) = declaration.documentation() {
+                println!("{}Documentation: {:?}", printer.prefix, documentation);
+            }
+            walker::walk_function_declaration(printer, arena, declaration)
+        });
+    }
+
+    fn visit_parameter(&mut self, arena: &SyntaxArena, parameter: &ParameterSyntax) {
+        self.node("ParameterSyntax", arena, |printer, arena| {
+            walker::walk_parameter(printer, arena, parameter)
+        });
+    }
+
+    fn visit_initializer(&mut self, arena: &SyntaxArena, initializer: &InitializerSyntax) {
+        self.node("InitializerSyntax", arena, |printer, arena| {
+            printer.visit_token(&initializer.equals_token);
+            printer.visit_expression(arena, initializer.expression);
+        });
+    }
+
+    fn visit_type_clause(&mut self, arena: &SyntaxArena, type_clause: &TypeClauseSyntax) {
+        self.node("TypeClauseSyntax", arena, |printer, _| {
+            printer.visit_token(&type_clause.colon_token);
+            printer.visit_token(&type_clause.type_token);
+            if let Some(question_mark_token) = &type_clause.question_mark_token {
+                printer.visit_token(question_mark_token);
+            }
+        });
+    }
+
+    fn visit_return_statement(&mut self, arena: &SyntaxArena, statement: &ReturnStatementSyntax) {
+        self.node("ReturnStatementSyntax", arena, |printer, arena| {
+            walker::walk_return_statement(printer, arena, statement)
+        });
+    }
+
+    fn visit_while_statement(&mut self, arena: &SyntaxArena, statement: &WhileStatementSyntax) {
+        self.node("WhileStatementSyntax", arena, |printer, arena| {
+            walker::walk_while_statement(printer, arena, statement)
+        });
+    }
+
+    fn visit_break_statement(&mut self, arena: &SyntaxArena, statement: &BreakStatementSyntax) {
+        self.node("BreakStatementSyntax", arena, |printer, _| {
+            printer.visit_token(&statement.keyword);
+            printer.visit_token(&statement.semicolon);
+        });
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ContinueStatementSyntax,
+    ) {
+        self.node("ContinueStatementSyntax", arena, |printer, _| {
+            printer.visit_token(&statement.keyword);
+            printer.visit_token(&statement.semicolon);
+        });
+    }
+
+    fn visit_attributed_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &AttributedStatementSyntax,
+    ) {
+        self.node("AttributedStatementSyntax", arena, |printer, arena| {
+            walker::walk_attributed_statement(printer, arena, statement)
+        });
+    }
+
+    fn visit_attribute(&mut self, arena: &SyntaxArena, attribute: &AttributeSyntax) {
+        self.node("AttributeSyntax", arena, |printer, arena| {
+            walker::walk_attribute(printer, arena, attribute)
+        });
+    }
+
+    fn visit_attribute_argument(
+        &mut self,
+        arena: &SyntaxArena,
+        argument: &AttributeArgumentSyntax,
+    ) {
+        self.node("AttributeArgumentSyntax", arena, |printer, _| {
+            for token in &argument.tokens {
+                printer.visit_token(token);
+            }
+        });
+    }
+
+    fn visit_error_statement(&mut self, arena: &SyntaxArena, statement: &ErrorStatementSyntax) {
+        self.node("ErrorStatementSyntax", arena, |printer, _| {
+            for token in &statement.tokens {
+                printer.visit_token(token);
+            }
+        });
+    }
+
+    fn visit_binary_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &BinaryExpressionSyntax,
+    ) {
+        self.node("BinaryExpressionSyntax", arena, |printer, arena| {
+            walker::walk_binary_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_parenthesized_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ParenthesizedExpressionSyntax,
+    ) {
+        self.node("ParenthesizedExpressionSyntax", arena, |printer, arena| {
+            walker::walk_parenthesized_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_unary_expression(&mut self, arena: &SyntaxArena, expression: &UnaryExpressionSyntax) {
+        self.node("UnaryExpressionSyntax", arena, |printer, arena| {
+            walker::walk_unary_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &LiteralExpressionSyntax,
+    ) {
+        self.node("LiteralExpressionSyntax", arena, |printer, _| {
+            printer.visit_token(&expression.literal_token)
+        });
+    }
+
+    fn visit_identifier_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &IdentifierExpressionSyntax,
+    ) {
+        self.node("IdentifierExpressionSyntax", arena, |printer, _| {
+            printer.visit_token(&expression.identifier)
+        });
+    }
+
+    fn visit_assignment_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &AssignmentExpressionSyntax,
+    ) {
+        self.node("AssignmentExpressionSyntax", arena, |printer, arena| {
+            walker::walk_assignment_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_compound_assignment_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &CompoundAssignmentExpressionSyntax,
+    ) {
+        self.node(
+            "CompoundAssignmentExpressionSyntax",
+            arena,
+            |printer, arena| {
+                walker::walk_compound_assignment_expression(printer, arena, expression)
+            },
+        );
+    }
+
+    fn visit_update_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &UpdateExpressionSyntax,
+    ) {
+        self.node("UpdateExpressionSyntax", arena, |printer, arena| {
+            walker::walk_update_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_conditional_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ConditionalExpressionSyntax,
+    ) {
+        self.node("ConditionalExpressionSyntax", arena, |printer, arena| {
+            walker::walk_conditional_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_index_expression(&mut self, arena: &SyntaxArena, expression: &IndexExpressionSyntax) {
+        self.node("IndexExpressionSyntax", arena, |printer, arena| {
+            walker::walk_index_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_member_access_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &MemberAccessExpressionSyntax,
+    ) {
+        self.node("MemberAccessExpressionSyntax", arena, |printer, arena| {
+            walker::walk_member_access_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_cast_expression(&mut self, arena: &SyntaxArena, expression: &CastExpressionSyntax) {
+        self.node("CastExpressionSyntax", arena, |printer, arena| {
+            walker::walk_cast_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_call_expression(&mut self, arena: &SyntaxArena, expression: &CallExpressionSyntax) {
+        self.node("CallExpressionSyntax", arena, |printer, arena| {
+            walker::walk_call_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_array_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ArrayLiteralExpressionSyntax,
+    ) {
+        self.node("ArrayLiteralExpressionSyntax", arena, |printer, arena| {
+            walker::walk_array_literal_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_object_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ObjectLiteralExpressionSyntax,
+    ) {
+        self.node("ObjectLiteralExpressionSyntax", arena, |printer, arena| {
+            walker::walk_object_literal_expression(printer, arena, expression)
+        });
+    }
+
+    fn visit_object_property(&mut self, arena: &SyntaxArena, property: &ObjectPropertySyntax) {
+        self.node("ObjectPropertySyntax", arena, |printer, arena| {
+            walker::walk_object_property(printer, arena, property)
+        });
+    }
+
+    fn visit_bad_expression(&mut self, arena: &SyntaxArena, expression: &BadExpressionSyntax) {
+        self.node("BadExpressionSyntax", arena, |printer, _| {
+            for token in &expression.tokens {
+                printer.visit_token(token);
+            }
+        });
+    }
+
+    fn visit_token(&mut self, token: &SyntaxToken) {
+        token.print_with_prefix(&self.prefix);
+    }
+}
+
+/// Collects the full text of every token, trivia included, which gives back
+/// the source the tree was parsed from.
+#[derive(Default)]
+pub(crate) struct FullTextWriter {
+    pub(crate) text: String,
+}
+
+impl SyntaxWalker for FullTextWriter {
+    fn visit_token(&mut self, token: &SyntaxToken) {
+        token.write_full_text(&mut self.text);
+    }
+}
@@ -0,0 +1,667 @@
+use super::{
+    ArrayLiteralExpressionSyntax, AssignmentExpressionSyntax, AttributedStatementSyntax,
+    BinaryExpressionSyntax, BlockStatementSyntax, CallExpressionSyntax, CastExpressionSyntax,
+    CompilationUnitSyntax, CompoundAssignmentExpressionSyntax, ConditionalExpressionSyntax,
+    ElseBodySyntax, ElseClauseSyntax, ExpressionId, ExpressionStatementSyntax, ExpressionSyntax,
+    FunctionDeclarationSyntax, IfStatementSyntax, IndexExpressionSyntax, InitializerSyntax,
+    MemberAccessExpressionSyntax, ObjectLiteralExpressionSyntax, ObjectPropertySyntax,
+    ParenthesizedExpressionSyntax, ReturnStatementSyntax, SeparatedSyntaxList, StatementId,
+    StatementSyntax, SyntaxArena, SyntaxTree, UnaryExpressionSyntax, UpdateExpressionSyntax,
+    VariableDeclarationStatementSyntax, WhileStatementSyntax,
+};
+
+/// Produces a transformed copy of a syntax tree. Each method returns the id
+/// of the node that replaces the one it was given, which may be of another
+/// kind. The defaults rewrite the children and only allocate a new node when
+/// one of them changed, so untouched subtrees are shared with the original.
+pub trait SyntaxRewriter {
+    fn rewrite_tree(&mut self, tree: &SyntaxTree) -> SyntaxTree {
+        let mut arena = tree.arena().clone();
+        let root = self.rewrite_compilation_unit(&mut arena, tree.root());
+        SyntaxTree::new(arena, root)
+    }
+
+    fn rewrite_compilation_unit(
+        &mut self,
+        arena: &mut SyntaxArena,
+        unit: &CompilationUnitSyntax,
+    ) -> CompilationUnitSyntax {
+        rewrite_compilation_unit(self, arena, unit)
+    }
+
+    fn rewrite_statement(
+        &mut self,
+        arena: &mut SyntaxArena,
+        statement: StatementId,
+    ) -> StatementId {
+        rewrite_statement(self, arena, statement)
+    }
+
+    fn rewrite_block_statement(
+        &mut self,
+        arena: &mut SyntaxArena,
+        block: &BlockStatementSyntax,
+    ) -> BlockStatementSyntax {
+        rewrite_block_statement(self, arena, block)
+    }
+
+    fn rewrite_if_statement(
+        &mut self,
+        arena: &mut SyntaxArena,
+        statement: &IfStatementSyntax,
+    ) -> IfStatementSyntax {
+        rewrite_if_statement(self, arena, statement)
+    }
+
+    fn rewrite_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        expression: ExpressionId,
+    ) -> ExpressionId {
+        rewrite_expression(self, arena, expression)
+    }
+
+    fn rewrite_binary_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &BinaryExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_binary_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_parenthesized_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &ParenthesizedExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_parenthesized_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_unary_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &UnaryExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_unary_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_assignment_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &AssignmentExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_assignment_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_compound_assignment_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &CompoundAssignmentExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_compound_assignment_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_update_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &UpdateExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_update_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_conditional_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &ConditionalExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_conditional_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_index_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &IndexExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_index_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_member_access_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &MemberAccessExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_member_access_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_cast_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &CastExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_cast_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_call_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &CallExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_call_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_array_literal_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &ArrayLiteralExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_array_literal_expression(self, arena, id, expression)
+    }
+
+    fn rewrite_object_literal_expression(
+        &mut self,
+        arena: &mut SyntaxArena,
+        id: ExpressionId,
+        expression: &ObjectLiteralExpressionSyntax,
+    ) -> ExpressionId {
+        rewrite_object_literal_expression(self, arena, id, expression)
+    }
+}
+
+pub fn rewrite_compilation_unit<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    unit: &CompilationUnitSyntax,
+) -> CompilationUnitSyntax {
+    let statements = unit
+        .statements
+        .iter()
+        .map(|statement| rewriter.rewrite_statement(arena, *statement))
+        .collect();
+    CompilationUnitSyntax::new(arena, statements, unit.end_of_file_token.clone())
+}
+
+pub fn rewrite_statement<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: StatementId,
+) -> StatementId {
+    let rewritten = match arena[id].clone() {
+        StatementSyntax::BlockStatementSyntax(block) => {
+            let rewritten = rewriter.rewrite_block_statement(arena, &block);
+            if rewritten == block {
+                return id;
+            }
+            StatementSyntax::BlockStatementSyntax(rewritten)
+        }
+        StatementSyntax::ExpressionStatementSyntax(statement) => {
+            let expression = rewriter.rewrite_expression(arena, statement.expression);
+            if expression == statement.expression {
+                return id;
+            }
+            StatementSyntax::ExpressionStatementSyntax(ExpressionStatementSyntax::new(
+                arena,
+                expression,
+                statement.semicolon,
+            ))
+        }
+        StatementSyntax::VariableDeclarationStatementSyntax(declaration) => {
+            let Some(initializer) = &declaration.initializer else {
+                return id;
+            };
+            let expression = rewriter.rewrite_expression(arena, initializer.expression);
+            if expression == initializer.expression {
+                return id;
+            }
+            let initializer =
+                InitializerSyntax::new(arena, initializer.equals_token.clone(), expression);
+            StatementSyntax::VariableDeclarationStatementSyntax(
+                VariableDeclarationStatementSyntax::new(
+                    declaration.keyword,
+                    declaration.mut_keyword,
+                    declaration.identifier,
+                    declaration.type_clause,
+                    Some(initializer),
+                    declaration.semicolon,
+                ),
+            )
+        }
+        StatementSyntax::IfStatementSyntax(statement) => {
+            let rewritten = rewriter.rewrite_if_statement(arena, &statement);
+            if rewritten == statement {
+                return id;
+            }
+            StatementSyntax::IfStatementSyntax(rewritten)
+        }
+        StatementSyntax::FunctionDeclarationSyntax(declaration) => {
+            let body = rewriter.rewrite_block_statement(arena, &declaration.body);
+            if body == declaration.body {
+                return id;
+            }
+            StatementSyntax::FunctionDeclarationSyntax(FunctionDeclarationSyntax::new(
+                declaration.fn_keyword,
+                declaration.identifier,
+                declaration.open_parenthesis,
+                declaration.parameters,
+                declaration.close_parenthesis,
+                declaration.return_type,
+                body,
+            ))
+        }
+        StatementSyntax::ReturnStatementSyntax(statement) => {
+            let expression = statement
+                .expression
+                .map(|expression| rewriter.rewrite_expression(arena, expression));
+            if expression == statement.expression {
+                return id;
+            }
+            StatementSyntax::ReturnStatementSyntax(ReturnStatementSyntax::new(
+                statement.return_keyword,
+                expression,
+                statement.semicolon,
+            ))
+        }
+        StatementSyntax::WhileStatementSyntax(statement) => {
+            let condition = rewriter.rewrite_expression(arena, statement.condition);
+            let body = rewriter.rewrite_block_statement(arena, &statement.body);
+            if condition == statement.condition && body == statement.body {
+                return id;
+            }
+            StatementSyntax::WhileStatementSyntax(WhileStatementSyntax::new(
+                statement.while_keyword,
+                statement.open_parenthesis,
+                condition,
+                statement.close_parenthesis,
+                body,
+            ))
+        }
+        StatementSyntax::AttributedStatementSyntax(statement) => {
+            let inner = rewriter.rewrite_statement(arena, statement.statement);
+            if inner == statement.statement {
+                return id;
+            }
+            StatementSyntax::AttributedStatementSyntax(AttributedStatementSyntax::new(
+                arena,
+                statement.attributes,
+                inner,
+            ))
+        }
+        StatementSyntax::BreakStatementSyntax(_)
+        | StatementSyntax::ContinueStatementSyntax(_)
+        | StatementSyntax::ErrorStatementSyntax(_) => return id,
+    };
+    arena.alloc_statement(rewritten)
+}
+
+pub fn rewrite_block_statement<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    block: &BlockStatementSyntax,
+) -> BlockStatementSyntax {
+    let statements = block
+        .statements
+        .iter()
+        .map(|statement| rewriter.rewrite_statement(arena, *statement))
+        .collect();
+    BlockStatementSyntax::new(
+        block.open_brace.clone(),
+        statements,
+        block.close_brace.clone(),
+    )
+}
+
+pub fn rewrite_if_statement<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    statement: &IfStatementSyntax,
+) -> IfStatementSyntax {
+    let condition = rewriter.rewrite_expression(arena, statement.condition);
+    let body = rewriter.rewrite_block_statement(arena, &statement.body);
+    let else_clause = statement.else_clause.as_ref().map(|else_clause| {
+        let body = match &else_clause.body {
+            ElseBodySyntax::Block(block) => {
+                ElseBodySyntax::Block(rewriter.rewrite_block_statement(arena, block))
+            }
+            ElseBodySyntax::If(statement) => {
+                ElseBodySyntax::If(Box::new(rewriter.rewrite_if_statement(arena, statement)))
+            }
+        };
+        ElseClauseSyntax::new(else_clause.else_keyword.clone(), body)
+    });
+    IfStatementSyntax::new(
+        statement.keyword.clone(),
+        statement.open_parenthesis.clone(),
+        condition,
+        statement.close_parenthesis.clone(),
+        body,
+        else_clause,
+    )
+}
+
+pub fn rewrite_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+) -> ExpressionId {
+    match arena[id].clone() {
+        ExpressionSyntax::BinaryExpressionSyntax(expression) => {
+            rewriter.rewrite_binary_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::ParenthesizedExpressionSyntax(expression) => {
+            rewriter.rewrite_parenthesized_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::UnaryExpressionSyntax(expression) => {
+            rewriter.rewrite_unary_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::AssignmentExpressionSyntax(expression) => {
+            rewriter.rewrite_assignment_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::CompoundAssignmentExpressionSyntax(expression) => {
+            rewriter.rewrite_compound_assignment_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::UpdateExpressionSyntax(expression) => {
+            rewriter.rewrite_update_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::ConditionalExpressionSyntax(expression) => {
+            rewriter.rewrite_conditional_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::IndexExpressionSyntax(expression) => {
+            rewriter.rewrite_index_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::MemberAccessExpressionSyntax(expression) => {
+            rewriter.rewrite_member_access_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::CastExpressionSyntax(expression) => {
+            rewriter.rewrite_cast_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::CallExpressionSyntax(expression) => {
+            rewriter.rewrite_call_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::ArrayLiteralExpressionSyntax(expression) => {
+            rewriter.rewrite_array_literal_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::ObjectLiteralExpressionSyntax(expression) => {
+            rewriter.rewrite_object_literal_expression(arena, id, &expression)
+        }
+        ExpressionSyntax::LiteralExpressionSyntax(_)
+        | ExpressionSyntax::IdentifierExpressionSyntax(_)
+        | ExpressionSyntax::BadExpressionSyntax(_) => id,
+    }
+}
+
+pub fn rewrite_binary_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &BinaryExpressionSyntax,
+) -> ExpressionId {
+    let left = rewriter.rewrite_expression(arena, expression.left);
+    let right = rewriter.rewrite_expression(arena, expression.right);
+    if left == expression.left && right == expression.right {
+        return id;
+    }
+    let rewritten = BinaryExpressionSyntax::new(arena, left, expression.operator.clone(), right);
+    arena.alloc_expression(ExpressionSyntax::BinaryExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_parenthesized_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &ParenthesizedExpressionSyntax,
+) -> ExpressionId {
+    let inner = rewriter.rewrite_expression(arena, expression.expression);
+    if inner == expression.expression {
+        return id;
+    }
+    let rewritten = ParenthesizedExpressionSyntax::new(
+        expression.open_parenthesis_token.clone(),
+        inner,
+        expression.close_parenthesis_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::ParenthesizedExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_unary_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &UnaryExpressionSyntax,
+) -> ExpressionId {
+    let operand = rewriter.rewrite_expression(arena, expression.operand);
+    if operand == expression.operand {
+        return id;
+    }
+    let rewritten = UnaryExpressionSyntax::new(arena, expression.operator.clone(), operand);
+    arena.alloc_expression(ExpressionSyntax::UnaryExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_assignment_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &AssignmentExpressionSyntax,
+) -> ExpressionId {
+    let target = rewriter.rewrite_expression(arena, expression.target);
+    let value = rewriter.rewrite_expression(arena, expression.value);
+    if target == expression.target && value == expression.value {
+        return id;
+    }
+    let rewritten =
+        AssignmentExpressionSyntax::new(arena, target, expression.equals_token.clone(), value);
+    arena.alloc_expression(ExpressionSyntax::AssignmentExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_compound_assignment_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &CompoundAssignmentExpressionSyntax,
+) -> ExpressionId {
+    let target = rewriter.rewrite_expression(arena, expression.target);
+    let value = rewriter.rewrite_expression(arena, expression.value);
+    if target == expression.target && value == expression.value {
+        return id;
+    }
+    let rewritten = CompoundAssignmentExpressionSyntax::new(
+        arena,
+        target,
+        expression.operator_token.clone(),
+        expression.binary_operator.clone(),
+        value,
+    );
+    arena.alloc_expression(ExpressionSyntax::CompoundAssignmentExpressionSyntax(
+        rewritten,
+    ))
+}
+
+pub fn rewrite_update_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &UpdateExpressionSyntax,
+) -> ExpressionId {
+    let operand = rewriter.rewrite_expression(arena, expression.operand);
+    if operand == expression.operand {
+        return id;
+    }
+    let rewritten = UpdateExpressionSyntax::new(
+        arena,
+        expression.operator_token.clone(),
+        expression.binary_operator.clone(),
+        operand,
+        expression.is_prefix,
+    );
+    arena.alloc_expression(ExpressionSyntax::UpdateExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_conditional_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &ConditionalExpressionSyntax,
+) -> ExpressionId {
+    let condition = rewriter.rewrite_expression(arena, expression.condition);
+    let when_true = rewriter.rewrite_expression(arena, expression.when_true);
+    let when_false = rewriter.rewrite_expression(arena, expression.when_false);
+    if condition == expression.condition
+        && when_true == expression.when_true
+        && when_false == expression.when_false
+    {
+        return id;
+    }
+    let rewritten = ConditionalExpressionSyntax::new(
+        arena,
+        condition,
+        expression.question_mark_token.clone(),
+        when_true,
+        expression.colon_token.clone(),
+        when_false,
+    );
+    arena.alloc_expression(ExpressionSyntax::ConditionalExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_index_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &IndexExpressionSyntax,
+) -> ExpressionId {
+    let target = rewriter.rewrite_expression(arena, expression.target);
+    let index = rewriter.rewrite_expression(arena, expression.index);
+    if target == expression.target && index == expression.index {
+        return id;
+    }
+    let rewritten = IndexExpressionSyntax::new(
+        arena,
+        target,
+        expression.open_bracket_token.clone(),
+        index,
+        expression.close_bracket_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::IndexExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_member_access_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &MemberAccessExpressionSyntax,
+) -> ExpressionId {
+    let target = rewriter.rewrite_expression(arena, expression.target);
+    if target == expression.target {
+        return id;
+    }
+    let rewritten = MemberAccessExpressionSyntax::new(
+        arena,
+        target,
+        expression.dot_token.clone(),
+        expression.name_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::MemberAccessExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_cast_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &CastExpressionSyntax,
+) -> ExpressionId {
+    let operand = rewriter.rewrite_expression(arena, expression.expression);
+    if operand == expression.expression {
+        return id;
+    }
+    let rewritten = CastExpressionSyntax::new(
+        arena,
+        operand,
+        expression.as_keyword.clone(),
+        expression.type_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::CastExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_call_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &CallExpressionSyntax,
+) -> ExpressionId {
+    let arguments: Vec<ExpressionId> = expression
+        .arguments
+        .iter()
+        .map(|argument| rewriter.rewrite_expression(arena, *argument))
+        .collect();
+    if arguments == expression.arguments.nodes() {
+        return id;
+    }
+    let rewritten = CallExpressionSyntax::new(
+        expression.identifier.clone(),
+        expression.open_parenthesis_token.clone(),
+        SeparatedSyntaxList::new(arguments, expression.arguments.separators().to_vec()),
+        expression.close_parenthesis_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::CallExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_array_literal_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &ArrayLiteralExpressionSyntax,
+) -> ExpressionId {
+    let elements: Vec<ExpressionId> = expression
+        .elements
+        .iter()
+        .map(|element| rewriter.rewrite_expression(arena, *element))
+        .collect();
+    if elements == expression.elements.nodes() {
+        return id;
+    }
+    let rewritten = ArrayLiteralExpressionSyntax::new(
+        expression.open_bracket_token.clone(),
+        SeparatedSyntaxList::new(elements, expression.elements.separators().to_vec()),
+        expression.close_bracket_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::ArrayLiteralExpressionSyntax(rewritten))
+}
+
+pub fn rewrite_object_literal_expression<R: SyntaxRewriter + ?Sized>(
+    rewriter: &mut R,
+    arena: &mut SyntaxArena,
+    id: ExpressionId,
+    expression: &ObjectLiteralExpressionSyntax,
+) -> ExpressionId {
+    let properties: Vec<ObjectPropertySyntax> = expression
+        .properties
+        .iter()
+        .map(|property| {
+            let value = rewriter.rewrite_expression(arena, property.value);
+            ObjectPropertySyntax::new(
+                arena,
+                property.name_token.clone(),
+                property.colon_token.clone(),
+                value,
+            )
+        })
+        .collect();
+    if properties == expression.properties.nodes() {
+        return id;
+    }
+    let rewritten = ObjectLiteralExpressionSyntax::new(
+        expression.open_brace_token.clone(),
+        SeparatedSyntaxList::new(properties, expression.properties.separators().to_vec()),
+        expression.close_brace_token.clone(),
+    );
+    arena.alloc_expression(ExpressionSyntax::ObjectLiteralExpressionSyntax(rewritten))
+}
@@ -0,0 +1,562 @@
+use crate::lexer::source_text::TextSpan;
+
+use super::{
+    expression_syntax::span_between, ExpressionId, Keywords, SeparatedSyntaxList, StatementId,
+    SyntaxArena, SyntaxKind, SyntaxToken,
+};
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum StatementSyntax {
+    BlockStatementSyntax(BlockStatementSyntax),
+    ExpressionStatementSyntax(ExpressionStatementSyntax),
+    VariableDeclarationStatementSyntax(VariableDeclarationStatementSyntax),
+    IfStatementSyntax(IfStatementSyntax),
+    FunctionDeclarationSyntax(FunctionDeclarationSyntax),
+    ReturnStatementSyntax(ReturnStatementSyntax),
+    WhileStatementSyntax(WhileStatementSyntax),
+    BreakStatementSyntax(BreakStatementSyntax),
+    ContinueStatementSyntax(ContinueStatementSyntax),
+    AttributedStatementSyntax(AttributedStatementSyntax),
+    ErrorStatementSyntax(ErrorStatementSyntax),
+}
+
+impl StatementSyntax {
+    pub fn span(&self) -> TextSpan {
+        match self {
+            StatementSyntax::BlockStatementSyntax(statement) => statement.span,
+            StatementSyntax::ExpressionStatementSyntax(statement) => statement.span,
+            StatementSyntax::VariableDeclarationStatementSyntax(statement) => statement.span,
+            StatementSyntax::IfStatementSyntax(statement) => statement.span,
+            StatementSyntax::FunctionDeclarationSyntax(declaration) => declaration.span,
+            StatementSyntax::ReturnStatementSyntax(statement) => statement.span,
+            StatementSyntax::WhileStatementSyntax(statement) => statement.span,
+            StatementSyntax::BreakStatementSyntax(statement) => statement.span,
+            StatementSyntax::ContinueStatementSyntax(statement) => statement.span,
+            StatementSyntax::AttributedStatementSyntax(statement) => statement.span,
+            StatementSyntax::ErrorStatementSyntax(statement) => statement.span,
+        }
+    }
+}
+
+/// The root of a syntax tree: every top level statement followed by the end
+/// of file token, which carries any trivia after the last statement.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompilationUnitSyntax {
+    pub statements: Vec<StatementId>,
+    pub end_of_file_token: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl CompilationUnitSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        statements: Vec<StatementId>,
+        end_of_file_token: SyntaxToken,
+    ) -> Self {
+        let span = match statements.first() {
+            Some(first) => span_between(arena[*first].span(), end_of_file_token.span()),
+            None => end_of_file_token.span(),
+        };
+        Self {
+            statements,
+            end_of_file_token,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BlockStatementSyntax {
+    pub open_brace: SyntaxToken,
+    pub statements: Vec<StatementId>,
+    pub close_brace: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl BlockStatementSyntax {
+    pub fn new(
+        open_brace: SyntaxToken,
+        statements: Vec<StatementId>,
+        close_brace: SyntaxToken,
+    ) -> Self {
+        let span = span_between(open_brace.span(), close_brace.span());
+        Self {
+            open_brace,
+            statements,
+            close_brace,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ExpressionStatementSyntax {
+    pub expression: ExpressionId,
+    pub semicolon: Option<SyntaxToken>,
+    pub span: TextSpan,
+}
+
+impl ExpressionStatementSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        expression: ExpressionId,
+        semicolon: Option<SyntaxToken>,
+    ) -> Self {
+        let span = match &semicolon {
+            Some(semicolon) => span_between(arena[expression].span(), semicolon.span()),
+            None => arena[expression].span(),
+        };
+        Self {
+            expression,
+            semicolon,
+            span,
+        }
+    }
+}
+
+/// `let name = value;`, `let mut name = value;` or `const name = value;`,
+/// each with an optional annotation such as `let name: type = value;`. The
+/// value can be left out, as in `let mut name;`, which the binder only
+/// allows for `let mut`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct VariableDeclarationStatementSyntax {
+    /// `let` or `const`.
+    pub keyword: SyntaxToken,
+    pub mut_keyword: Option<SyntaxToken>,
+    pub identifier: SyntaxToken,
+    pub type_clause: Option<TypeClauseSyntax>,
+    pub initializer: Option<InitializerSyntax>,
+    pub semicolon: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl VariableDeclarationStatementSyntax {
+    pub fn new(
+        keyword: SyntaxToken,
+        mut_keyword: Option<SyntaxToken>,
+        identifier: SyntaxToken,
+        type_clause: Option<TypeClauseSyntax>,
+        initializer: Option<InitializerSyntax>,
+        semicolon: SyntaxToken,
+    ) -> Self {
+        let span = span_between(keyword.span(), semicolon.span());
+        Self {
+            keyword,
+            mut_keyword,
+            identifier,
+            type_clause,
+            initializer,
+            semicolon,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.identifier.text()
+    }
+
+    pub fn is_const(&self) -> bool {
+        self.keyword.kind() == &SyntaxKind::Keyword(Keywords::ConstKeyword)
+    }
+
+    pub fn is_mutable(&self) -> bool {
+        self.mut_keyword.is_some()
+    }
+
+    /// The `///` doc comment written above the declaration, if any.
+    pub fn documentation(&self) -> Option<String> {
+        self.keyword.documentation()
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct IfStatementSyntax {
+    pub keyword: SyntaxToken,
+    pub open_parenthesis: SyntaxToken,
+    pub condition: ExpressionId,
+    pub close_parenthesis: SyntaxToken,
+    pub body: BlockStatementSyntax,
+    pub else_clause: Option<ElseClauseSyntax>,
+    pub span: TextSpan,
+}
+
+impl IfStatementSyntax {
+    pub fn new(
+        keyword: SyntaxToken,
+        open_parenthesis: SyntaxToken,
+        condition: ExpressionId,
+        close_parenthesis: SyntaxToken,
+        body: BlockStatementSyntax,
+        else_clause: Option<ElseClauseSyntax>,
+    ) -> Self {
+        let end = match &else_clause {
+            Some(else_clause) => else_clause.span,
+            None => body.span,
+        };
+        let span = span_between(keyword.span(), end);
+        Self {
+            keyword,
+            open_parenthesis,
+            condition,
+            close_parenthesis,
+            body,
+            else_clause,
+            span,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ElseClauseSyntax {
+    pub else_keyword: SyntaxToken,
+    pub body: ElseBodySyntax,
+    pub span: TextSpan,
+}
+
+impl ElseClauseSyntax {
+    pub fn new(else_keyword: SyntaxToken, body: ElseBodySyntax) -> Self {
+        let end = match &body {
+            ElseBodySyntax::Block(block) => block.span,
+            ElseBodySyntax::If(statement) => statement.span,
+        };
+        let span = span_between(else_keyword.span(), end);
+        Self {
+            else_keyword,
+            body,
+            span,
+        }
+    }
+}
+
+/// What may follow `else`: a block, or another `if` for `else if` chains.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ElseBodySyntax {
+    Block(BlockStatementSyntax),
+    If(Box<IfStatementSyntax>),
+}
+
+/// The `= value` that gives a variable its first value.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InitializerSyntax {
+    pub equals_token: SyntaxToken,
+    pub expression: ExpressionId,
+    pub span: TextSpan,
+}
+
+impl InitializerSyntax {
+    pub fn new(arena: &SyntaxArena, equals_token: SyntaxToken, expression: ExpressionId) -> Self {
+        let span = span_between(equals_token.span(), arena[expression].span());
+        Self {
+            equals_token,
+            expression,
+            span,
+        }
+    }
+}
+
+/// A type annotation: `: int`, or `: int?` for a type that also allows
+/// null.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TypeClauseSyntax {
+    pub colon_token: SyntaxToken,
+    pub type_token: SyntaxToken,
+    pub question_mark_token: Option<SyntaxToken>,
+    pub span: TextSpan,
+}
+
+impl TypeClauseSyntax {
+    pub fn new(
+        colon_token: SyntaxToken,
+        type_token: SyntaxToken,
+        question_mark_token: Option<SyntaxToken>,
+    ) -> Self {
+        let end = match &question_mark_token {
+            Some(question_mark_token) => question_mark_token.span(),
+            None => type_token.span(),
+        };
+        let span = span_between(colon_token.span(), end);
+        Self {
+            colon_token,
+            type_token,
+            question_mark_token,
+            span,
+        }
+    }
+
+    pub fn type_name(&self) -> &str {
+        self.type_token.text()
+    }
+
+    pub fn is_nullable(&self) -> bool {
+        self.question_mark_token.is_some()
+    }
+}
+
+/// `fn name(parameters): type { body }`. The return type is optional.
+#[derive(Clone, Debug, PartialEq)]
+pub struct FunctionDeclarationSyntax {
+    pub fn_keyword: SyntaxToken,
+    pub identifier: SyntaxToken,
+    pub open_parenthesis: SyntaxToken,
+    pub parameters: SeparatedSyntaxList<ParameterSyntax>,
+    pub close_parenthesis: SyntaxToken,
+    pub return_type: Option<TypeClauseSyntax>,
+    pub body: BlockStatementSyntax,
+    pub span: TextSpan,
+}
+
+impl FunctionDeclarationSyntax {
+    pub fn new(
+        fn_keyword: SyntaxToken,
+        identifier: SyntaxToken,
+        open_parenthesis: SyntaxToken,
+        parameters: SeparatedSyntaxList<ParameterSyntax>,
+        close_parenthesis: SyntaxToken,
+        return_type: Option<TypeClauseSyntax>,
+        body: BlockStatementSyntax,
+    ) -> Self {
+        let span = span_between(fn_keyword.span(), body.span);
+        Self {
+            fn_keyword,
+            identifier,
+            open_parenthesis,
+            parameters,
+            close_parenthesis,
+            return_type,
+            body,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.identifier.text()
+    }
+
+    /// The `///` doc comment written above the declaration, if any.
+    pub fn documentation(&self) -> Option<String> {
+        self.fn_keyword.documentation()
+    }
+}
+
+/// One parameter of a function declaration, with an optional type.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParameterSyntax {
+    pub identifier: SyntaxToken,
+    pub type_clause: Option<TypeClauseSyntax>,
+    pub span: TextSpan,
+}
+
+impl ParameterSyntax {
+    pub fn new(identifier: SyntaxToken, type_clause: Option<TypeClauseSyntax>) -> Self {
+        let span = match &type_clause {
+            Some(type_clause) => span_between(identifier.span(), type_clause.span),
+            None => identifier.span(),
+        };
+        Self {
+            identifier,
+            type_clause,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.identifier.text()
+    }
+}
+
+/// `return;` or `return value;`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReturnStatementSyntax {
+    pub return_keyword: SyntaxToken,
+    pub expression: Option<ExpressionId>,
+    pub semicolon: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl ReturnStatementSyntax {
+    pub fn new(
+        return_keyword: SyntaxToken,
+        expression: Option<ExpressionId>,
+        semicolon: SyntaxToken,
+    ) -> Self {
+        let span = span_between(return_keyword.span(), semicolon.span());
+        Self {
+            return_keyword,
+            expression,
+            semicolon,
+            span,
+        }
+    }
+}
+
+/// `while (condition) { ... }`, which runs its body for as long as the
+/// condition is true.
+#[derive(Clone, Debug, PartialEq)]
+pub struct WhileStatementSyntax {
+    pub while_keyword: SyntaxToken,
+    pub open_parenthesis: SyntaxToken,
+    pub condition: ExpressionId,
+    pub close_parenthesis: SyntaxToken,
+    pub body: BlockStatementSyntax,
+    pub span: TextSpan,
+}
+
+impl WhileStatementSyntax {
+    pub fn new(
+        while_keyword: SyntaxToken,
+        open_parenthesis: SyntaxToken,
+        condition: ExpressionId,
+        close_parenthesis: SyntaxToken,
+        body: BlockStatementSyntax,
+    ) -> Self {
+        let span = span_between(while_keyword.span(), body.span);
+        Self {
+            while_keyword,
+            open_parenthesis,
+            condition,
+            close_parenthesis,
+            body,
+            span,
+        }
+    }
+}
+
+/// `break;`, which leaves the innermost loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BreakStatementSyntax {
+    pub keyword: SyntaxToken,
+    pub semicolon: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl BreakStatementSyntax {
+    pub fn new(keyword: SyntaxToken, semicolon: SyntaxToken) -> Self {
+        let span = span_between(keyword.span(), semicolon.span());
+        Self {
+            keyword,
+            semicolon,
+            span,
+        }
+    }
+}
+
+/// `continue;`, which goes back to the condition of the innermost loop.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ContinueStatementSyntax {
+    pub keyword: SyntaxToken,
+    pub semicolon: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl ContinueStatementSyntax {
+    pub fn new(keyword: SyntaxToken, semicolon: SyntaxToken) -> Self {
+        let span = span_between(keyword.span(), semicolon.span());
+        Self {
+            keyword,
+            semicolon,
+            span,
+        }
+    }
+}
+
+/// A statement with attributes written before it, such as
+/// `@allow(unused-variable) let x = 1;`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributedStatementSyntax {
+    pub attributes: Vec<AttributeSyntax>,
+    pub statement: StatementId,
+    pub span: TextSpan,
+}
+
+impl AttributedStatementSyntax {
+    pub fn new(
+        arena: &SyntaxArena,
+        attributes: Vec<AttributeSyntax>,
+        statement: StatementId,
+    ) -> Self {
+        let statement_span = arena[statement].span();
+        let span = match attributes.first() {
+            Some(attribute) => span_between(attribute.span, statement_span),
+            None => statement_span,
+        };
+        Self {
+            attributes,
+            statement,
+            span,
+        }
+    }
+}
+
+/// `@name(argument, ...)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeSyntax {
+    pub at_token: SyntaxToken,
+    pub identifier: SyntaxToken,
+    pub open_parenthesis: SyntaxToken,
+    pub arguments: SeparatedSyntaxList<AttributeArgumentSyntax>,
+    pub close_parenthesis: SyntaxToken,
+    pub span: TextSpan,
+}
+
+impl AttributeSyntax {
+    pub fn new(
+        at_token: SyntaxToken,
+        identifier: SyntaxToken,
+        open_parenthesis: SyntaxToken,
+        arguments: SeparatedSyntaxList<AttributeArgumentSyntax>,
+        close_parenthesis: SyntaxToken,
+    ) -> Self {
+        let span = span_between(at_token.span(), close_parenthesis.span());
+        Self {
+            at_token,
+            identifier,
+            open_parenthesis,
+            arguments,
+            close_parenthesis,
+            span,
+        }
+    }
+
+    pub fn name(&self) -> &str {
+        self.identifier.text()
+    }
+}
+
+/// A name given to an attribute, made of identifiers joined by `-`, such as
+/// `unused-variable`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct AttributeArgumentSyntax {
+    pub tokens: Vec<SyntaxToken>,
+    pub span: TextSpan,
+}
+
+impl AttributeArgumentSyntax {
+    pub fn new(tokens: Vec<SyntaxToken>) -> Self {
+        let first = tokens.first().expect("an argument has a token").span();
+        let last = tokens.last().expect("an argument has a token").span();
+        Self {
+            span: span_between(first, last),
+            tokens,
+        }
+    }
+
+    pub fn name(&self) -> String {
+        self.tokens.iter().map(|token| token.text()).collect()
+    }
+}
+
+/// The tokens the parser skipped while recovering from a syntax error. The
+/// error itself has already been reported; this node only keeps the skipped
+/// source in the tree.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ErrorStatementSyntax {
+    pub tokens: Vec<SyntaxToken>,
+    pub span: TextSpan,
+}
+
+impl ErrorStatementSyntax {
+    pub fn new(tokens: Vec<SyntaxToken>) -> Self {
+        let span = span_between(tokens[0].span(), tokens[tokens.len() - 1].span());
+        Self { tokens, span }
+    }
+}
@@ -0,0 +1,635 @@
+use super::{
+    ArrayLiteralExpressionSyntax, AssignmentExpressionSyntax, AttributedStatementSyntax,
+    BadExpressionSyntax, BinaryExpressionSyntax, BlockStatementSyntax, BreakStatementSyntax,
+    CallExpressionSyntax, CastExpressionSyntax, CompoundAssignmentExpressionSyntax,
+    ConditionalExpressionSyntax, ContinueStatementSyntax, ErrorStatementSyntax, ExpressionId,
+    ExpressionStatementSyntax, ExpressionSyntax, FunctionDeclarationSyntax,
+    IdentifierExpressionSyntax, IfStatementSyntax, IndexExpressionSyntax, LiteralExpressionSyntax,
+    MemberAccessExpressionSyntax, ObjectLiteralExpressionSyntax, ParenthesizedExpressionSyntax,
+    ReturnStatementSyntax, StatementId, StatementSyntax, SyntaxArena, UnaryExpressionSyntax,
+    UpdateExpressionSyntax, VariableDeclarationStatementSyntax, WhileStatementSyntax,
+};
+
+/// Computes a value for each node. Unlike `SyntaxWalker` nothing is visited
+/// implicitly: `visit_statement` and `visit_expression` only dispatch on the
+/// node kind, and each implementation decides which children to visit and in
+/// what order.
+pub trait SyntaxVisitor {
+    type Output;
+
+    fn visit_statement(&mut self, arena: &SyntaxArena, statement: StatementId) -> Self::Output {
+        match &arena[statement] {
+            StatementSyntax::BlockStatementSyntax(block) => {
+                self.visit_block_statement(arena, block)
+            }
+            StatementSyntax::ExpressionStatementSyntax(statement) => {
+                self.visit_expression_statement(arena, statement)
+            }
+            StatementSyntax::VariableDeclarationStatementSyntax(declaration) => {
+                self.visit_variable_declaration(arena, declaration)
+            }
+            StatementSyntax::IfStatementSyntax(statement) => {
+                self.visit_if_statement(arena, statement)
+            }
+            StatementSyntax::FunctionDeclarationSyntax(declaration) => {
+                self.visit_function_declaration(arena, declaration)
+            }
+            StatementSyntax::ReturnStatementSyntax(statement) => {
+                self.visit_return_statement(arena, statement)
+            }
+            StatementSyntax::WhileStatementSyntax(statement) => {
+                self.visit_while_statement(arena, statement)
+            }
+            StatementSyntax::BreakStatementSyntax(statement) => {
+                self.visit_break_statement(arena, statement)
+            }
+            StatementSyntax::ContinueStatementSyntax(statement) => {
+                self.visit_continue_statement(arena, statement)
+            }
+            StatementSyntax::AttributedStatementSyntax(statement) => {
+                self.visit_attributed_statement(arena, statement)
+            }
+            StatementSyntax::ErrorStatementSyntax(statement) => {
+                self.visit_error_statement(arena, statement)
+            }
+        }
+    }
+
+    fn visit_block_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        block: &BlockStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_expression_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ExpressionStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_variable_declaration(
+        &mut self,
+        arena: &SyntaxArena,
+        declaration: &VariableDeclarationStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_if_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &IfStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_function_declaration(
+        &mut self,
+        arena: &SyntaxArena,
+        declaration: &FunctionDeclarationSyntax,
+    ) -> Self::Output;
+
+    fn visit_return_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ReturnStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_while_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &WhileStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_break_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &BreakStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_continue_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ContinueStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_attributed_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &AttributedStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_error_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ErrorStatementSyntax,
+    ) -> Self::Output;
+
+    fn visit_expression(&mut self, arena: &SyntaxArena, expression: ExpressionId) -> Self::Output {
+        match &arena[expression] {
+            ExpressionSyntax::BinaryExpressionSyntax(expression) => {
+                self.visit_binary_expression(arena, expression)
+            }
+            ExpressionSyntax::ParenthesizedExpressionSyntax(expression) => {
+                self.visit_parenthesized_expression(arena, expression)
+            }
+            ExpressionSyntax::UnaryExpressionSyntax(expression) => {
+                self.visit_unary_expression(arena, expression)
+            }
+            ExpressionSyntax::LiteralExpressionSyntax(expression) => {
+                self.visit_literal_expression(arena, expression)
+            }
+            ExpressionSyntax::IdentifierExpressionSyntax(expression) => {
+                self.visit_identifier_expression(arena, expression)
+            }
+            ExpressionSyntax::AssignmentExpressionSyntax(expression) => {
+                self.visit_assignment_expression(arena, expression)
+            }
+            ExpressionSyntax::CompoundAssignmentExpressionSyntax(expression) => {
+                self.visit_compound_assignment_expression(arena, expression)
+            }
+            ExpressionSyntax::UpdateExpressionSyntax(expression) => {
+                self.visit_update_expression(arena, expression)
+            }
+            ExpressionSyntax::ConditionalExpressionSyntax(expression) => {
+                self.visit_conditional_expression(arena, expression)
+            }
+            ExpressionSyntax::IndexExpressionSyntax(expression) => {
+                self.visit_index_expression(arena, expression)
+            }
+            ExpressionSyntax::MemberAccessExpressionSyntax(expression) => {
+                self.visit_member_access_expression(arena, expression)
+            }
+            ExpressionSyntax::CastExpressionSyntax(expression) => {
+                self.visit_cast_expression(arena, expression)
+            }
+            ExpressionSyntax::CallExpressionSyntax(expression) => {
+                self.visit_call_expression(arena, expression)
+            }
+            ExpressionSyntax::ArrayLiteralExpressionSyntax(expression) => {
+                self.visit_array_literal_expression(arena, expression)
+            }
+            ExpressionSyntax::ObjectLiteralExpressionSyntax(expression) => {
+                self.visit_object_literal_expression(arena, expression)
+            }
+            ExpressionSyntax::BadExpressionSyntax(expression) => {
+                self.visit_bad_expression(arena, expression)
+            }
+        }
+    }
+
+    fn visit_binary_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &BinaryExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_parenthesized_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ParenthesizedExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_unary_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &UnaryExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &LiteralExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_identifier_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &IdentifierExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_assignment_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &AssignmentExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_compound_assignment_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &CompoundAssignmentExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_update_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &UpdateExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_conditional_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ConditionalExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_index_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &IndexExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_member_access_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &MemberAccessExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_cast_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &CastExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_call_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &CallExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_array_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ArrayLiteralExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_object_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ObjectLiteralExpressionSyntax,
+    ) -> Self::Output;
+
+    fn visit_bad_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &BadExpressionSyntax,
+    ) -> Self::Output;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        lexer::{source_text::SourceText, Lexer},
+        parser::Parser,
+        syntax::ElseBodySyntax,
+    };
+
+    /// Writes a tree with every operator's operands in parentheses, which
+    /// shows how the parser grouped them.
+    struct Parenthesizer;
+
+    impl Parenthesizer {
+        fn list(&mut self, arena: &SyntaxArena, expressions: &[ExpressionId]) -> String {
+            let expressions: Vec<String> = expressions
+                .iter()
+                .map(|expression| self.visit_expression(arena, *expression))
+                .collect();
+            expressions.join(", ")
+        }
+    }
+
+    impl SyntaxVisitor for Parenthesizer {
+        type Output = String;
+
+        fn visit_block_statement(
+            &mut self,
+            arena: &SyntaxArena,
+            block: &BlockStatementSyntax,
+        ) -> String {
+            let statements: Vec<String> = block
+                .statements
+                .iter()
+                .map(|statement| self.visit_statement(arena, *statement))
+                .collect();
+            format!("{{ {} }}", statements.join(" "))
+        }
+
+        fn visit_expression_statement(
+            &mut self,
+            arena: &SyntaxArena,
+            statement: &ExpressionStatementSyntax,
+        ) -> String {
+            format!("{};", self.visit_expression(arena, statement.expression))
+        }
+
+        fn visit_variable_declaration(
+            &mut self,
+            arena: &SyntaxArena,
+            declaration: &VariableDeclarationStatementSyntax,
+        ) -> String {
+            match &declaration.initializer {
+                Some(initializer) => format!(
+                    "let {} = {};",
+                    declaration.identifier.text(),
+                    self.visit_expression(arena, initializer.expression)
+                ),
+                None => format!("let {};", declaration.identifier.text()),
+            }
+        }
+
+        fn visit_if_statement(
+            &mut self,
+            arena: &SyntaxArena,
+            statement: &IfStatementSyntax,
+        ) -> String {
+            let condition = self.visit_expression(arena, statement.condition);
+            let body = self.visit_block_statement(arena, &statement.body);
+            let else_clause = match statement.else_clause.as_ref().map(|clause| &clause.body) {
+                Some(ElseBodySyntax::Block(block)) => {
+                    format!(" else {}", self.visit_block_statement(arena, block))
+                }
+                Some(ElseBodySyntax::If(statement)) => {
+                    format!(" else {}", self.visit_if_statement(arena, statement))
+                }
+                None => String::new(),
+            };
+            format!("if {} {}{}", condition, body, else_clause)
+        }
+
+        fn visit_function_declaration(
+            &mut self,
+            arena: &SyntaxArena,
+            declaration: &FunctionDeclarationSyntax,
+        ) -> String {
+            let parameters: Vec<&str> = declaration
+                .parameters
+                .iter()
+                .map(|parameter| parameter.identifier.text())
+                .collect();
+            format!(
+                "fn {}({}) {}",
+                declaration.identifier.text(),
+                parameters.join(", "),
+                self.visit_block_statement(arena, &declaration.body)
+            )
+        }
+
+        fn visit_return_statement(
+            &mut self,
+            arena: &SyntaxArena,
+            statement: &ReturnStatementSyntax,
+        ) -> String {
+            match statement.expression {
+                Some(expression) => format!("return {};", self.visit_expression(arena, expression)),
+                None => "return;".to_string(),
+            }
+        }
+
+        fn visit_while_statement(
+            &mut self,
+            arena: &SyntaxArena,
+            statement: &WhileStatementSyntax,
+        ) -> String {
+            format!(
+                "while {} {}",
+                self.visit_expression(arena, statement.condition),
+                self.visit_block_statement(arena, &statement.body)
+            )
+        }
+
+        fn visit_break_statement(&mut self, _: &SyntaxArena, _: &BreakStatementSyntax) -> String {
+            "break;".to_string()
+        }
+
+        fn visit_continue_statement(
+            &mut self,
+            _: &SyntaxArena,
+            _: &ContinueStatementSyntax,
+        ) -> String {
+            "continue;".to_string()
+        }
+
+        fn visit_attributed_statement(
+            &mut self,
+            arena: &SyntaxArena,
+            statement: &AttributedStatementSyntax,
+        ) -> String {
+            self.visit_statement(arena, statement.statement)
+        }
+
+        fn visit_error_statement(&mut self, _: &SyntaxArena, _: &ErrorStatementSyntax) -> String {
+            "<error>".to_string()
+        }
+
+        fn visit_binary_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &BinaryExpressionSyntax,
+        ) -> String {
+            format!(
+                "({} {} {})",
+                self.visit_expression(arena, expression.left),
+                expression.operator.text(),
+                self.visit_expression(arena, expression.right)
+            )
+        }
+
+        fn visit_parenthesized_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &ParenthesizedExpressionSyntax,
+        ) -> String {
+            self.visit_expression(arena, expression.expression)
+        }
+
+        fn visit_unary_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &UnaryExpressionSyntax,
+        ) -> String {
+            format!(
+                "({}{})",
+                expression.operator.text(),
+                self.visit_expression(arena, expression.operand)
+            )
+        }
+
+        fn visit_literal_expression(
+            &mut self,
+            _: &SyntaxArena,
+            expression: &LiteralExpressionSyntax,
+        ) -> String {
+            expression.literal_token.text().to_string()
+        }
+
+        fn visit_identifier_expression(
+            &mut self,
+            _: &SyntaxArena,
+            expression: &IdentifierExpressionSyntax,
+        ) -> String {
+            expression.identifier.text().to_string()
+        }
+
+        fn visit_assignment_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &AssignmentExpressionSyntax,
+        ) -> String {
+            format!(
+                "({} = {})",
+                self.visit_expression(arena, expression.target),
+                self.visit_expression(arena, expression.value)
+            )
+        }
+
+        fn visit_compound_assignment_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &CompoundAssignmentExpressionSyntax,
+        ) -> String {
+            format!(
+                "({} {} {})",
+                self.visit_expression(arena, expression.target),
+                expression.operator_token.text(),
+                self.visit_expression(arena, expression.value)
+            )
+        }
+
+        fn visit_update_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &UpdateExpressionSyntax,
+        ) -> String {
+            let operand = self.visit_expression(arena, expression.operand);
+            let operator = expression.operator_token.text();
+            match expression.is_prefix {
+                true => format!("({}{})", operator, operand),
+                false => format!("({}{})", operand, operator),
+            }
+        }
+
+        fn visit_conditional_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &ConditionalExpressionSyntax,
+        ) -> String {
+            format!(
+                "({} ? {} : {})",
+                self.visit_expression(arena, expression.condition),
+                self.visit_expression(arena, expression.when_true),
+                self.visit_expression(arena, expression.when_false)
+            )
+        }
+
+        fn visit_index_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &IndexExpressionSyntax,
+        ) -> String {
+            format!(
+                "{}{}{}]",
+                self.visit_expression(arena, expression.target),
+                expression.open_bracket_token.text(),
+                self.visit_expression(arena, expression.index)
+            )
+        }
+
+        fn visit_member_access_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &MemberAccessExpressionSyntax,
+        ) -> String {
+            format!(
+                "{}{}{}",
+                self.visit_expression(arena, expression.target),
+                expression.dot_token.text(),
+                expression.name_token.text()
+            )
+        }
+
+        fn visit_cast_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &CastExpressionSyntax,
+        ) -> String {
+            format!(
+                "({} as {})",
+                self.visit_expression(arena, expression.expression),
+                expression.type_token.text()
+            )
+        }
+
+        fn visit_call_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &CallExpressionSyntax,
+        ) -> String {
+            format!(
+                "{}({})",
+                expression.identifier.text(),
+                self.list(arena, expression.arguments.nodes())
+            )
+        }
+
+        fn visit_array_literal_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &ArrayLiteralExpressionSyntax,
+        ) -> String {
+            format!("[{}]", self.list(arena, expression.elements.nodes()))
+        }
+
+        fn visit_object_literal_expression(
+            &mut self,
+            arena: &SyntaxArena,
+            expression: &ObjectLiteralExpressionSyntax,
+        ) -> String {
+            let properties: Vec<String> = expression
+                .properties
+                .iter()
+                .map(|property| {
+                    format!(
+                        "{}: {}",
+                        property.name_token.text(),
+                        self.visit_expression(arena, property.value)
+                    )
+                })
+                .collect();
+            format!("{{ {} }}", properties.join(", "))
+        }
+
+        fn visit_bad_expression(&mut self, _: &SyntaxArena, _: &BadExpressionSyntax) -> String {
+            "<error>".to_string()
+        }
+    }
+
+    fn parenthesize(text: &str) -> String {
+        let tokens = Lexer::new(SourceText::new(text.to_string())).lex();
+        let tree = Parser::new(tokens, text.to_string()).parse();
+        let statements: Vec<String> = tree
+            .root()
+            .statements
+            .iter()
+            .map(|statement| Parenthesizer.visit_statement(tree.arena(), *statement))
+            .collect();
+        statements.join(" ")
+    }
+
+    #[test]
+    fn visits_the_nodes_an_implementation_chooses() {
+        let cases = [
+            ("1 + 2 * 3", "(1 + (2 * 3));"),
+            ("a || b && c ?? d", "((a || (b && c)) ?? d);"),
+            ("x = y += -z as float", "(x = (y += ((-z) as float)));"),
+            ("c ? 1 : d ? 2 : 3", "(c ? 1 : (d ? 2 : 3));"),
+            (
+                "o?.a[i++] == f(1, [2 + 3])",
+                "(o?.a[(i++)] == f(1, [(2 + 3)]));",
+            ),
+            (
+                "fn f(n) { if (n < 2) { return n; } else { return f(n - 1); } }",
+                "fn f(n) { if (n < 2) { return n; } else { return f((n - 1)); } }",
+            ),
+            ("while (true) { break; }", "while true { break; }"),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(parenthesize(input), expected, "{}", input);
+        }
+    }
+}
@@ -0,0 +1,661 @@
+use super::{
+    ArrayLiteralExpressionSyntax, AssignmentExpressionSyntax, AttributeArgumentSyntax,
+    AttributeSyntax, AttributedStatementSyntax, BadExpressionSyntax, BinaryExpressionSyntax,
+    BlockStatementSyntax, BreakStatementSyntax, CallExpressionSyntax, CastExpressionSyntax,
+    CompilationUnitSyntax, CompoundAssignmentExpressionSyntax, ConditionalExpressionSyntax,
+    ContinueStatementSyntax, ElseBodySyntax, ErrorStatementSyntax, ExpressionId,
+    ExpressionStatementSyntax, ExpressionSyntax, FunctionDeclarationSyntax,
+    IdentifierExpressionSyntax, IfStatementSyntax, IndexExpressionSyntax, InitializerSyntax,
+    LiteralExpressionSyntax, MemberAccessExpressionSyntax, ObjectLiteralExpressionSyntax,
+    ObjectPropertySyntax, ParameterSyntax, ParenthesizedExpressionSyntax, ReturnStatementSyntax,
+    StatementId, StatementSyntax, SyntaxArena, SyntaxToken, TypeClauseSyntax,
+    UnaryExpressionSyntax, UpdateExpressionSyntax, VariableDeclarationStatementSyntax,
+    WhileStatementSyntax,
+};
+
+/// Walks a syntax tree in source order. Every method descends into the
+/// node's children by default, so a pass only overrides the nodes it cares
+/// about and calls the matching `walk_*` function to keep descending.
+pub trait SyntaxWalker {
+    fn visit_compilation_unit(&mut self, arena: &SyntaxArena, unit: &CompilationUnitSyntax) {
+        walk_compilation_unit(self, arena, unit);
+    }
+
+    fn visit_statement(&mut self, arena: &SyntaxArena, statement: StatementId) {
+        walk_statement(self, arena, statement);
+    }
+
+    fn visit_block_statement(&mut self, arena: &SyntaxArena, block: &BlockStatementSyntax) {
+        walk_block_statement(self, arena, block);
+    }
+
+    fn visit_expression_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &ExpressionStatementSyntax,
+    ) {
+        walk_expression_statement(self, arena, statement);
+    }
+
+    fn visit_variable_declaration(
+        &mut self,
+        arena: &SyntaxArena,
+        declaration: &VariableDeclarationStatementSyntax,
+    ) {
+        walk_variable_declaration(self, arena, declaration);
+    }
+
+    fn visit_if_statement(&mut self, arena: &SyntaxArena, statement: &IfStatementSyntax) {
+        walk_if_statement(self, arena, statement);
+    }
+
+    fn visit_function_declaration(
+        &mut self,
+        arena: &SyntaxArena,
+        declaration: &FunctionDeclarationSyntax,
+    ) {
+        walk_function_declaration(self, arena, declaration);
+    }
+
+    fn visit_parameter(&mut self, arena: &SyntaxArena, parameter: &ParameterSyntax) {
+        walk_parameter(self, arena, parameter);
+    }
+
+    fn visit_type_clause(&mut self, _arena: &SyntaxArena, type_clause: &TypeClauseSyntax) {
+        self.visit_token(&type_clause.colon_token);
+        self.visit_token(&type_clause.type_token);
+        if let Some(question_mark_token) = &type_clause.question_mark_token {
+            self.visit_token(question_mark_token);
+        }
+    }
+
+    fn visit_initializer(&mut self, arena: &SyntaxArena, initializer: &InitializerSyntax) {
+        self.visit_token(&initializer.equals_token);
+        self.visit_expression(arena, initializer.expression);
+    }
+
+    fn visit_return_statement(&mut self, arena: &SyntaxArena, statement: &ReturnStatementSyntax) {
+        walk_return_statement(self, arena, statement);
+    }
+
+    fn visit_while_statement(&mut self, arena: &SyntaxArena, statement: &WhileStatementSyntax) {
+        walk_while_statement(self, arena, statement);
+    }
+
+    fn visit_break_statement(&mut self, _arena: &SyntaxArena, statement: &BreakStatementSyntax) {
+        self.visit_token(&statement.keyword);
+        self.visit_token(&statement.semicolon);
+    }
+
+    fn visit_continue_statement(
+        &mut self,
+        _arena: &SyntaxArena,
+        statement: &ContinueStatementSyntax,
+    ) {
+        self.visit_token(&statement.keyword);
+        self.visit_token(&statement.semicolon);
+    }
+
+    fn visit_attributed_statement(
+        &mut self,
+        arena: &SyntaxArena,
+        statement: &AttributedStatementSyntax,
+    ) {
+        walk_attributed_statement(self, arena, statement);
+    }
+
+    fn visit_attribute(&mut self, arena: &SyntaxArena, attribute: &AttributeSyntax) {
+        walk_attribute(self, arena, attribute);
+    }
+
+    fn visit_attribute_argument(
+        &mut self,
+        _arena: &SyntaxArena,
+        argument: &AttributeArgumentSyntax,
+    ) {
+        for token in &argument.tokens {
+            self.visit_token(token);
+        }
+    }
+
+    fn visit_error_statement(&mut self, _arena: &SyntaxArena, statement: &ErrorStatementSyntax) {
+        for token in &statement.tokens {
+            self.visit_token(token);
+        }
+    }
+
+    fn visit_expression(&mut self, arena: &SyntaxArena, expression: ExpressionId) {
+        walk_expression(self, arena, expression);
+    }
+
+    fn visit_binary_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &BinaryExpressionSyntax,
+    ) {
+        walk_binary_expression(self, arena, expression);
+    }
+
+    fn visit_parenthesized_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ParenthesizedExpressionSyntax,
+    ) {
+        walk_parenthesized_expression(self, arena, expression);
+    }
+
+    fn visit_unary_expression(&mut self, arena: &SyntaxArena, expression: &UnaryExpressionSyntax) {
+        walk_unary_expression(self, arena, expression);
+    }
+
+    fn visit_literal_expression(
+        &mut self,
+        _arena: &SyntaxArena,
+        expression: &LiteralExpressionSyntax,
+    ) {
+        self.visit_token(&expression.literal_token);
+    }
+
+    fn visit_identifier_expression(
+        &mut self,
+        _arena: &SyntaxArena,
+        expression: &IdentifierExpressionSyntax,
+    ) {
+        self.visit_token(&expression.identifier);
+    }
+
+    fn visit_assignment_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &AssignmentExpressionSyntax,
+    ) {
+        walk_assignment_expression(self, arena, expression);
+    }
+
+    fn visit_compound_assignment_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &CompoundAssignmentExpressionSyntax,
+    ) {
+        walk_compound_assignment_expression(self, arena, expression);
+    }
+
+    fn visit_update_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &UpdateExpressionSyntax,
+    ) {
+        walk_update_expression(self, arena, expression);
+    }
+
+    fn visit_conditional_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ConditionalExpressionSyntax,
+    ) {
+        walk_conditional_expression(self, arena, expression);
+    }
+
+    fn visit_index_expression(&mut self, arena: &SyntaxArena, expression: &IndexExpressionSyntax) {
+        walk_index_expression(self, arena, expression);
+    }
+
+    fn visit_member_access_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &MemberAccessExpressionSyntax,
+    ) {
+        walk_member_access_expression(self, arena, expression);
+    }
+
+    fn visit_cast_expression(&mut self, arena: &SyntaxArena, expression: &CastExpressionSyntax) {
+        walk_cast_expression(self, arena, expression);
+    }
+
+    fn visit_call_expression(&mut self, arena: &SyntaxArena, expression: &CallExpressionSyntax) {
+        walk_call_expression(self, arena, expression);
+    }
+
+    fn visit_array_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ArrayLiteralExpressionSyntax,
+    ) {
+        walk_array_literal_expression(self, arena, expression);
+    }
+
+    fn visit_object_literal_expression(
+        &mut self,
+        arena: &SyntaxArena,
+        expression: &ObjectLiteralExpressionSyntax,
+    ) {
+        walk_object_literal_expression(self, arena, expression);
+    }
+
+    fn visit_object_property(&mut self, arena: &SyntaxArena, property: &ObjectPropertySyntax) {
+        walk_object_property(self, arena, property);
+    }
+
+    fn visit_bad_expression(&mut self, _arena: &SyntaxArena, expression: &BadExpressionSyntax) {
+        for token in &expression.tokens {
+            self.visit_token(token);
+        }
+    }
+
+    fn visit_token(&mut self, _token: &SyntaxToken) {}
+}
+
+pub fn walk_compilation_unit<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    unit: &CompilationUnitSyntax,
+) {
+    for statement in &unit.statements {
+        walker.visit_statement(arena, *statement);
+    }
+    walker.visit_token(&unit.end_of_file_token);
+}
+
+pub fn walk_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    statement: StatementId,
+) {
+    match &arena[statement] {
+        StatementSyntax::BlockStatementSyntax(block) => walker.visit_block_statement(arena, block),
+        StatementSyntax::ExpressionStatementSyntax(statement) => {
+            walker.visit_expression_statement(arena, statement)
+        }
+        StatementSyntax::VariableDeclarationStatementSyntax(declaration) => {
+            walker.visit_variable_declaration(arena, declaration)
+        }
+        StatementSyntax::IfStatementSyntax(statement) => {
+            walker.visit_if_statement(arena, statement)
+        }
+        StatementSyntax::FunctionDeclarationSyntax(declaration) => {
+            walker.visit_function_declaration(arena, declaration)
+        }
+        StatementSyntax::ReturnStatementSyntax(statement) => {
+            walker.visit_return_statement(arena, statement)
+        }
+        StatementSyntax::WhileStatementSyntax(statement) => {
+            walker.visit_while_statement(arena, statement)
+        }
+        StatementSyntax::BreakStatementSyntax(statement) => {
+            walker.visit_break_statement(arena, statement)
+        }
+        StatementSyntax::ContinueStatementSyntax(statement) => {
+            walker.visit_continue_statement(arena, statement)
+        }
+        StatementSyntax::AttributedStatementSyntax(statement) => {
+            walker.visit_attributed_statement(arena, statement)
+        }
+        StatementSyntax::ErrorStatementSyntax(statement) => {
+            walker.visit_error_statement(arena, statement)
+        }
+    }
+}
+
+pub fn walk_attributed_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    statement: &AttributedStatementSyntax,
+) {
+    for attribute in &statement.attributes {
+        walker.visit_attribute(arena, attribute);
+    }
+    walker.visit_statement(arena, statement.statement);
+}
+
+pub fn walk_attribute<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    attribute: &AttributeSyntax,
+) {
+    walker.visit_token(&attribute.at_token);
+    walker.visit_token(&attribute.identifier);
+    walker.visit_token(&attribute.open_parenthesis);
+    for (index, argument) in attribute.arguments.iter().enumerate() {
+        walker.visit_attribute_argument(arena, argument);
+        if let Some(comma) = attribute.arguments.separators().get(index) {
+            walker.visit_token(comma);
+        }
+    }
+    walker.visit_token(&attribute.close_parenthesis);
+}
+
+pub fn walk_block_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    block: &BlockStatementSyntax,
+) {
+    walker.visit_token(&block.open_brace);
+    for statement in &block.statements {
+        walker.visit_statement(arena, *statement);
+    }
+    walker.visit_token(&block.close_brace);
+}
+
+pub fn walk_expression_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    statement: &ExpressionStatementSyntax,
+) {
+    walker.visit_expression(arena, statement.expression);
+    if let Some(semicolon) = &statement.semicolon {
+        walker.visit_token(semicolon);
+    }
+}
+
+pub fn walk_variable_declaration<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    declaration: &VariableDeclarationStatementSyntax,
+) {
+    walker.visit_token(&declaration.keyword);
+    if let Some(mut_keyword) = &declaration.mut_keyword {
+        walker.visit_token(mut_keyword);
+    }
+    walker.visit_token(&declaration.identifier);
+    if let Some(type_clause) = &declaration.type_clause {
+        walker.visit_type_clause(arena, type_clause);
+    }
+    if let Some(initializer) = &declaration.initializer {
+        walker.visit_initializer(arena, initializer);
+    }
+    walker.visit_token(&declaration.semicolon);
+}
+
+pub fn walk_if_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    statement: &IfStatementSyntax,
+) {
+    walker.visit_token(&statement.keyword);
+    walker.visit_token(&statement.open_parenthesis);
+    walker.visit_expression(arena, statement.condition);
+    walker.visit_token(&statement.close_parenthesis);
+    walker.visit_block_statement(arena, &statement.body);
+    if let Some(else_clause) = &statement.else_clause {
+        walker.visit_token(&else_clause.else_keyword);
+        match &else_clause.body {
+            ElseBodySyntax::Block(block) => walker.visit_block_statement(arena, block),
+            ElseBodySyntax::If(statement) => walker.visit_if_statement(arena, statement),
+        }
+    }
+}
+
+pub fn walk_function_declaration<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    declaration: &FunctionDeclarationSyntax,
+) {
+    walker.visit_token(&declaration.fn_keyword);
+    walker.visit_token(&declaration.identifier);
+    walker.visit_token(&declaration.open_parenthesis);
+    for (index, parameter) in declaration.parameters.iter().enumerate() {
+        walker.visit_parameter(arena, parameter);
+        if let Some(comma) = declaration.parameters.separators().get(index) {
+            walker.visit_token(comma);
+        }
+    }
+    walker.visit_token(&declaration.close_parenthesis);
+    if let Some(return_type) = &declaration.return_type {
+        walker.visit_type_clause(arena, return_type);
+    }
+    walker.visit_block_statement(arena, &declaration.body);
+}
+
+pub fn walk_parameter<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    parameter: &ParameterSyntax,
+) {
+    walker.visit_token(&parameter.identifier);
+    if let Some(type_clause) = &parameter.type_clause {
+        walker.visit_type_clause(arena, type_clause);
+    }
+}
+
+pub fn walk_return_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    statement: &ReturnStatementSyntax,
+) {
+    walker.visit_token(&statement.return_keyword);
+    if let Some(expression) = statement.expression {
+        walker.visit_expression(arena, expression);
+    }
+    walker.visit_token(&statement.semicolon);
+}
+
+pub fn walk_while_statement<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    statement: &WhileStatementSyntax,
+) {
+    walker.visit_token(&statement.while_keyword);
+    walker.visit_token(&statement.open_parenthesis);
+    walker.visit_expression(arena, statement.condition);
+    walker.visit_token(&statement.close_parenthesis);
+    walker.visit_block_statement(arena, &statement.body);
+}
+
+pub fn walk_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: ExpressionId,
+) {
+    match &arena[expression] {
+        ExpressionSyntax::BinaryExpressionSyntax(expression) => {
+            walker.visit_binary_expression(arena, expression)
+        }
+        ExpressionSyntax::ParenthesizedExpressionSyntax(expression) => {
+            walker.visit_parenthesized_expression(arena, expression)
+        }
+        ExpressionSyntax::UnaryExpressionSyntax(expression) => {
+            walker.visit_unary_expression(arena, expression)
+        }
+        ExpressionSyntax::LiteralExpressionSyntax(expression) => {
+            walker.visit_literal_expression(arena, expression)
+        }
+        ExpressionSyntax::IdentifierExpressionSyntax(expression) => {
+            walker.visit_identifier_expression(arena, expression)
+        }
+        ExpressionSyntax::AssignmentExpressionSyntax(expression) => {
+            walker.visit_assignment_expression(arena, expression)
+        }
+        ExpressionSyntax::CompoundAssignmentExpressionSyntax(expression) => {
+            walker.visit_compound_assignment_expression(arena, expression)
+        }
+        ExpressionSyntax::UpdateExpressionSyntax(expression) => {
+            walker.visit_update_expression(arena, expression)
+        }
+        ExpressionSyntax::ConditionalExpressionSyntax(expression) => {
+            walker.visit_conditional_expression(arena, expression)
+        }
+        ExpressionSyntax::IndexExpressionSyntax(expression) => {
+            walker.visit_index_expression(arena, expression)
+        }
+        ExpressionSyntax::MemberAccessExpressionSyntax(expression) => {
+            walker.visit_member_access_expression(arena, expression)
+        }
+        ExpressionSyntax::CastExpressionSyntax(expression) => {
+            walker.visit_cast_expression(arena, expression)
+        }
+        ExpressionSyntax::CallExpressionSyntax(expression) => {
+            walker.visit_call_expression(arena, expression)
+        }
+        ExpressionSyntax::ArrayLiteralExpressionSyntax(expression) => {
+            walker.visit_array_literal_expression(arena, expression)
+        }
+        ExpressionSyntax::ObjectLiteralExpressionSyntax(expression) => {
+            walker.visit_object_literal_expression(arena, expression)
+        }
+        ExpressionSyntax::BadExpressionSyntax(expression) => {
+            walker.visit_bad_expression(arena, expression)
+        }
+    }
+}
+
+pub fn walk_binary_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &BinaryExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.left);
+    walker.visit_token(&expression.operator);
+    walker.visit_expression(arena, expression.right);
+}
+
+pub fn walk_parenthesized_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &ParenthesizedExpressionSyntax,
+) {
+    walker.visit_token(&expression.open_parenthesis_token);
+    walker.visit_expression(arena, expression.expression);
+    walker.visit_token(&expression.close_parenthesis_token);
+}
+
+pub fn walk_unary_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &UnaryExpressionSyntax,
+) {
+    walker.visit_token(&expression.operator);
+    walker.visit_expression(arena, expression.operand);
+}
+
+pub fn walk_assignment_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &AssignmentExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.target);
+    walker.visit_token(&expression.equals_token);
+    walker.visit_expression(arena, expression.value);
+}
+
+pub fn walk_compound_assignment_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &CompoundAssignmentExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.target);
+    walker.visit_token(&expression.operator_token);
+    walker.visit_expression(arena, expression.value);
+}
+
+pub fn walk_update_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &UpdateExpressionSyntax,
+) {
+    if expression.is_prefix {
+        walker.visit_token(&expression.operator_token);
+        walker.visit_expression(arena, expression.operand);
+    } else {
+        walker.visit_expression(arena, expression.operand);
+        walker.visit_token(&expression.operator_token);
+    }
+}
+
+pub fn walk_conditional_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &ConditionalExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.condition);
+    walker.visit_token(&expression.question_mark_token);
+    walker.visit_expression(arena, expression.when_true);
+    walker.visit_token(&expression.colon_token);
+    walker.visit_expression(arena, expression.when_false);
+}
+
+pub fn walk_index_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &IndexExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.target);
+    walker.visit_token(&expression.open_bracket_token);
+    walker.visit_expression(arena, expression.index);
+    walker.visit_token(&expression.close_bracket_token);
+}
+
+pub fn walk_member_access_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &MemberAccessExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.target);
+    walker.visit_token(&expression.dot_token);
+    walker.visit_token(&expression.name_token);
+}
+
+pub fn walk_cast_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &CastExpressionSyntax,
+) {
+    walker.visit_expression(arena, expression.expression);
+    walker.visit_token(&expression.as_keyword);
+    walker.visit_token(&expression.type_token);
+}
+
+pub fn walk_call_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &CallExpressionSyntax,
+) {
+    walker.visit_token(&expression.identifier);
+    walker.visit_token(&expression.open_parenthesis_token);
+    for (index, argument) in expression.arguments.iter().enumerate() {
+        walker.visit_expression(arena, *argument);
+        if let Some(comma) = expression.arguments.separators().get(index) {
+            walker.visit_token(comma);
+        }
+    }
+    walker.visit_token(&expression.close_parenthesis_token);
+}
+
+pub fn walk_array_literal_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &ArrayLiteralExpressionSyntax,
+) {
+    walker.visit_token(&expression.open_bracket_token);
+    for (index, element) in expression.elements.iter().enumerate() {
+        walker.visit_expression(arena, *element);
+        if let Some(comma) = expression.elements.separators().get(index) {
+            walker.visit_token(comma);
+        }
+    }
+    walker.visit_token(&expression.close_bracket_token);
+}
+
+pub fn walk_object_literal_expression<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    expression: &ObjectLiteralExpressionSyntax,
+) {
+    walker.visit_token(&expression.open_brace_token);
+    for (index, property) in expression.properties.iter().enumerate() {
+        walker.visit_object_property(arena, property);
+        if let Some(comma) = expression.properties.separators().get(index) {
+            walker.visit_token(comma);
+        }
+    }
+    walker.visit_token(&expression.close_brace_token);
+}
+
+pub fn walk_object_property<W: SyntaxWalker + ?Sized>(
+    walker: &mut W,
+    arena: &SyntaxArena,
+    property: &ObjectPropertySyntax,
+) {
+    walker.visit_token(&property.name_token);
+    walker.visit_token(&property.colon_token);
+    walker.visit_expression(arena, property.value);
+}